@@ -14,6 +14,10 @@ pub enum D21Error {
     DescriptionTooLong,
     #[msg("Label too long")]
     LabelTooLong,
+    #[msg("Label too short")]
+    LabelTooShort,
+    #[msg("Label does not fit in the option account")]
+    LabelTooLarge,
     #[msg("Invalid sentiment")]
     InvalidSentiment,
     #[msg("Out of positive credits")]
@@ -33,10 +37,18 @@ pub enum D21Error {
     PollIdMismatch,
     #[msg("Plus Credit is zero")]
     PlusCreditIsZero,
+    // minus_credits == 0 is a valid config that disables negative votes;
+    // reserved for a config that asks for negatives without any credits
     #[msg("Minus Credit is zero")]
     MinusCreditIsZero,
     #[msg("Invalid voting time window")]
     InvalidTimeWindow,
+    #[msg("Finalize grace period must not be negative")]
+    InvalidFinalizeGrace,
+    #[msg("Minimum label length must be between 1 and the maximum label length")]
+    InvalidMinLabelLen,
+    #[msg("Negative vote ratio must be at least 1")]
+    InvalidNegRatio,
 
     #[msg("Can't add an option, voting is already started")]
     VotingStarted,
@@ -46,7 +58,113 @@ pub enum D21Error {
     LabelAlreadyUsed,
     #[msg("Label seed/hash mismatch")]
     LabelSeedMismatch,
+    #[msg("Option description too long")]
+    OptionDescriptionTooLong,
+    #[msg("Option image URI too long")]
+    ImageUriTooLong,
 
     #[msg("Option dont't belong to this poll")]
-    PollMismatch
+    PollMismatch,
+
+    #[msg("Too many tags")]
+    TooManyTags,
+    #[msg("Tag too long")]
+    TagTooLong,
+    #[msg("Tag is empty")]
+    TagEmpty,
+    #[msg("Tag is listed twice")]
+    DuplicateTag,
+    #[msg("Tag index account mismatch")]
+    TagIndexMismatch,
+
+    #[msg("Vote note too long")]
+    NoteTooLong,
+
+    #[msg("Option account passed more than once")]
+    DuplicateOptionAccount,
+    #[msg("Not every option of the poll was passed")]
+    OptionCountMismatch,
+    #[msg("Poll has no options")]
+    NoOptions,
+
+    #[msg("Signer lacks the required role")]
+    InsufficientRole,
+    #[msg("Too many admins")]
+    TooManyAdmins,
+    #[msg("Admin not found")]
+    AdminNotFound,
+
+    #[msg("Voter account passed more than once")]
+    DuplicateVoterAccount,
+
+    #[msg("Options are sealed")]
+    OptionsSealed,
+
+    #[msg("Sample stride must be positive")]
+    InvalidStride,
+
+    #[msg("Voting period has not ended yet")]
+    VotingNotOver,
+
+    #[msg("Poll results are not final yet")]
+    NotFinalized,
+
+    #[msg("Finalize window has closed")]
+    FinalizeWindowClosed,
+
+    #[msg("Batch must hold between 1 and 10 votes")]
+    InvalidBatchSize,
+
+    #[msg("Batch accounts do not match the votes")]
+    BatchAccountMismatch,
+
+    #[msg("Options must be added and removed at the end of the list")]
+    NonContiguousOptionIndex,
+
+    #[msg("Poll does not have enough options to vote yet")]
+    NotEnoughOptions,
+
+    #[msg("Instruction is not supported in this voting mode")]
+    UnsupportedVotingMode,
+
+    #[msg("Reveal phase has not started")]
+    RevealNotStarted,
+    #[msg("Revealed vote does not match the commitment")]
+    CommitmentMismatch,
+
+    #[msg("Voter is not on the poll allowlist")]
+    NotOnAllowlist,
+
+    #[msg("Cannot delegate votes to yourself")]
+    SelfDelegation,
+
+    #[msg("Poll has not been activated with begin_poll")]
+    PollNotActive,
+    #[msg("Poll is already active")]
+    PollAlreadyActive,
+
+    #[msg("Unsupported account version")]
+    UnsupportedVersion,
+
+    #[msg("Clock grace period must not be negative")]
+    InvalidGraceSecs,
+
+    #[msg("Poll is paused")]
+    PollPaused,
+    #[msg("Poll is not paused")]
+    PollNotPaused,
+
+    #[msg("Vote cooldown must not be negative")]
+    InvalidVoteCooldown,
+    #[msg("Wait for the vote cooldown to pass before voting again")]
+    VoteCooldownActive,
+
+    #[msg("Voter already holds the maximum number of votes for this poll")]
+    TooManyVotes,
+
+    #[msg("Poll ID belonged to a closed poll and cannot be reused")]
+    PollIdClosed,
+
+    #[msg("Poll gates voters by allowlist_root")]
+    AllowlistRootSet,
 }
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::states::{Admins, AllowedVoter, Poll, Role, Versioned, ACCOUNT_VERSION};
+
+/// Adds `voter` to the poll allowlist and bumps `allowed_count`.
+/// Requires FullAdmin; adding the same voter twice fails on the existing PDA.
+/// Once a poll has an entry only allowlisted voters can vote, see
+/// Poll::check_allowlist. Entries are only accepted before begin_poll, so
+/// every counted voter is allowlisted, and never on a poll with an
+/// `allowlist_root`.
+pub fn handler(ctx: Context<AddAllowedVoter>, voter: Pubkey) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    let poll_key = ctx.accounts.poll.key();
+    ctx.accounts.poll.require_role(
+        &poll_key,
+        ctx.accounts.admins.as_deref(),
+        &ctx.accounts.signer.key(),
+        Role::FullAdmin,
+    )?;
+    require!(ctx.accounts.poll.allowlist_root.is_none(), D21Error::AllowlistRootSet);
+    require!(!ctx.accounts.poll.active, D21Error::PollAlreadyActive);
+
+    let entry = &mut ctx.accounts.allowed_voter;
+    entry.poll = poll_key;
+    entry.voter = voter;
+
+    let poll = &mut ctx.accounts.poll;
+    poll.allowed_count = poll.allowed_count.checked_add(1).ok_or(D21Error::MathOverflow)?;
+
+    emit!(AllowedVoterAdded { poll: poll_key, voter, allowed_count: poll.allowed_count });
+    Ok(())
+}
+
+#[event]
+pub struct AllowedVoterAdded {
+    pub poll: Pubkey,
+    pub voter: Pubkey,
+    pub allowed_count: u32,
+}
+
+#[derive(Accounts)]
+#[instruction(voter: Pubkey)]
+pub struct AddAllowedVoter<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+    )]
+    pub poll: Account<'info, Poll>,
+
+    pub admins: Option<Account<'info, Admins>>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + AllowedVoter::INIT_SPACE,
+        seeds = [b"allowed", poll.key().as_ref(), voter.as_ref()],
+        bump
+    )]
+    pub allowed_voter: Account<'info, AllowedVoter>,
+
+    pub system_program: Program<'info, System>,
+}
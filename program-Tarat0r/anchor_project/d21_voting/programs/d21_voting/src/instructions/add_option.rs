@@ -1,23 +1,45 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::hash;
 use crate::errors::D21Error;
-use crate::states::{LabelGuard, MAX_LABEL, OptionNode, Poll};
+use crate::labels;
+use crate::states::{Admins, LabelGuard, Versioned, ACCOUNT_VERSION, MAX_LABEL, MAX_LABEL_BYTES, MAX_OPTION_DESC, MAX_URI, OptionNode, Poll, Role};
 
-pub fn handler(ctx: Context<AddOption>, index: u16, label: String, label_seed: [u8; 32]) -> Result<()> {
+pub fn handler(
+    ctx: Context<AddOption>,
+    index: u16,
+    label: String,
+    label_seed: [u8; 32],
+    description: String,
+    image_uri: String,
+) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
     
     let poll = &mut ctx.accounts.poll;
+    poll.require_role(
+        &poll.key(),
+        ctx.accounts.admins.as_deref(),
+        &ctx.accounts.authority.key(),
+        Role::OptionManager,
+    )?;
     
-    // no edits after start
+    // no edits after the ballot was sealed or voting started
+    require!(!poll.options_sealed, D21Error::OptionsSealed);
+    require!(!poll.active, D21Error::VotingStarted);
     require!(poll.start_ts > Clock::get()?.unix_timestamp, D21Error::VotingStarted);
     
     let trimmed = label.trim();
     require!(!trimmed.is_empty(), D21Error::LabelEmpty);
-    require!(trimmed.len() <= MAX_LABEL, D21Error::LabelTooLong);
+    let chars = trimmed.chars().count();
+    require!(chars >= poll.min_label_len as usize, D21Error::LabelTooShort);
+    require!(chars <= MAX_LABEL, D21Error::LabelTooLong);
+    require!(trimmed.len() <= MAX_LABEL_BYTES, D21Error::LabelTooLarge);
+    require!(description.len() <= MAX_OPTION_DESC, D21Error::OptionDescriptionTooLong);
+    require!(image_uri.len() <= MAX_URI, D21Error::ImageUriTooLong);
 
     // Canonicalize and verify the seed matches canonical label
-    let canonical = trimmed.to_lowercase();
-    let expected = hash::hash(canonical.as_bytes()).to_bytes();
-    require!(label_seed == expected, D21Error::LabelSeedMismatch);
+    require!(label_seed == labels::label_seed(trimmed), D21Error::LabelSeedMismatch);
+
+    // options are appended in order, so options_count is an exact count
+    require!(index == poll.options_count, D21Error::NonContiguousOptionIndex);
 
     // Uniqueness: guard must be unused before
     let guard = &mut ctx.accounts.label_guard;
@@ -34,9 +56,13 @@ pub fn handler(ctx: Context<AddOption>, index: u16, label: String, label_seed: [
     option.label = trimmed.to_string();
     option.plus_votes = 0;
     option.minus_votes = 0;
-    poll.options_count = poll.options_count.max(index.saturating_add(1));
+    option.abstain_votes = 0;
+    option.description = description.clone();
+    option.image_uri = image_uri.clone();
+    option.version = ACCOUNT_VERSION;
+    poll.options_count = poll.options_count.checked_add(1).ok_or(D21Error::MathOverflow)?;
 
-    emit!(OptionAdded { poll: poll.key(), index, label });
+    emit!(OptionAdded { poll: poll.key(), index, label, description, image_uri });
     Ok(())
 }
 
@@ -45,6 +71,8 @@ pub struct OptionAdded {
     pub poll: Pubkey,
     pub index: u16,
     pub label: String,
+    pub description: String,
+    pub image_uri: String,
 }
 
 #[derive(Accounts)]
@@ -55,17 +83,19 @@ pub struct AddOption<'info> {
 
     #[account(
         mut,
-        seeds = [b"poll", poll.authority.as_ref(), &poll.poll_id.to_le_bytes()],
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
         bump,
-        constraint = poll.authority == authority.key() @ D21Error::Unauthorized,
         constraint = !poll.ended @ D21Error::VotingClosed
     )]
     pub poll: Account<'info, Poll>,
 
+    // only needed when the signer is not poll.authority
+    pub admins: Option<Account<'info, Admins>>,
+
     #[account(
         init_if_needed,
         payer = authority,
-        space = LabelGuard::SPACE,
+        space = 8 + LabelGuard::INIT_SPACE,
         seeds = [b"option_label", poll.key().as_ref(), &label_seed],
         bump
     )]
@@ -74,7 +104,7 @@ pub struct AddOption<'info> {
     #[account(
         init,
         payer = authority,
-        space = OptionNode::SPACE,
+        space = 8 + OptionNode::INIT_SPACE,
         seeds = [b"option", poll.key().as_ref(), &index.to_le_bytes()],
         bump
     )]
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::ranking;
+use crate::states::{Poll, ResultAttestation, ResultsSnapshot, Versioned, ACCOUNT_VERSION};
+
+/// Writes the poll's final result into a ResultAttestation once
+/// `finalize_poll` has stored the winner, stamped with the finalize time from
+/// its ResultsSnapshot. All of the poll's options must be passed in
+/// `remaining_accounts`, in any order. The attestation can only be created
+/// once, so it cannot be rewritten later.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, AttestResult<'info>>) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    let poll = &ctx.accounts.poll;
+    require!(poll.winner_index.is_some(), D21Error::NotFinalized);
+    // finalize_poll writes the snapshot together with winner_index
+    let snapshot = ResultsSnapshot::try_deserialize(&mut &ctx.accounts.snapshot.try_borrow_data()?[..])?;
+
+    let standings = ranking::load_standings(&poll.key(), ctx.remaining_accounts)?;
+    require!(
+        standings.len() == poll.options_count as usize,
+        D21Error::OptionCountMismatch
+    );
+    let winner = ranking::winner(&standings).ok_or(D21Error::NoOptions)?;
+
+    let attestation = &mut ctx.accounts.attestation;
+    attestation.poll = poll.key();
+    attestation.poll_id = poll.poll_id;
+    attestation.winner_index = winner.index;
+    attestation.winner_net = winner.net();
+    attestation.scores_hash = ranking::scores_hash(&standings);
+    attestation.finalized_ts = snapshot.finalized_ts;
+    attestation.seal = attestation.compute_seal();
+
+    emit!(ResultAttested {
+        poll: attestation.poll,
+        attestation: attestation.key(),
+        winner_index: attestation.winner_index,
+        scores_hash: attestation.scores_hash,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct ResultAttested {
+    pub poll: Pubkey,
+    pub attestation: Pubkey,
+    pub winner_index: u16,
+    pub scores_hash: [u8; 32],
+}
+
+#[derive(Accounts)]
+pub struct AttestResult<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+    )]
+    pub poll: Account<'info, Poll>,
+
+    /// CHECK: finalize_poll's ResultsSnapshot, deserialized once the poll is finalized
+    #[account(
+        seeds = [b"snapshot", poll.key().as_ref()],
+        bump
+    )]
+    pub snapshot: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ResultAttestation::INIT_SPACE,
+        seeds = [b"attestation", poll.key().as_ref()],
+        bump
+    )]
+    pub attestation: Account<'info, ResultAttestation>,
+
+    pub system_program: Program<'info, System>,
+}
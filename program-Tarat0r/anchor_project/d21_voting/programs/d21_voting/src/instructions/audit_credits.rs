@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::states::{Poll, Voter, Versioned, ACCOUNT_VERSION};
+
+/// Sums the credits used by the Voter accounts passed in `remaining_accounts`
+/// and checks them against what the poll issued. Mutates nothing.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, AuditCredits<'info>>) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    let poll = &ctx.accounts.poll;
+    let poll_key = poll.key();
+
+    let mut seen: Vec<Pubkey> = Vec::with_capacity(ctx.remaining_accounts.len());
+    let mut total_plus: u64 = 0;
+    let mut total_minus: u64 = 0;
+    let mut within_bounds = true;
+
+    for info in ctx.remaining_accounts.iter() {
+        let voter = Account::<Voter>::try_from(info)?;
+        voter.assert_version(ACCOUNT_VERSION)?;
+        require_keys_eq!(voter.poll, poll_key, D21Error::PollMismatch);
+        require!(!seen.contains(&info.key()), D21Error::DuplicateVoterAccount);
+        seen.push(info.key());
+
+        within_bounds &= voter.used_plus <= poll.plus_credits && voter.used_minus <= poll.minus_credits;
+        total_plus += voter.used_plus as u64;
+        total_minus += voter.used_minus as u64;
+    }
+
+    let voter_count = seen.len() as u64;
+    let consistent = within_bounds
+        && total_plus <= voter_count * poll.plus_credits as u64
+        && total_minus <= voter_count * poll.minus_credits as u64;
+
+    emit!(CreditAudit {
+        poll: poll_key,
+        voter_count,
+        total_plus,
+        total_minus,
+        consistent,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct CreditAudit {
+    pub poll: Pubkey,
+    pub voter_count: u64,
+    pub total_plus: u64,
+    pub total_minus: u64,
+    pub consistent: bool,
+}
+
+#[derive(Accounts)]
+pub struct AuditCredits<'info> {
+    #[account(
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+    )]
+    pub poll: Account<'info, Poll>,
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::states::{Poll, Versioned, ACCOUNT_VERSION};
+
+/// Opens voting once `start_ts` has passed. Until then, and without this
+/// call, no vote is accepted; afterwards the ballot can't be edited.
+pub fn handler(ctx: Context<BeginPoll>) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    let now = Clock::get()?.unix_timestamp;
+    let poll = &mut ctx.accounts.poll;
+    require!(now >= poll.start_ts, D21Error::VotingNotStarted);
+    require!(now <= poll.end_ts, D21Error::VotingClosed);
+
+    poll.active = true;
+
+    emit!(PollActivated { poll: poll.key(), activated_ts: now });
+    Ok(())
+}
+
+#[event]
+pub struct PollActivated {
+    pub poll: Pubkey,
+    pub activated_ts: i64,
+}
+
+#[derive(Accounts)]
+pub struct BeginPoll<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+        has_one = authority @ D21Error::Unauthorized,
+        constraint = !poll.ended @ D21Error::VotingClosed,
+        constraint = !poll.active @ D21Error::PollAlreadyActive,
+    )]
+    pub poll: Account<'info, Poll>,
+}
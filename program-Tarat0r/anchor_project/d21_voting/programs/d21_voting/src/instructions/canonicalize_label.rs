@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use crate::labels;
+
+/// View: emits the canonical form of `label` and the seed `add_option`
+/// expects for it, so clients never have to reimplement canonicalization.
+pub fn handler(_ctx: Context<CanonicalizeLabel>, label: String) -> Result<()> {
+    let canonical = labels::canonicalize_label(&label);
+    let label_seed = labels::label_seed(&label);
+    emit!(LabelCanonicalized { label, canonical, label_seed });
+    Ok(())
+}
+
+#[event]
+pub struct LabelCanonicalized {
+    pub label: String,
+    pub canonical: String,
+    pub label_seed: [u8; 32],
+}
+
+#[derive(Accounts)]
+pub struct CanonicalizeLabel {}
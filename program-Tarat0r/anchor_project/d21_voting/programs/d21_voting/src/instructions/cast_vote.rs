@@ -1,61 +1,199 @@
 use anchor_lang::prelude::*;
 use crate::errors::D21Error;
-use crate::states::{OptionNode, Poll, Receipt, Voter};
+use crate::ranking::{self, Standing};
+use crate::states::{AllowedVoter, Delegation, OptionNode, Poll, Receipt, Versioned, Voter, VotingMode, ACCOUNT_VERSION, MAX_NOTE};
 
-pub fn handler(ctx: Context<CastVote>, _index: u16, sentiment: i8) -> Result<()> {
+/// Every rule a vote has to pass besides account ownership and the note
+/// length. Shared with `precheck_vote` so both paths always agree.
+/// `weight` is the weight already on the voter's receipt for this option,
+/// 0 before the first vote; only quadratic polls look at it.
+pub fn check_vote(
+    poll: &Poll,
+    voter: &Voter,
+    already_voted: bool,
+    weight: u16,
+    sentiment: i8,
+    now: i64,
+) -> std::result::Result<(), D21Error> {
+    if poll.ended || now > poll.vote_deadline() {
+        return Err(D21Error::VotingClosed);
+    }
+    if now < poll.start_ts {
+        return Err(D21Error::VotingNotStarted);
+    }
+    if !poll.active {
+        return Err(D21Error::PollNotActive);
+    }
+    if poll.paused {
+        return Err(D21Error::PollPaused);
+    }
+    if poll.vote_cooldown_secs > 0 && now.saturating_sub(voter.last_vote_ts) < poll.vote_cooldown_secs {
+        return Err(D21Error::VoteCooldownActive);
+    }
+    if poll.options_count < poll.min_options {
+        return Err(D21Error::NotEnoughOptions);
+    }
+    if !matches!(sentiment, 1 | 0 | -1) {
+        return Err(D21Error::InvalidSentiment);
+    }
+    if poll.mode != VotingMode::D21 && sentiment != 1 {
+        return Err(D21Error::InvalidSentiment);
+    }
+    // a repeat quadratic vote adds weight to the existing receipt
+    if already_voted && poll.mode != VotingMode::Quadratic {
+        return Err(D21Error::AlreadyVotedThisOption);
+    }
+    if !already_voted
+        && poll.max_votes_per_voter > 0
+        && voter.options_voted >= poll.max_votes_per_voter
+    {
+        return Err(D21Error::TooManyVotes);
+    }
+    match poll.mode {
+        VotingMode::D21 => {}
+        // approvals are unlimited, one per option
+        VotingMode::Approval => return Ok(()),
+        VotingMode::Plurality => {
+            return if voter.used_plus == 0 { Ok(()) } else { Err(D21Error::OutOfPositiveCredits) };
+        }
+        // the next vote on this option costs quadratic_cost(weight + 1)
+        VotingMode::Quadratic => {
+            let cost = weight.checked_add(1).map(quadratic_cost).ok_or(D21Error::MathOverflow)?;
+            return if voter.credits_remaining >= cost { Ok(()) } else { Err(D21Error::OutOfPositiveCredits) };
+        }
+    }
+    if sentiment == 1 {
+        if voter.used_plus >= poll.plus_credits {
+            return Err(D21Error::OutOfPositiveCredits);
+        }
+    } else if sentiment == -1 {
+        // minus_credits == 0 disables negative votes outright
+        if poll.minus_credits == 0 {
+            return Err(D21Error::OutOfNegativeCredits);
+        }
+        // ratio gate: require P >= neg_ratio*(M+1) before casting this minus
+        if (voter.used_plus as u16) < poll.neg_ratio as u16 * (voter.used_minus as u16 + 1) {
+            return Err(D21Error::InsufficientPositivesForNegative);
+        }
+        if voter.used_minus >= poll.minus_credits {
+            return Err(D21Error::OutOfNegativeCredits);
+        }
+    }
+    // an abstention (0) uses no credits
+    Ok(())
+}
+
+/// Whose credits and receipts a vote counts against: the delegator when a
+/// `Delegation` is passed, the signer otherwise.
+pub fn ballot_owner(delegation: &Option<Account<Delegation>>, signer: &Signer) -> Pubkey {
+    delegation.as_ref().map_or(signer.key(), |d| d.delegator)
+}
+
+/// Credits charged for raising a quadratic receipt to `weight` votes.
+pub fn quadratic_cost(weight: u16) -> u32 {
+    weight as u32 * weight as u32
+}
+
+pub fn handler(
+    ctx: Context<CastVote>,
+    index: u16,
+    sentiment: i8,
+    note: Option<String>,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
     let now = Clock::get()?.unix_timestamp;
     let poll = &mut ctx.accounts.poll;
-    
-    require!(now >= poll.start_ts, D21Error::VotingNotStarted);
-    require!(now <= poll.end_ts, D21Error::VotingClosed);
-    require!(matches!(sentiment, 1 | -1), D21Error::InvalidSentiment);
+    poll.assert_version(ACCOUNT_VERSION)?;
+    // commit-reveal polls only take votes through reveal_vote
+    require!(!poll.is_commit_reveal(), D21Error::UnsupportedVotingMode);
+    let owner = ballot_owner(&ctx.accounts.delegation, &ctx.accounts.voter_authority);
+    if let Some(delegation) = &ctx.accounts.delegation {
+        require_keys_eq!(delegation.delegate, ctx.accounts.voter_authority.key(), D21Error::Unauthorized);
+    }
+    let poll_key = poll.key();
+    poll.check_allowlist(&poll_key, &owner, &proof, ctx.accounts.allowed_voter.as_deref())?;
 
     let option = &mut ctx.accounts.option_node;
+    option.assert_version(ACCOUNT_VERSION)?;
+    // the receipt seeds use `index`, so the option has to be that same one
+    require!(option.index == index, D21Error::PollMismatch);
     let voter = &mut ctx.accounts.voter;
     let receipt = &mut ctx.accounts.receipt;
     
     if voter.poll == Pubkey::default() {
         voter.poll = poll.key();
-        voter.voter = ctx.accounts.voter_authority.key();
+        voter.voter = owner;
         voter.used_plus = 0;
         voter.used_minus = 0;
+        voter.credits_remaining = poll.plus_credits as u32;
+        voter.version = ACCOUNT_VERSION;
+        voter.last_vote_ts = 0;
+        voter.options_voted = 0;
+        poll.total_voters = poll.total_voters.checked_add(1).ok_or(D21Error::MathOverflow)?;
     } else {
         require_keys_eq!(voter.poll, poll.key(), D21Error::PollMismatch);
-        require_keys_eq!(voter.voter, ctx.accounts.voter_authority.key(), D21Error::Unauthorized);
+        require_keys_eq!(voter.voter, owner, D21Error::Unauthorized);
+        voter.assert_version(ACCOUNT_VERSION)?;
     }
     
-    if receipt.poll != Pubkey::default() {
+    let already_voted = receipt.poll != Pubkey::default();
+    if already_voted {
         // already created before
         require_keys_eq!(receipt.poll, poll.key(), D21Error::PollMismatch);
-        require_keys_eq!(receipt.voter, ctx.accounts.voter_authority.key(), D21Error::Unauthorized);
+        require_keys_eq!(receipt.voter, owner, D21Error::Unauthorized);
         require!(receipt.option_index == option.index, D21Error::PollMismatch);
-        return err!(D21Error::AlreadyVotedThisOption);
+    }
+    check_vote(poll, voter, already_voted, receipt.weight, sentiment, now)?;
+    if let Some(n) = &note {
+        require!(n.len() <= MAX_NOTE, D21Error::NoteTooLong);
     }
 
-    match sentiment {
-        1 => {
-            require!(voter.used_plus < poll.plus_credits, D21Error::OutOfPositiveCredits);
-            voter.used_plus = voter.used_plus.checked_add(1).ok_or(D21Error::MathOverflow)?;
-            option.plus_votes = option.plus_votes.checked_add(1).ok_or(D21Error::MathOverflow)?;
-        }
-        -1 => {
-            // ratio gate: require P >= 2*(M+1) before casting this minus
-            let p = voter.used_plus as u16;
-            let m_next = (voter.used_minus as u16) + 1;
-            require!(p >= 2 * m_next, D21Error::InsufficientPositivesForNegative);
-
-            require!(voter.used_minus < poll.minus_credits, D21Error::OutOfNegativeCredits);
-            voter.used_minus = voter.used_minus.checked_add(1).ok_or(D21Error::MathOverflow)?;
-            option.minus_votes = option.minus_votes.checked_add(1).ok_or(D21Error::MathOverflow)?;
+    if poll.mode == VotingMode::Quadratic {
+        // the n-th vote on this option costs n² credits
+        let weight = receipt.weight.checked_add(1).ok_or(D21Error::MathOverflow)?;
+        voter.credits_remaining = voter
+            .credits_remaining
+            .checked_sub(quadratic_cost(weight))
+            .ok_or(D21Error::OutOfPositiveCredits)?;
+        receipt.weight = weight;
+        option.plus_votes = option.plus_votes.checked_add(1).ok_or(D21Error::MathOverflow)?;
+        poll.total_plus = poll.total_plus.checked_add(1).ok_or(D21Error::MathOverflow)?;
+    } else {
+        receipt.weight = 1;
+        match sentiment {
+            1 => {
+                voter.used_plus = voter.used_plus.checked_add(1).ok_or(D21Error::MathOverflow)?;
+                option.plus_votes = option.plus_votes.checked_add(1).ok_or(D21Error::MathOverflow)?;
+                poll.total_plus = poll.total_plus.checked_add(1).ok_or(D21Error::MathOverflow)?;
+            }
+            -1 => {
+                voter.used_minus = voter.used_minus.checked_add(1).ok_or(D21Error::MathOverflow)?;
+                option.minus_votes = option.minus_votes.checked_add(1).ok_or(D21Error::MathOverflow)?;
+                poll.total_minus = poll.total_minus.checked_add(1).ok_or(D21Error::MathOverflow)?;
+            }
+            0 => {
+                option.abstain_votes = option.abstain_votes.checked_add(1).ok_or(D21Error::MathOverflow)?;
+            }
+            _ => unreachable!(),
         }
-        _ => unreachable!(),
     }
 
+    ranking::track_leader(poll, Standing {
+        index: option.index,
+        plus_votes: option.plus_votes,
+        minus_votes: option.minus_votes,
+    });
+
     // write receipt so this option cannot be voted again by this voter
     receipt.poll = poll.key();
-    receipt.voter = ctx.accounts.voter_authority.key();
+    receipt.voter = owner;
     receipt.option_index = option.index;
     receipt.sentiment = sentiment;
+    receipt.note = note.clone();
+    voter.last_vote_ts = now;
+    if !already_voted {
+        voter.options_voted = voter.options_voted.checked_add(1).ok_or(D21Error::MathOverflow)?;
+    }
     
     emit!(crate::states::VoteCast {
         poll: poll.key(),
@@ -63,7 +201,10 @@ pub fn handler(ctx: Context<CastVote>, _index: u16, sentiment: i8) -> Result<()>
         option_index: option.index,
         sentiment,
         used_plus: voter.used_plus,
-        used_minus: voter.used_minus
+        used_minus: voter.used_minus,
+        note,
+        option_plus_votes: option.plus_votes,
+        option_minus_votes: option.minus_votes,
     });
     Ok(())
 }
@@ -73,17 +214,30 @@ pub fn handler(ctx: Context<CastVote>, _index: u16, sentiment: i8) -> Result<()>
 #[instruction(index: u16)]
 pub struct CastVote<'info> {
    
+    // funds the Voter/Receipt rent, may be a sponsor or voter_authority itself
     #[account(mut)]
+    pub payer: Signer<'info>,
+
     pub voter_authority: Signer<'info>,
 
     #[account(
         mut,
-        seeds = [b"poll", poll.authority.as_ref(), &poll.poll_id.to_le_bytes()],
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
         bump,
         constraint = !poll.ended @ D21Error::VotingClosed,
     )]
     pub poll: Account<'info, Poll>,
 
+    // lets voter_authority vote as the delegator, see delegate_votes
+    #[account(
+        seeds = [b"delegation", poll.key().as_ref(), delegation.delegator.as_ref()],
+        bump,
+    )]
+    pub delegation: Option<Account<'info, Delegation>>,
+
+    // the ballot owner's allowlist entry, only needed while poll.allowed_count > 0
+    pub allowed_voter: Option<Account<'info, AllowedVoter>>,
+
     #[account(
         mut,
         seeds = [b"option", poll.key().as_ref(), &index.to_le_bytes()],
@@ -94,18 +248,18 @@ pub struct CastVote<'info> {
 
     #[account(
         init_if_needed,
-        payer = voter_authority,
-        space = Voter::SPACE,
-        seeds = [b"voter", poll.key().as_ref(), voter_authority.key().as_ref()],
+        payer = payer,
+        space = 8 + Voter::INIT_SPACE,
+        seeds = [b"voter", poll.key().as_ref(), ballot_owner(&delegation, &voter_authority).as_ref()],
         bump
     )]
     pub voter: Account<'info, Voter>,
 
     #[account(
         init_if_needed,
-        payer = voter_authority,
-        space = Receipt::SPACE,
-        seeds = [b"receipt", poll.key().as_ref(), &index.to_le_bytes(), voter_authority.key().as_ref()],
+        payer = payer,
+        space = 8 + Receipt::INIT_SPACE,
+        seeds = [b"receipt", poll.key().as_ref(), &index.to_le_bytes(), ballot_owner(&delegation, &voter_authority).as_ref()],
         bump
     )]
     pub receipt: Account<'info, Receipt>,
@@ -0,0 +1,165 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::instructions::cast_vote::check_vote;
+use crate::instructions::initialize_poll::create_pda_account;
+use crate::ranking::{self, Standing};
+use crate::states::{AllowedVoter, OptionNode, Poll, Receipt, Voter, VoteCast, VotingMode, Versioned, ACCOUNT_VERSION, MAX_BATCH};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct BatchVote {
+    pub index: u16,
+    pub sentiment: i8,
+}
+
+/// Casts several votes in one transaction. `remaining_accounts` holds an
+/// `[option_node, receipt]` pair per vote, in the same order as `votes`.
+/// Votes are applied in order through the same `check_vote` rules as
+/// `cast_vote`, so a later minus can rely on an earlier plus in the batch.
+/// Any invalid vote fails the whole transaction. Quadratic polls vote one
+/// option at a time through `cast_vote`, commit-reveal polls through
+/// `reveal_vote`.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CastVotesBatch<'info>>,
+    votes: Vec<BatchVote>,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    require!(ctx.accounts.poll.mode != VotingMode::Quadratic, D21Error::UnsupportedVotingMode);
+    require!(!ctx.accounts.poll.is_commit_reveal(), D21Error::UnsupportedVotingMode);
+    require!(!votes.is_empty() && votes.len() <= MAX_BATCH, D21Error::InvalidBatchSize);
+    require!(ctx.remaining_accounts.len() == votes.len() * 2, D21Error::BatchAccountMismatch);
+
+    let now = Clock::get()?.unix_timestamp;
+    let poll_key = ctx.accounts.poll.key();
+    let authority = ctx.accounts.voter_authority.key();
+    let payer = ctx.accounts.payer.to_account_info();
+    let system = ctx.accounts.system_program.to_account_info();
+    let poll = &mut ctx.accounts.poll;
+    poll.check_allowlist(&poll_key, &authority, &proof, ctx.accounts.allowed_voter.as_deref())?;
+    let voter = &mut ctx.accounts.voter;
+
+    if voter.poll == Pubkey::default() {
+        voter.poll = poll_key;
+        voter.voter = authority;
+        voter.used_plus = 0;
+        voter.used_minus = 0;
+        voter.credits_remaining = poll.plus_credits as u32;
+        voter.version = ACCOUNT_VERSION;
+        voter.last_vote_ts = 0;
+        voter.options_voted = 0;
+        poll.total_voters = poll.total_voters.checked_add(1).ok_or(D21Error::MathOverflow)?;
+    } else {
+        voter.assert_version(ACCOUNT_VERSION)?;
+        require_keys_eq!(voter.poll, poll_key, D21Error::PollMismatch);
+        require_keys_eq!(voter.voter, authority, D21Error::Unauthorized);
+    }
+
+    // the whole batch counts as one vote for the cooldown, so last_vote_ts
+    // is only written after the loop
+    for (vote, pair) in votes.iter().zip(ctx.remaining_accounts.chunks(2)) {
+        let (option_info, receipt_info) = (&pair[0], &pair[1]);
+        let index_bytes = vote.index.to_le_bytes();
+
+        let (expected, _) = Pubkey::find_program_address(
+            &[b"option", poll_key.as_ref(), &index_bytes],
+            &crate::ID,
+        );
+        require_keys_eq!(option_info.key(), expected, D21Error::BatchAccountMismatch);
+        let mut option = Account::<OptionNode>::try_from(option_info)?;
+        option.assert_version(ACCOUNT_VERSION)?;
+        require_keys_eq!(option.poll, poll_key, D21Error::PollMismatch);
+
+        let receipt_seeds: &[&[u8]] = &[b"receipt", poll_key.as_ref(), &index_bytes, authority.as_ref()];
+        let (expected, bump) = Pubkey::find_program_address(receipt_seeds, &crate::ID);
+        require_keys_eq!(receipt_info.key(), expected, D21Error::BatchAccountMismatch);
+
+        check_vote(poll, voter, !receipt_info.data_is_empty(), 0, vote.sentiment, now)?;
+
+        match vote.sentiment {
+            1 => {
+                voter.used_plus = voter.used_plus.checked_add(1).ok_or(D21Error::MathOverflow)?;
+                option.plus_votes = option.plus_votes.checked_add(1).ok_or(D21Error::MathOverflow)?;
+                poll.total_plus = poll.total_plus.checked_add(1).ok_or(D21Error::MathOverflow)?;
+            }
+            -1 => {
+                voter.used_minus = voter.used_minus.checked_add(1).ok_or(D21Error::MathOverflow)?;
+                option.minus_votes = option.minus_votes.checked_add(1).ok_or(D21Error::MathOverflow)?;
+                poll.total_minus = poll.total_minus.checked_add(1).ok_or(D21Error::MathOverflow)?;
+            }
+            0 => {
+                option.abstain_votes = option.abstain_votes.checked_add(1).ok_or(D21Error::MathOverflow)?;
+            }
+            _ => unreachable!(),
+        }
+        option.exit(&crate::ID)?;
+        voter.options_voted = voter.options_voted.checked_add(1).ok_or(D21Error::MathOverflow)?;
+
+        ranking::track_leader(poll, Standing {
+            index: option.index,
+            plus_votes: option.plus_votes,
+            minus_votes: option.minus_votes,
+        });
+
+        create_pda_account(
+            receipt_info,
+            &payer,
+            &system,
+            8 + Receipt::INIT_SPACE,
+            &[b"receipt", poll_key.as_ref(), &index_bytes, authority.as_ref(), &[bump]],
+        )?;
+        let receipt = Receipt {
+            poll: poll_key,
+            voter: authority,
+            option_index: vote.index,
+            sentiment: vote.sentiment,
+            note: None,
+            weight: 1,
+        };
+        receipt.try_serialize(&mut &mut receipt_info.try_borrow_mut_data()?[..])?;
+
+        emit!(VoteCast {
+            poll: poll_key,
+            voter: authority,
+            option_index: vote.index,
+            sentiment: vote.sentiment,
+            used_plus: voter.used_plus,
+            used_minus: voter.used_minus,
+            note: None,
+            option_plus_votes: option.plus_votes,
+            option_minus_votes: option.minus_votes,
+        });
+    }
+    voter.last_vote_ts = now;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CastVotesBatch<'info> {
+    // funds the Voter/Receipt rent, may be a sponsor or voter_authority itself
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub voter_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+        constraint = !poll.ended @ D21Error::VotingClosed,
+    )]
+    pub poll: Account<'info, Poll>,
+
+    // voter_authority's allowlist entry, only needed while poll.allowed_count > 0
+    pub allowed_voter: Option<Account<'info, AllowedVoter>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + Voter::INIT_SPACE,
+        seeds = [b"voter", poll.key().as_ref(), voter_authority.key().as_ref()],
+        bump
+    )]
+    pub voter: Account<'info, Voter>,
+
+    pub system_program: Program<'info, System>,
+}
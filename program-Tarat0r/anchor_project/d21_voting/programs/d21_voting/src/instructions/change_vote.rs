@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::instructions::cast_vote::check_vote;
+use crate::ranking::{self, Standing};
+use crate::states::{OptionNode, Poll, Receipt, Voter, Versioned, ACCOUNT_VERSION};
+
+/// Changes an existing vote to another sentiment. The old vote is taken back
+/// first and the new one then has to pass the same `check_vote` rules as a
+/// fresh vote, so the ratio gate sees the counters without the old vote.
+pub fn handler(ctx: Context<ChangeVote>, _index: u16, sentiment: i8) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    ctx.accounts.option_node.assert_version(ACCOUNT_VERSION)?;
+    ctx.accounts.voter.assert_version(ACCOUNT_VERSION)?;
+    let now = Clock::get()?.unix_timestamp;
+    let poll = &mut ctx.accounts.poll;
+    let option = &mut ctx.accounts.option_node;
+    let voter = &mut ctx.accounts.voter;
+    let receipt = &mut ctx.accounts.receipt;
+
+    require!(receipt.sentiment != sentiment, D21Error::AlreadyVotedThisOption);
+
+    let (mut used_plus, mut used_minus) = (voter.used_plus, voter.used_minus);
+    let (mut plus_votes, mut minus_votes) = (option.plus_votes, option.minus_votes);
+    let mut abstain_votes = option.abstain_votes;
+    match receipt.sentiment {
+        1 => {
+            used_plus = used_plus.checked_sub(1).ok_or(D21Error::MathOverflow)?;
+            plus_votes = plus_votes.checked_sub(1).ok_or(D21Error::MathOverflow)?;
+        }
+        -1 => {
+            used_minus = used_minus.checked_sub(1).ok_or(D21Error::MathOverflow)?;
+            minus_votes = minus_votes.checked_sub(1).ok_or(D21Error::MathOverflow)?;
+        }
+        _ => {
+            abstain_votes = abstain_votes.checked_sub(1).ok_or(D21Error::MathOverflow)?;
+        }
+    }
+    // the receipt being changed is taken back too, so the vote cap sees the
+    // voter without it
+    let taken_back = Voter {
+        used_plus,
+        used_minus,
+        options_voted: voter.options_voted.saturating_sub(1),
+        ..(**voter).clone()
+    };
+    check_vote(poll, &taken_back, false, 0, sentiment, now)?;
+
+    match sentiment {
+        1 => {
+            used_plus = used_plus.checked_add(1).ok_or(D21Error::MathOverflow)?;
+            plus_votes = plus_votes.checked_add(1).ok_or(D21Error::MathOverflow)?;
+        }
+        -1 => {
+            used_minus = used_minus.checked_add(1).ok_or(D21Error::MathOverflow)?;
+            minus_votes = minus_votes.checked_add(1).ok_or(D21Error::MathOverflow)?;
+        }
+        0 => {
+            abstain_votes = abstain_votes.checked_add(1).ok_or(D21Error::MathOverflow)?;
+        }
+        _ => unreachable!(),
+    }
+    voter.used_plus = used_plus;
+    voter.used_minus = used_minus;
+    voter.last_vote_ts = now;
+    poll.total_plus = poll
+        .total_plus
+        .checked_add(plus_votes as u64)
+        .and_then(|t| t.checked_sub(option.plus_votes as u64))
+        .ok_or(D21Error::MathOverflow)?;
+    poll.total_minus = poll
+        .total_minus
+        .checked_add(minus_votes as u64)
+        .and_then(|t| t.checked_sub(option.minus_votes as u64))
+        .ok_or(D21Error::MathOverflow)?;
+    option.plus_votes = plus_votes;
+    option.minus_votes = minus_votes;
+    option.abstain_votes = abstain_votes;
+
+    ranking::track_leader(poll, Standing {
+        index: option.index,
+        plus_votes: option.plus_votes,
+        minus_votes: option.minus_votes,
+    });
+
+    let previous = receipt.sentiment;
+    receipt.sentiment = sentiment;
+
+    emit!(VoteChanged {
+        poll: poll.key(),
+        voter: voter.voter,
+        option_index: option.index,
+        previous,
+        sentiment,
+        used_plus: voter.used_plus,
+        used_minus: voter.used_minus,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct VoteChanged {
+    pub poll: Pubkey,
+    pub voter: Pubkey,
+    pub option_index: u16,
+    pub previous: i8,
+    pub sentiment: i8,
+    pub used_plus: u8,
+    pub used_minus: u8,
+}
+
+#[derive(Accounts)]
+#[instruction(index: u16)]
+pub struct ChangeVote<'info> {
+    pub voter_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+        constraint = !poll.ended @ D21Error::VotingClosed,
+    )]
+    pub poll: Account<'info, Poll>,
+
+    #[account(
+        mut,
+        seeds = [b"option", poll.key().as_ref(), &index.to_le_bytes()],
+        bump,
+        constraint = option_node.poll == poll.key() @ D21Error::PollMismatch,
+    )]
+    pub option_node: Account<'info, OptionNode>,
+
+    #[account(
+        mut,
+        seeds = [b"voter", poll.key().as_ref(), voter_authority.key().as_ref()],
+        bump
+    )]
+    pub voter: Account<'info, Voter>,
+
+    #[account(
+        mut,
+        seeds = [b"receipt", poll.key().as_ref(), &index.to_le_bytes(), voter_authority.key().as_ref()],
+        bump
+    )]
+    pub receipt: Account<'info, Receipt>,
+}
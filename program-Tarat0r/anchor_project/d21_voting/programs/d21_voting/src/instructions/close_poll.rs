@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::states::{ClosedPoll, Poll, Versioned, ACCOUNT_VERSION};
+
+/// Closes the poll account after voting is over and refunds its rent to the
+/// authority. OptionNodes and LabelGuards stay behind; use `close_poll_full`
+/// first to reclaim those. Voters keep closing their Voter and Receipt
+/// accounts afterwards; the ClosedPoll left in place blocks the poll id.
+pub fn handler(ctx: Context<ClosePoll>) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    let now = Clock::get()?.unix_timestamp;
+    let poll = &mut ctx.accounts.poll;
+    require!(now > poll.vote_deadline(), D21Error::VotingNotOver);
+    poll.ended = true;
+    ctx.accounts.closed_poll.poll = poll.key();
+
+    emit!(PollClosed { poll: poll.key(), authority: ctx.accounts.authority.key() });
+    Ok(())
+}
+
+#[event]
+pub struct PollClosed {
+    pub poll: Pubkey,
+    pub authority: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct ClosePoll<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+        has_one = authority @ D21Error::Unauthorized,
+        close = authority,
+    )]
+    pub poll: Account<'info, Poll>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ClosedPoll::INIT_SPACE,
+        seeds = [b"closed", poll.key().as_ref()],
+        bump
+    )]
+    pub closed_poll: Account<'info, ClosedPoll>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::states::{LabelGuard, OptionNode, Poll, Versioned, ACCOUNT_VERSION};
+
+/// Teardown in one transaction: marks the poll ended and closes every
+/// OptionNode/LabelGuard passed in `remaining_accounts`, refunding their rent
+/// to the authority. Any account that is not an OptionNode/LabelGuard of
+/// this poll fails the instruction, so nothing is closed.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ClosePollFull<'info>>) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    let now = Clock::get()?.unix_timestamp;
+    let poll_key = ctx.accounts.poll.key();
+    let poll = &mut ctx.accounts.poll;
+    require!(now > poll.vote_deadline(), D21Error::VotingNotOver);
+    poll.ended = true;
+
+    let authority = ctx.accounts.authority.to_account_info();
+    for info in ctx.remaining_accounts.iter() {
+        if let Ok(option) = Account::<OptionNode>::try_from(info) {
+            option.assert_version(ACCOUNT_VERSION)?;
+            require_keys_eq!(option.poll, poll_key, D21Error::PollMismatch);
+            option.close(authority.clone())?;
+        } else {
+            let guard = Account::<LabelGuard>::try_from(info)?;
+            require_keys_eq!(guard.poll, poll_key, D21Error::PollMismatch);
+            guard.close(authority.clone())?;
+        }
+    }
+
+    emit!(PollTornDown { poll: poll_key, closed: ctx.remaining_accounts.len() as u32 });
+    Ok(())
+}
+
+#[event]
+pub struct PollTornDown {
+    pub poll: Pubkey,
+    pub closed: u32,
+}
+
+#[derive(Accounts)]
+pub struct ClosePollFull<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+        has_one = authority @ D21Error::Unauthorized,
+    )]
+    pub poll: Account<'info, Poll>,
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::states::{Poll, Receipt};
+
+/// Refunds a receipt's rent to its voter once voting is over or the poll
+/// was closed. The vote stays counted on the OptionNode.
+pub fn handler(ctx: Context<CloseReceipt>, _index: u16) -> Result<()> {
+    Poll::require_cleanup_allowed(&ctx.accounts.poll)
+}
+
+#[derive(Accounts)]
+#[instruction(index: u16)]
+pub struct CloseReceipt<'info> {
+    #[account(mut)]
+    pub voter_authority: Signer<'info>,
+
+    /// CHECK: possibly already closed by close_poll, see
+    /// Poll::require_cleanup_allowed; the receipt seeds bind it to the receipt
+    pub poll: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"receipt", poll.key().as_ref(), &index.to_le_bytes(), voter_authority.key().as_ref()],
+        bump,
+        constraint = receipt.voter == voter_authority.key() @ D21Error::Unauthorized,
+        close = voter_authority,
+    )]
+    pub receipt: Account<'info, Receipt>,
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::states::{Poll, Voter, Versioned, ACCOUNT_VERSION};
+
+/// Refunds a Voter account's rent once voting is over. Closing resets the
+/// credit counters, so it is only allowed once no vote can be cast any more:
+/// the poll is ended or past `end_ts + grace_secs`, and extend_voting_window
+/// cannot reopen a poll after `end_ts`. A poll removed by close_poll counts
+/// as over.
+pub fn handler(ctx: Context<CloseVoter>) -> Result<()> {
+    ctx.accounts.voter.assert_version(ACCOUNT_VERSION)?;
+    Poll::require_cleanup_allowed(&ctx.accounts.poll)
+}
+
+#[derive(Accounts)]
+pub struct CloseVoter<'info> {
+    #[account(mut)]
+    pub voter_authority: Signer<'info>,
+
+    /// CHECK: possibly already closed by close_poll, see
+    /// Poll::require_cleanup_allowed; the voter seeds bind it to the voter
+    pub poll: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"voter", poll.key().as_ref(), voter_authority.key().as_ref()],
+        bump,
+        constraint = voter.voter == voter_authority.key() @ D21Error::Unauthorized,
+        close = voter_authority,
+    )]
+    pub voter: Account<'info, Voter>,
+}
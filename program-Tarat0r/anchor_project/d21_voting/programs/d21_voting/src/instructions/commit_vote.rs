@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::states::{Commitment, Poll, Versioned, ACCOUNT_VERSION};
+
+/// Stores `Commitment::hash(index, sentiment, nonce)` during the commit
+/// phase of a commit-reveal poll, one hidden vote per voter. The vote is
+/// applied later by `reveal_vote`.
+pub fn handler(ctx: Context<CommitVote>, commitment: [u8; 32]) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    let now = Clock::get()?.unix_timestamp;
+    let poll = &ctx.accounts.poll;
+    require!(poll.is_commit_reveal(), D21Error::UnsupportedVotingMode);
+    require!(now >= poll.start_ts, D21Error::VotingNotStarted);
+    require!(now <= poll.commit_end_ts, D21Error::VotingClosed);
+    require!(!poll.paused, D21Error::PollPaused);
+
+    let entry = &mut ctx.accounts.commitment;
+    entry.poll = poll.key();
+    entry.voter = ctx.accounts.voter_authority.key();
+    entry.commitment = commitment;
+
+    emit!(VoteCommitted { poll: entry.poll, voter: entry.voter, commitment });
+    Ok(())
+}
+
+#[event]
+pub struct VoteCommitted {
+    pub poll: Pubkey,
+    pub voter: Pubkey,
+    pub commitment: [u8; 32],
+}
+
+#[derive(Accounts)]
+pub struct CommitVote<'info> {
+    #[account(mut)]
+    pub voter_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+        constraint = !poll.ended @ D21Error::VotingClosed,
+    )]
+    pub poll: Account<'info, Poll>,
+
+    #[account(
+        init,
+        payer = voter_authority,
+        space = 8 + Commitment::INIT_SPACE,
+        seeds = [b"commitment", poll.key().as_ref(), voter_authority.key().as_ref()],
+        bump
+    )]
+    pub commitment: Account<'info, Commitment>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::ranking;
+use crate::states::{Poll, Versioned, ACCOUNT_VERSION};
+
+/// Emits the current winner among the option accounts passed in
+/// `remaining_accounts`. All of the poll's options must be passed, in any
+/// order. Mutates nothing.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ComputeWinner<'info>>) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    let poll = &ctx.accounts.poll;
+    let standings = ranking::load_standings(&poll.key(), ctx.remaining_accounts)?;
+    require!(
+        standings.len() == poll.options_count as usize,
+        D21Error::OptionCountMismatch
+    );
+    let winner = ranking::winner(&standings).ok_or(D21Error::NoOptions)?;
+
+    emit!(WinnerComputed {
+        poll: poll.key(),
+        option_index: winner.index,
+        net: winner.net(),
+        plus_votes: winner.plus_votes,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct WinnerComputed {
+    pub poll: Pubkey,
+    pub option_index: u16,
+    pub net: i64,
+    pub plus_votes: u32,
+}
+
+#[derive(Accounts)]
+pub struct ComputeWinner<'info> {
+    #[account(
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+    )]
+    pub poll: Account<'info, Poll>,
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::states::{Delegation, Poll, Versioned, ACCOUNT_VERSION};
+
+/// Lets `delegate` cast votes on the delegator's behalf. Those votes use the
+/// delegator's Voter credits and receipts; the delegator can still vote
+/// directly and can take the right back with `revoke_delegation`.
+pub fn handler(ctx: Context<DelegateVotes>, delegate: Pubkey) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    let delegator = ctx.accounts.delegator.key();
+    require_keys_neq!(delegate, delegator, D21Error::SelfDelegation);
+
+    let entry = &mut ctx.accounts.delegation;
+    entry.poll = ctx.accounts.poll.key();
+    entry.delegator = delegator;
+    entry.delegate = delegate;
+
+    emit!(VotesDelegated { poll: entry.poll, delegator, delegate });
+    Ok(())
+}
+
+#[event]
+pub struct VotesDelegated {
+    pub poll: Pubkey,
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct DelegateVotes<'info> {
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+
+    #[account(
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+        constraint = !poll.ended @ D21Error::VotingClosed,
+    )]
+    pub poll: Account<'info, Poll>,
+
+    #[account(
+        init,
+        payer = delegator,
+        space = 8 + Delegation::INIT_SPACE,
+        seeds = [b"delegation", poll.key().as_ref(), delegator.key().as_ref()],
+        bump
+    )]
+    pub delegation: Account<'info, Delegation>,
+
+    pub system_program: Program<'info, System>,
+}
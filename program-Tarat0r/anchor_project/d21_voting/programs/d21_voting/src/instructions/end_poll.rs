@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::states::{Admins, Poll, Role, Versioned, ACCOUNT_VERSION};
+
+/// Stops voting early by marking the poll as ended. Requires Closer.
+pub fn handler(ctx: Context<EndPoll>) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    let poll_key = ctx.accounts.poll.key();
+    let signer = ctx.accounts.signer.key();
+    let poll = &mut ctx.accounts.poll;
+    poll.require_role(&poll_key, ctx.accounts.admins.as_deref(), &signer, Role::Closer)?;
+
+    poll.ended = true;
+
+    emit!(PollEnded { poll: poll_key, ended_by: signer });
+    Ok(())
+}
+
+#[event]
+pub struct PollEnded {
+    pub poll: Pubkey,
+    pub ended_by: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct EndPoll<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+        constraint = !poll.ended @ D21Error::VotingClosed
+    )]
+    pub poll: Account<'info, Poll>,
+
+    // only needed when the signer is not poll.authority
+    pub admins: Option<Account<'info, Admins>>,
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::states::{Poll, Versioned, ACCOUNT_VERSION, MAX_DESC_EXTENDED, MAX_TITLE_EXTENDED};
+
+/// Grows the poll account to `Poll::EXTENDED_SPACE` (the authority pays the
+/// extra rent) and writes a title and/or description up to the extended
+/// limits. Like update_poll_metadata, only before voting starts.
+pub fn handler(ctx: Context<ExpandPollText>, new_title: Option<String>, new_desc: Option<String>) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    let poll = &mut ctx.accounts.poll;
+    require!(Clock::get()?.unix_timestamp < poll.start_ts, D21Error::VotingStarted);
+
+    let title_changed = new_title.is_some();
+    let description_changed = new_desc.is_some();
+    if let Some(title) = new_title {
+        require!(title.len() <= MAX_TITLE_EXTENDED, D21Error::TitleTooLong);
+        poll.title = title;
+    }
+    if let Some(desc) = new_desc {
+        require!(desc.len() <= MAX_DESC_EXTENDED, D21Error::DescriptionTooLong);
+        poll.description = desc;
+    }
+
+    emit!(crate::instructions::update_poll_metadata::PollMetadataUpdated {
+        poll: poll.key(),
+        title_changed,
+        description_changed,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExpandPollText<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+        has_one = authority @ D21Error::Unauthorized,
+        realloc = Poll::EXTENDED_SPACE,
+        realloc::payer = authority,
+        realloc::zero = false,
+    )]
+    pub poll: Account<'info, Poll>,
+
+    pub system_program: Program<'info, System>,
+}
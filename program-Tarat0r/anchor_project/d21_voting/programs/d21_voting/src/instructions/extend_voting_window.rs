@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::states::{Poll, Versioned, ACCOUNT_VERSION};
+
+/// Moves `end_ts` later while voting is still open. The window can only
+/// grow, and a poll that ended (early or past `end_ts`) stays ended, since
+/// close_receipt/close_voter may already have reset its voters.
+pub fn handler(ctx: Context<ExtendVotingWindow>, new_end_ts: i64) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    let now = Clock::get()?.unix_timestamp;
+    let poll = &mut ctx.accounts.poll;
+    require!(now <= poll.end_ts, D21Error::VotingClosed);
+    require!(new_end_ts > poll.end_ts, D21Error::InvalidTimeWindow);
+    require!(new_end_ts > now, D21Error::InvalidTimeWindow);
+
+    let old_end_ts = poll.end_ts;
+    poll.end_ts = new_end_ts;
+
+    emit!(VotingWindowExtended { poll: poll.key(), old_end_ts, new_end_ts });
+    Ok(())
+}
+
+#[event]
+pub struct VotingWindowExtended {
+    pub poll: Pubkey,
+    pub old_end_ts: i64,
+    pub new_end_ts: i64,
+}
+
+#[derive(Accounts)]
+pub struct ExtendVotingWindow<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+        has_one = authority @ D21Error::Unauthorized,
+        constraint = !poll.ended @ D21Error::VotingClosed,
+    )]
+    pub poll: Account<'info, Poll>,
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::ranking;
+use crate::states::{Poll, ResultsSnapshot, Versioned, ACCOUNT_VERSION};
+
+/// Stores the winner on the poll once voting is over, while the finalize
+/// window is open. All of the poll's options must be passed in
+/// `remaining_accounts`, in any order.
+///
+/// The winner is the option with the highest net score. Ties go to the
+/// option with more plus votes and then to the lowest index, the same
+/// ordering `compute_winner` and `attest_result` use.
+///
+/// A poll below its quorum is still finalized; `quorum_met` reports it.
+///
+/// The totals also go into a `ResultsSnapshot`. Its `init` makes the
+/// instruction one-shot: a second finalize fails on the existing snapshot.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, FinalizePoll<'info>>) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    let now = Clock::get()?.unix_timestamp;
+    let poll_key = ctx.accounts.poll.key();
+    let poll = &mut ctx.accounts.poll;
+    require!(now > poll.vote_deadline(), D21Error::VotingNotOver);
+    require!(poll.finalize_window_open(now), D21Error::FinalizeWindowClosed);
+
+    let standings = ranking::load_standings(&poll_key, ctx.remaining_accounts)?;
+    require!(
+        standings.len() == poll.options_count as usize,
+        D21Error::OptionCountMismatch
+    );
+    let winner = ranking::winner(&standings).ok_or(D21Error::NoOptions)?;
+
+    poll.winner_index = Some(winner.index);
+    poll.winner_score = winner.net();
+    poll.quorum_met = poll.total_voters >= poll.quorum;
+
+    let snapshot = &mut ctx.accounts.snapshot;
+    snapshot.poll = poll_key;
+    snapshot.total_plus = standings.iter().map(|s| s.plus_votes as u64).sum();
+    snapshot.total_minus = standings.iter().map(|s| s.minus_votes as u64).sum();
+    snapshot.winner_index = winner.index;
+    snapshot.winner_score = winner.net();
+    snapshot.option_count = poll.options_count;
+    snapshot.finalized_ts = now;
+
+    emit!(PollFinalized {
+        poll: poll_key,
+        winner_index: winner.index,
+        winner_score: winner.net(),
+        quorum_met: poll.quorum_met,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct PollFinalized {
+    pub poll: Pubkey,
+    pub winner_index: u16,
+    pub winner_score: i64,
+    pub quorum_met: bool,
+}
+
+#[derive(Accounts)]
+pub struct FinalizePoll<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+    )]
+    pub poll: Account<'info, Poll>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ResultsSnapshot::INIT_SPACE,
+        seeds = [b"snapshot", poll.key().as_ref()],
+        bump
+    )]
+    pub snapshot: Account<'info, ResultsSnapshot>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -1,20 +1,182 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
 use crate::errors::D21Error;
-use crate::states::{Poll, MAX_DESC, MAX_TITLE};
+use crate::states::{Poll, PollCreated, PollInitialized, TagIndex, VotingMode, ACCOUNT_VERSION, MAX_DESC, MAX_LABEL, MAX_TAGS, MAX_TAG_LEN, MAX_TITLE};
 
 
-pub fn handler(ctx: Context<InitializePoll>, cfg: PollConfig) -> Result<()> {
-    
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, InitializePoll<'info>>,
+    cfg: PollConfig,
+) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let payer = ctx.accounts.payer.to_account_info();
+    let system = ctx.accounts.system_program.to_account_info();
+    create_poll(&mut ctx.accounts.poll, authority, &payer, &system, ctx.remaining_accounts, cfg)
+}
+
+/// Validates `cfg`, bumps its tag indexes and writes the poll. Shared by
+/// `initialize_poll` and `initialize_poll_auto`.
+pub(crate) fn create_poll<'info>(
+    poll: &mut Account<'info, Poll>,
+    authority: Pubkey,
+    payer: &AccountInfo<'info>,
+    system: &AccountInfo<'info>,
+    tag_indexes: &'info [AccountInfo<'info>],
+    cfg: PollConfig,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
     require!(cfg.poll_id != 0, D21Error::InvalidPollId);
     require!(cfg.title.len() <= MAX_TITLE, D21Error::TitleTooLong);
     require!(cfg.description.len() <= MAX_DESC, D21Error::DescriptionTooLong);
     require!(cfg.plus_credits > 0, D21Error::PlusCreditIsZero);
     require!(cfg.end_ts > cfg.start_ts, D21Error::InvalidTimeWindow);
-    require!(cfg.start_ts >= Clock::get()?.unix_timestamp, D21Error::InvalidTimeWindow);
-    
-    let authority = ctx.accounts.authority.key();
-    ctx.accounts.poll.set_inner(Poll::from_config(cfg, authority));
-    
+    require!(cfg.start_ts >= now, D21Error::InvalidTimeWindow);
+    require!(cfg.finalize_grace >= 0, D21Error::InvalidFinalizeGrace);
+    require!(cfg.grace_secs >= 0, D21Error::InvalidGraceSecs);
+    require!(cfg.vote_cooldown_secs >= 0, D21Error::InvalidVoteCooldown);
+    require!(
+        cfg.min_label_len >= 1 && cfg.min_label_len as usize <= MAX_LABEL,
+        D21Error::InvalidMinLabelLen
+    );
+    require!(cfg.neg_ratio >= 1, D21Error::InvalidNegRatio);
+    if cfg.commit_end_ts != 0 {
+        // both phases have to fit inside the voting window
+        require!(
+            cfg.commit_end_ts > cfg.start_ts
+                && cfg.reveal_end_ts > cfg.commit_end_ts
+                && cfg.reveal_end_ts <= cfg.end_ts,
+            D21Error::InvalidTimeWindow
+        );
+        require!(cfg.mode != VotingMode::Quadratic, D21Error::UnsupportedVotingMode);
+    } else {
+        require!(cfg.reveal_end_ts == 0, D21Error::InvalidTimeWindow);
+    }
+    validate_tags(&cfg.tags)?;
+
+    // one TagIndex account per tag, in the same order as cfg.tags
+    require!(tag_indexes.len() == cfg.tags.len(), D21Error::TagIndexMismatch);
+    for (tag, info) in cfg.tags.iter().zip(tag_indexes.iter()) {
+        bump_tag_index(tag, info, payer, system)?;
+    }
+
+    poll.set_inner(Poll::from_config(cfg, authority, now));
+
+    emit!(PollCreated {
+        poll: poll.key(),
+        authority,
+        poll_id: poll.poll_id,
+        tags: poll.tags.clone(),
+    });
+    emit!(PollInitialized {
+        poll: poll.key(),
+        authority,
+        poll_id: poll.poll_id,
+        start_ts: poll.start_ts,
+        end_ts: poll.end_ts,
+        plus_credits: poll.plus_credits,
+        minus_credits: poll.minus_credits,
+        created_ts: poll.created_ts,
+        tags: poll.tags.clone(),
+    });
+
+    Ok(())
+}
+
+pub(crate) fn validate_tags(tags: &[String]) -> Result<()> {
+    require!(tags.len() <= MAX_TAGS, D21Error::TooManyTags);
+    for (i, tag) in tags.iter().enumerate() {
+        require!(!tag.is_empty(), D21Error::TagEmpty);
+        require!(tag.len() <= MAX_TAG_LEN, D21Error::TagTooLong);
+        require!(!tags[..i].contains(tag), D21Error::DuplicateTag);
+    }
+    Ok(())
+}
+
+/// Increments the `["tag", tag]` index, creating it on first use.
+pub(crate) fn bump_tag_index<'info>(
+    tag: &str,
+    info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system: &AccountInfo<'info>,
+) -> Result<()> {
+    let (expected, bump) = Pubkey::find_program_address(&[b"tag", tag.as_bytes()], &crate::ID);
+    require_keys_eq!(info.key(), expected, D21Error::TagIndexMismatch);
+
+    let mut index = if info.data_is_empty() {
+        let seeds: &[&[u8]] = &[b"tag", tag.as_bytes(), &[bump]];
+        create_pda_account(info, payer, system, 8 + TagIndex::INIT_SPACE, seeds)?;
+        TagIndex { tag: tag.to_string(), poll_count: 0 }
+    } else {
+        TagIndex::try_deserialize(&mut &info.try_borrow_data()?[..])?
+    };
+
+    index.poll_count = index.poll_count.checked_add(1).ok_or(D21Error::MathOverflow)?;
+    index.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+    Ok(())
+}
+
+/// Creates the program-owned PDA behind `info`, signed with `seeds`. Like
+/// Anchor's `init`, an address that already holds lamports is topped up to
+/// rent exemption, then allocated and assigned, since create_account would
+/// fail on it.
+pub(crate) fn create_pda_account<'info>(
+    info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system: &AccountInfo<'info>,
+    space: usize,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let balance = info.lamports();
+    if balance == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system.clone(),
+                system_program::CreateAccount { from: payer.clone(), to: info.clone() },
+                &[seeds],
+            ),
+            rent,
+            space as u64,
+            &crate::ID,
+        );
+    }
+
+    let top_up = rent.saturating_sub(balance);
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system.clone(),
+                system_program::Transfer { from: payer.clone(), to: info.clone() },
+            ),
+            top_up,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system.clone(),
+            system_program::Allocate { account_to_allocate: info.clone() },
+            &[seeds],
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system.clone(),
+            system_program::Assign { account_to_assign: info.clone() },
+            &[seeds],
+        ),
+        &crate::ID,
+    )
+}
+
+/// Decrements the `["tag", tag]` index of a tag a poll no longer carries.
+pub(crate) fn drop_tag_index(tag: &str, info: &AccountInfo) -> Result<()> {
+    let (expected, _) = Pubkey::find_program_address(&[b"tag", tag.as_bytes()], &crate::ID);
+    require_keys_eq!(info.key(), expected, D21Error::TagIndexMismatch);
+
+    let mut index = TagIndex::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    index.poll_count = index.poll_count.checked_sub(1).ok_or(D21Error::MathOverflow)?;
+    index.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
     Ok(())
 }
 
@@ -28,12 +190,20 @@ pub struct InitializePoll<'info> {
     #[account(
         init,
         payer = payer,
-        space = Poll::SPACE,
+        space = 8 + Poll::INIT_SPACE,
         seeds = [b"poll", authority.key().as_ref(), &cfg.poll_id.to_le_bytes()],
         bump
     )]
     pub poll: Account<'info, Poll>,
 
+    /// CHECK: must stay empty, close_poll creates it to retire the poll id
+    #[account(
+        seeds = [b"closed", poll.key().as_ref()],
+        bump,
+        constraint = closed_poll.data_is_empty() @ D21Error::PollIdClosed,
+    )]
+    pub closed_poll: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -46,10 +216,23 @@ pub struct PollConfig {
    pub  minus_credits: u8,
    pub  start_ts: i64,
    pub  end_ts: i64,
+   pub  tags: Vec<String>,
+   pub  finalize_grace: i64,
+   pub  min_label_len: u16,
+   pub  neg_ratio: u8,
+   pub  quorum: u32,
+   pub  min_options: u16,
+   pub  mode: VotingMode,
+   pub  commit_end_ts: i64,
+   pub  reveal_end_ts: i64,
+   pub  allowlist_root: Option<[u8; 32]>,
+   pub  grace_secs: i64,
+   pub  vote_cooldown_secs: i64,
+   pub  max_votes_per_voter: u16,
 }
 
 impl Poll {
-    pub fn from_config(cfg: PollConfig, authority: Pubkey) -> Self {
+    pub fn from_config(cfg: PollConfig, authority: Pubkey, created_ts: i64) -> Self {
         Self {
             authority,
             poll_id: cfg.poll_id,
@@ -61,6 +244,35 @@ impl Poll {
             end_ts: cfg.end_ts,
             options_count: 0,
             ended: false,
+            tags: cfg.tags,
+            finalize_grace: cfg.finalize_grace,
+            options_sealed: false,
+            leader_index: 0,
+            leader_net: 0,
+            leader_plus: 0,
+            allowed_count: 0,
+            total_voters: 0,
+            min_label_len: cfg.min_label_len,
+            neg_ratio: cfg.neg_ratio,
+            quorum: cfg.quorum,
+            quorum_met: false,
+            creator: authority,
+            min_options: cfg.min_options,
+            mode: cfg.mode,
+            commit_end_ts: cfg.commit_end_ts,
+            reveal_end_ts: cfg.reveal_end_ts,
+            allowlist_root: cfg.allowlist_root,
+            active: false,
+            version: ACCOUNT_VERSION,
+            grace_secs: cfg.grace_secs,
+            paused: false,
+            vote_cooldown_secs: cfg.vote_cooldown_secs,
+            created_ts,
+            max_votes_per_voter: cfg.max_votes_per_voter,
+            total_plus: 0,
+            total_minus: 0,
+            winner_index: None,
+            winner_score: 0,
         }
     }
 }
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::instructions::initialize_poll::{create_poll, PollConfig};
+use crate::states::{Poll, Registry};
+
+/// Same as `initialize_poll`, but the poll id comes from the registry and
+/// `cfg.poll_id` is ignored. Ids are global, so they never repeat across
+/// authorities; an id the authority already used manually fails on the
+/// existing poll account.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, InitializePollAuto<'info>>,
+    mut cfg: PollConfig,
+) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    cfg.poll_id = registry.next_poll_id;
+    registry.next_poll_id = registry.next_poll_id.checked_add(1).ok_or(D21Error::MathOverflow)?;
+
+    let authority = ctx.accounts.authority.key();
+    let payer = ctx.accounts.payer.to_account_info();
+    let system = ctx.accounts.system_program.to_account_info();
+    create_poll(&mut ctx.accounts.poll, authority, &payer, &system, ctx.remaining_accounts, cfg)
+}
+
+#[derive(Accounts)]
+pub struct InitializePollAuto<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"registry"],
+        bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Poll::INIT_SPACE,
+        seeds = [b"poll", authority.key().as_ref(), &registry.next_poll_id.to_le_bytes()],
+        bump
+    )]
+    pub poll: Account<'info, Poll>,
+
+    /// CHECK: must stay empty, close_poll creates it to retire the poll id
+    #[account(
+        seeds = [b"closed", poll.key().as_ref()],
+        bump,
+        constraint = closed_poll.data_is_empty() @ D21Error::PollIdClosed,
+    )]
+    pub closed_poll: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
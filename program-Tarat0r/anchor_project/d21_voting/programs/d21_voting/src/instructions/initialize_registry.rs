@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+use crate::states::Registry;
+
+/// Creates the program-wide poll id counter. Runs once; ids start at 1.
+pub fn handler(ctx: Context<InitializeRegistry>) -> Result<()> {
+    ctx.accounts.registry.next_poll_id = 1;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeRegistry<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Registry::INIT_SPACE,
+        seeds = [b"registry"],
+        bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -1,10 +1,83 @@
 pub mod initialize_poll;
 pub mod add_option;
 pub mod cast_vote;
+pub mod my_receipts;
+pub mod canonicalize_label;
+pub mod compute_winner;
+pub mod set_admin;
+pub mod remove_admin;
+pub mod end_poll;
+pub mod audit_credits;
+pub mod seal_options;
+pub mod add_allowed_voter;
+pub mod participation_rate;
+pub mod sample_receipts;
+pub mod close_poll_full;
+pub mod attest_result;
+pub mod precheck_vote;
+pub mod close_poll;
+pub mod finalize_poll;
+pub mod change_vote;
+pub mod retract_vote;
+pub mod tally_results;
+pub mod remove_option;
+pub mod update_poll_metadata;
+pub mod extend_voting_window;
+pub mod cast_votes_batch;
+pub mod transfer_poll_authority;
+pub mod close_receipt;
+pub mod close_voter;
+pub mod commit_vote;
+pub mod reveal_vote;
+pub mod delegate_votes;
+pub mod revoke_delegation;
+pub mod begin_poll;
+pub mod initialize_registry;
+pub mod initialize_poll_auto;
+pub mod expand_poll_text;
+pub mod rank_options;
+pub mod pause_poll;
+pub mod resume_poll;
+pub mod set_tags;
 
 pub use initialize_poll::*;
 pub use add_option::*;
 pub use cast_vote::*;
-
-
-
+pub use my_receipts::*;
+pub use canonicalize_label::*;
+pub use compute_winner::*;
+pub use set_admin::*;
+pub use remove_admin::*;
+pub use end_poll::*;
+pub use audit_credits::*;
+pub use seal_options::*;
+pub use add_allowed_voter::*;
+pub use participation_rate::*;
+pub use sample_receipts::*;
+pub use close_poll_full::*;
+pub use attest_result::*;
+pub use precheck_vote::*;
+pub use close_poll::*;
+pub use finalize_poll::*;
+pub use change_vote::*;
+pub use retract_vote::*;
+pub use tally_results::*;
+pub use remove_option::*;
+pub use update_poll_metadata::*;
+pub use extend_voting_window::*;
+pub use cast_votes_batch::*;
+pub use transfer_poll_authority::*;
+pub use close_receipt::*;
+pub use close_voter::*;
+pub use commit_vote::*;
+pub use reveal_vote::*;
+pub use delegate_votes::*;
+pub use revoke_delegation::*;
+pub use begin_poll::*;
+pub use initialize_registry::*;
+pub use initialize_poll_auto::*;
+pub use expand_poll_text::*;
+pub use rank_options::*;
+pub use pause_poll::*;
+pub use resume_poll::*;
+pub use set_tags::*;
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::states::{Poll, Receipt, Versioned, ACCOUNT_VERSION};
+
+/// Emits every receipt passed in `remaining_accounts` as a single event so a
+/// voter can review their whole ballot in one call. Mutates nothing.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, MyReceipts<'info>>) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    let poll_key = ctx.accounts.poll.key();
+    let voter_key = ctx.accounts.voter_authority.key();
+
+    let mut entries = Vec::with_capacity(ctx.remaining_accounts.len());
+    for info in ctx.remaining_accounts.iter() {
+        let receipt = Account::<Receipt>::try_from(info)?;
+        require_keys_eq!(receipt.poll, poll_key, D21Error::PollMismatch);
+        require_keys_eq!(receipt.voter, voter_key, D21Error::Unauthorized);
+        entries.push(ReceiptEntry {
+            option_index: receipt.option_index,
+            sentiment: receipt.sentiment,
+        });
+    }
+
+    emit!(VoterReceipts { poll: poll_key, voter: voter_key, entries });
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ReceiptEntry {
+    pub option_index: u16,
+    pub sentiment: i8,
+}
+
+#[event]
+pub struct VoterReceipts {
+    pub poll: Pubkey,
+    pub voter: Pubkey,
+    pub entries: Vec<ReceiptEntry>,
+}
+
+#[derive(Accounts)]
+pub struct MyReceipts<'info> {
+    pub voter_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+    )]
+    pub poll: Account<'info, Poll>,
+}
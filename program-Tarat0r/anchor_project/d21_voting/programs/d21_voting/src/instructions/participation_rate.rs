@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::states::{Poll, Versioned, ACCOUNT_VERSION};
+
+/// Reports turnout against the AllowedVoter allowlist as basis points.
+/// Only allowlisted voters can vote once it has entries, so the rate stays
+/// within 10000. An empty allowlist, including a merkle-gated poll, reports
+/// a rate of 0. Mutates nothing.
+pub fn handler(ctx: Context<ParticipationRate>) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    let poll = &ctx.accounts.poll;
+    let voted = poll.total_voters;
+    let allowed = poll.allowed_count;
+    let rate_bps = if allowed == 0 {
+        0
+    } else {
+        voted as u64 * 10_000 / allowed as u64
+    };
+
+    emit!(Participation { poll: poll.key(), voted, allowed, rate_bps });
+    Ok(())
+}
+
+#[event]
+pub struct Participation {
+    pub poll: Pubkey,
+    pub voted: u32,
+    pub allowed: u32,
+    pub rate_bps: u64,
+}
+
+#[derive(Accounts)]
+pub struct ParticipationRate<'info> {
+    #[account(
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+    )]
+    pub poll: Account<'info, Poll>,
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::states::{Poll, Versioned, ACCOUNT_VERSION};
+
+/// Halts voting until `resume_poll`. The clock keeps running: a pause does
+/// not move `end_ts`, so time spent paused is lost to voters. To give it
+/// back, the authority can call `extend_voting_window` with
+/// `end_ts + paused_secs` before the original end, then `resume_poll`.
+pub fn handler(ctx: Context<PausePoll>) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    let now = Clock::get()?.unix_timestamp;
+    let poll = &mut ctx.accounts.poll;
+    require!(now <= poll.vote_deadline(), D21Error::VotingClosed);
+
+    poll.paused = true;
+
+    emit!(PollPaused { poll: poll.key(), paused_ts: now });
+    Ok(())
+}
+
+#[event]
+pub struct PollPaused {
+    pub poll: Pubkey,
+    pub paused_ts: i64,
+}
+
+#[derive(Accounts)]
+pub struct PausePoll<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+        has_one = authority @ D21Error::Unauthorized,
+        constraint = !poll.ended @ D21Error::VotingClosed,
+        constraint = !poll.paused @ D21Error::PollPaused,
+    )]
+    pub poll: Account<'info, Poll>,
+}
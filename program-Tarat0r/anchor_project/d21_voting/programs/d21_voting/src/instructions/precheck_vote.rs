@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::instructions::cast_vote::check_vote;
+use crate::states::{AllowedVoter, OptionNode, Poll, Receipt, Voter, Versioned, ACCOUNT_VERSION};
+
+/// Dry run of `cast_vote` for the given voter. Applies the same
+/// commit-reveal guard, allowlist and `check_vote` rules and reports the
+/// first failure as a reason code. Mutates nothing.
+pub fn handler(ctx: Context<PrecheckVote>, _index: u16, sentiment: i8, proof: Vec<[u8; 32]>) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    ctx.accounts.option_node.assert_version(ACCOUNT_VERSION)?;
+    let now = Clock::get()?.unix_timestamp;
+    let poll = &ctx.accounts.poll;
+
+    let voter = if ctx.accounts.voter.data_is_empty() {
+        Voter::default()
+    } else {
+        let voter = Voter::try_deserialize(&mut &ctx.accounts.voter.try_borrow_data()?[..])?;
+        voter.assert_version(ACCOUNT_VERSION)?;
+        voter
+    };
+    let (already_voted, weight) = if ctx.accounts.receipt.data_is_empty() {
+        (false, 0)
+    } else {
+        let receipt = Receipt::try_deserialize(&mut &ctx.accounts.receipt.try_borrow_data()?[..])?;
+        (true, receipt.weight)
+    };
+
+    // commit-reveal polls only take votes through reveal_vote
+    let checked = if poll.is_commit_reveal() {
+        Err(D21Error::UnsupportedVotingMode)
+    } else {
+        poll.check_allowlist(
+            &poll.key(),
+            &ctx.accounts.voter_authority.key(),
+            &proof,
+            ctx.accounts.allowed_voter.as_deref(),
+        )
+        .and_then(|()| check_vote(poll, &voter, already_voted, weight, sentiment, now))
+    };
+    let reason = match checked {
+        Ok(()) => 0,
+        Err(e) => reason_code(e),
+    };
+
+    emit!(VotePrecheck {
+        poll: poll.key(),
+        voter: ctx.accounts.voter_authority.key(),
+        option_index: ctx.accounts.option_node.index,
+        allowed: reason == 0,
+        reason,
+    });
+    Ok(())
+}
+
+/// Stable codes for `VotePrecheck::reason`; 0 means the vote would go through.
+fn reason_code(e: D21Error) -> u8 {
+    match e {
+        D21Error::VotingNotStarted => 1,
+        D21Error::VotingClosed => 2,
+        D21Error::InvalidSentiment => 3,
+        D21Error::AlreadyVotedThisOption => 4,
+        D21Error::OutOfPositiveCredits => 5,
+        D21Error::OutOfNegativeCredits => 6,
+        D21Error::InsufficientPositivesForNegative => 7,
+        D21Error::NotEnoughOptions => 8,
+        D21Error::PollNotActive => 9,
+        D21Error::PollPaused => 10,
+        D21Error::VoteCooldownActive => 11,
+        D21Error::TooManyVotes => 12,
+        D21Error::UnsupportedVotingMode => 13,
+        D21Error::NotOnAllowlist => 14,
+        _ => u8::MAX,
+    }
+}
+
+#[event]
+pub struct VotePrecheck {
+    pub poll: Pubkey,
+    pub voter: Pubkey,
+    pub option_index: u16,
+    pub allowed: bool,
+    pub reason: u8,
+}
+
+#[derive(Accounts)]
+#[instruction(index: u16)]
+pub struct PrecheckVote<'info> {
+    /// CHECK: only used to derive the voter and receipt addresses
+    pub voter_authority: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+    )]
+    pub poll: Account<'info, Poll>,
+
+    // the voter's allowlist entry, only needed while poll.allowed_count > 0
+    pub allowed_voter: Option<Account<'info, AllowedVoter>>,
+
+    #[account(
+        seeds = [b"option", poll.key().as_ref(), &index.to_le_bytes()],
+        bump,
+        constraint = option_node.poll == poll.key() @ D21Error::PollMismatch,
+    )]
+    pub option_node: Account<'info, OptionNode>,
+
+    /// CHECK: may not exist yet; deserialized as a Voter when it does
+    #[account(
+        seeds = [b"voter", poll.key().as_ref(), voter_authority.key().as_ref()],
+        bump
+    )]
+    pub voter: UncheckedAccount<'info>,
+
+    /// CHECK: may not exist yet; deserialized as a Receipt when it does
+    #[account(
+        seeds = [b"receipt", poll.key().as_ref(), &index.to_le_bytes(), voter_authority.key().as_ref()],
+        bump
+    )]
+    pub receipt: UncheckedAccount<'info>,
+}
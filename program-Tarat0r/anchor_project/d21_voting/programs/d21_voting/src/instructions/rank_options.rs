@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use std::cmp::Reverse;
+use crate::errors::D21Error;
+use crate::states::{OptionNode, Poll, Versioned, ACCOUNT_VERSION};
+
+/// Emits every option index ordered by net score, best first, with ties going
+/// to the lowest index. Unlike `compute_winner` plus votes do not break ties.
+/// All of the poll's options must be passed in `remaining_accounts`, in any
+/// order. Mutates nothing.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, RankOptions<'info>>) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    let poll = &ctx.accounts.poll;
+    let mut scores: Vec<(u16, i64)> = Vec::with_capacity(ctx.remaining_accounts.len());
+    for info in ctx.remaining_accounts.iter() {
+        let option = Account::<OptionNode>::try_from(info)?;
+        option.assert_version(ACCOUNT_VERSION)?;
+        require_keys_eq!(option.poll, poll.key(), D21Error::PollMismatch);
+        require!(
+            scores.iter().all(|(i, _)| *i != option.index),
+            D21Error::DuplicateOptionAccount
+        );
+        scores.push((option.index, option.net_score()));
+    }
+    require!(
+        scores.len() == poll.options_count as usize,
+        D21Error::OptionCountMismatch
+    );
+    scores.sort_by_key(|(index, net)| (Reverse(*net), *index));
+
+    emit!(RankingComputed {
+        poll: poll.key(),
+        ordered_indices: scores.into_iter().map(|(index, _)| index).collect(),
+    });
+    Ok(())
+}
+
+#[event]
+pub struct RankingComputed {
+    pub poll: Pubkey,
+    pub ordered_indices: Vec<u16>,
+}
+
+#[derive(Accounts)]
+pub struct RankOptions<'info> {
+    #[account(
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+    )]
+    pub poll: Account<'info, Poll>,
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::states::{Admins, Poll, Versioned, ACCOUNT_VERSION};
+
+/// Revokes every role of `key`. Only the poll authority may call this.
+pub fn handler(ctx: Context<RemoveAdmin>, key: Pubkey) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    let poll_key = ctx.accounts.poll.key();
+    let admins = &mut ctx.accounts.admins;
+    require_keys_eq!(ctx.accounts.signer.key(), ctx.accounts.poll.authority, D21Error::Unauthorized);
+
+    let before = admins.entries.len();
+    admins.entries.retain(|e| e.key != key);
+    require!(admins.entries.len() < before, D21Error::AdminNotFound);
+
+    emit!(AdminRemoved { poll: poll_key, key });
+    Ok(())
+}
+
+#[event]
+pub struct AdminRemoved {
+    pub poll: Pubkey,
+    pub key: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct RemoveAdmin<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+    )]
+    pub poll: Account<'info, Poll>,
+
+    #[account(
+        mut,
+        seeds = [b"admins", poll.key().as_ref()],
+        bump
+    )]
+    pub admins: Account<'info, Admins>,
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::labels;
+use crate::states::{LabelGuard, OptionNode, Poll, Versioned, ACCOUNT_VERSION};
+
+/// Deletes the last option before voting starts and refunds its OptionNode
+/// and LabelGuard rent to the authority, which frees the label for reuse.
+/// Only the highest index can go, so option indices stay contiguous.
+pub fn handler(ctx: Context<RemoveOption>, index: u16) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    ctx.accounts.option_node.assert_version(ACCOUNT_VERSION)?;
+    let poll = &mut ctx.accounts.poll;
+    require!(!poll.options_sealed, D21Error::OptionsSealed);
+    require!(Clock::get()?.unix_timestamp < poll.start_ts, D21Error::VotingStarted);
+    require!(index.saturating_add(1) == poll.options_count, D21Error::NonContiguousOptionIndex);
+
+    poll.options_count = index;
+
+    emit!(OptionRemoved { poll: poll.key(), index });
+    Ok(())
+}
+
+#[event]
+pub struct OptionRemoved {
+    pub poll: Pubkey,
+    pub index: u16,
+}
+
+#[derive(Accounts)]
+#[instruction(index: u16)]
+pub struct RemoveOption<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+        has_one = authority @ D21Error::Unauthorized,
+    )]
+    pub poll: Account<'info, Poll>,
+
+    #[account(
+        mut,
+        seeds = [b"option", poll.key().as_ref(), &index.to_le_bytes()],
+        bump,
+        constraint = option_node.poll == poll.key() @ D21Error::PollMismatch,
+        close = authority,
+    )]
+    pub option_node: Account<'info, OptionNode>,
+
+    #[account(
+        mut,
+        seeds = [b"option_label", poll.key().as_ref(), &labels::label_seed(&option_node.label)],
+        bump,
+        constraint = label_guard.poll == poll.key() @ D21Error::PollMismatch,
+        close = authority,
+    )]
+    pub label_guard: Account<'info, LabelGuard>,
+}
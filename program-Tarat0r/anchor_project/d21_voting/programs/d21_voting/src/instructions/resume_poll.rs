@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::states::{Poll, Versioned, ACCOUNT_VERSION};
+
+/// Lifts a `pause_poll`. Votes are accepted again from here on, as long as
+/// the voting window is still open.
+pub fn handler(ctx: Context<ResumePoll>) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    let poll = &mut ctx.accounts.poll;
+    poll.paused = false;
+
+    emit!(PollResumed { poll: poll.key(), resumed_ts: Clock::get()?.unix_timestamp });
+    Ok(())
+}
+
+#[event]
+pub struct PollResumed {
+    pub poll: Pubkey,
+    pub resumed_ts: i64,
+}
+
+#[derive(Accounts)]
+pub struct ResumePoll<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+        has_one = authority @ D21Error::Unauthorized,
+        constraint = poll.paused @ D21Error::PollNotPaused,
+    )]
+    pub poll: Account<'info, Poll>,
+}
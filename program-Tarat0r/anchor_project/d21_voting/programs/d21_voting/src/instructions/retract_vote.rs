@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::ranking::{self, Standing};
+use crate::instructions::cast_vote::quadratic_cost;
+use crate::states::{OptionNode, Poll, Receipt, Voter, VotingMode, Versioned, ACCOUNT_VERSION};
+
+/// Takes a vote back before the poll ends and refunds the receipt rent to
+/// the voter. A plus vote can only be retracted while the remaining plus
+/// votes still cover the voter's minus votes (P >= neg_ratio*M). A quadratic
+/// receipt takes back all of its weight and refunds the credits it cost.
+pub fn handler(ctx: Context<RetractVote>, _index: u16) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    ctx.accounts.option_node.assert_version(ACCOUNT_VERSION)?;
+    ctx.accounts.voter.assert_version(ACCOUNT_VERSION)?;
+    let now = Clock::get()?.unix_timestamp;
+    let poll = &mut ctx.accounts.poll;
+    require!(now <= poll.vote_deadline(), D21Error::VotingClosed);
+    require!(!poll.paused, D21Error::PollPaused);
+
+    let option = &mut ctx.accounts.option_node;
+    let voter = &mut ctx.accounts.voter;
+    let sentiment = ctx.accounts.receipt.sentiment;
+    let weight = ctx.accounts.receipt.weight;
+
+    if poll.mode == VotingMode::Quadratic {
+        let refund: u32 = (1..=weight).map(quadratic_cost).sum();
+        voter.credits_remaining = voter.credits_remaining.checked_add(refund).ok_or(D21Error::MathOverflow)?;
+        option.plus_votes = option.plus_votes.checked_sub(weight as u32).ok_or(D21Error::MathOverflow)?;
+        poll.total_plus = poll.total_plus.checked_sub(weight as u64).ok_or(D21Error::MathOverflow)?;
+    } else if sentiment == 0 {
+        option.abstain_votes = option.abstain_votes.checked_sub(1).ok_or(D21Error::MathOverflow)?;
+    } else if sentiment == 1 {
+        let p_next = voter.used_plus.checked_sub(1).ok_or(D21Error::MathOverflow)?;
+        require!(
+            p_next as u16 >= poll.neg_ratio as u16 * voter.used_minus as u16,
+            D21Error::InsufficientPositivesForNegative
+        );
+        voter.used_plus = p_next;
+        option.plus_votes = option.plus_votes.checked_sub(1).ok_or(D21Error::MathOverflow)?;
+        poll.total_plus = poll.total_plus.checked_sub(1).ok_or(D21Error::MathOverflow)?;
+    } else {
+        voter.used_minus = voter.used_minus.checked_sub(1).ok_or(D21Error::MathOverflow)?;
+        option.minus_votes = option.minus_votes.checked_sub(1).ok_or(D21Error::MathOverflow)?;
+        poll.total_minus = poll.total_minus.checked_sub(1).ok_or(D21Error::MathOverflow)?;
+    }
+    // frees a slot under poll.max_votes_per_voter
+    voter.options_voted = voter.options_voted.saturating_sub(1);
+
+    ranking::track_leader(poll, Standing {
+        index: option.index,
+        plus_votes: option.plus_votes,
+        minus_votes: option.minus_votes,
+    });
+
+    emit!(VoteRetracted {
+        poll: poll.key(),
+        voter: voter.voter,
+        option_index: option.index,
+        sentiment,
+        used_plus: voter.used_plus,
+        used_minus: voter.used_minus,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct VoteRetracted {
+    pub poll: Pubkey,
+    pub voter: Pubkey,
+    pub option_index: u16,
+    pub sentiment: i8,
+    pub used_plus: u8,
+    pub used_minus: u8,
+}
+
+#[derive(Accounts)]
+#[instruction(index: u16)]
+pub struct RetractVote<'info> {
+    #[account(mut)]
+    pub voter_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+        constraint = !poll.ended @ D21Error::VotingClosed,
+    )]
+    pub poll: Account<'info, Poll>,
+
+    #[account(
+        mut,
+        seeds = [b"option", poll.key().as_ref(), &index.to_le_bytes()],
+        bump,
+        constraint = option_node.poll == poll.key() @ D21Error::PollMismatch,
+    )]
+    pub option_node: Account<'info, OptionNode>,
+
+    #[account(
+        mut,
+        seeds = [b"voter", poll.key().as_ref(), voter_authority.key().as_ref()],
+        bump
+    )]
+    pub voter: Account<'info, Voter>,
+
+    #[account(
+        mut,
+        seeds = [b"receipt", poll.key().as_ref(), &index.to_le_bytes(), voter_authority.key().as_ref()],
+        bump,
+        close = voter_authority,
+    )]
+    pub receipt: Account<'info, Receipt>,
+}
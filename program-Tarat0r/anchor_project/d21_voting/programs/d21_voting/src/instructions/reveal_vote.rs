@@ -0,0 +1,154 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::instructions::cast_vote::check_vote;
+use crate::ranking::{self, Standing};
+use crate::states::{AllowedVoter, Commitment, OptionNode, Poll, Receipt, Voter, VoteCast, Versioned, ACCOUNT_VERSION};
+
+/// Opens a commitment during the reveal phase and applies the vote through
+/// the same `check_vote` rules as `cast_vote`. The commitment rent goes back
+/// to the voter.
+pub fn handler(
+    ctx: Context<RevealVote>,
+    index: u16,
+    sentiment: i8,
+    nonce: [u8; 32],
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    ctx.accounts.option_node.assert_version(ACCOUNT_VERSION)?;
+    let now = Clock::get()?.unix_timestamp;
+    let poll = &mut ctx.accounts.poll;
+    require!(now > poll.commit_end_ts, D21Error::RevealNotStarted);
+    require!(now <= poll.reveal_end_ts, D21Error::VotingClosed);
+    let poll_key = poll.key();
+    poll.check_allowlist(
+        &poll_key,
+        &ctx.accounts.voter_authority.key(),
+        &proof,
+        ctx.accounts.allowed_voter.as_deref(),
+    )?;
+
+    let option = &mut ctx.accounts.option_node;
+    require!(option.index == index, D21Error::PollMismatch);
+    require!(
+        Commitment::hash(option.index, sentiment, &nonce) == ctx.accounts.commitment.commitment,
+        D21Error::CommitmentMismatch
+    );
+
+    let voter = &mut ctx.accounts.voter;
+    if voter.poll == Pubkey::default() {
+        voter.poll = poll.key();
+        voter.voter = ctx.accounts.voter_authority.key();
+        voter.used_plus = 0;
+        voter.used_minus = 0;
+        voter.credits_remaining = poll.plus_credits as u32;
+        voter.version = ACCOUNT_VERSION;
+        voter.last_vote_ts = 0;
+        voter.options_voted = 0;
+        poll.total_voters = poll.total_voters.checked_add(1).ok_or(D21Error::MathOverflow)?;
+    } else {
+        voter.assert_version(ACCOUNT_VERSION)?;
+    }
+
+    let receipt = &mut ctx.accounts.receipt;
+    check_vote(poll, voter, receipt.poll != Pubkey::default(), receipt.weight, sentiment, now)?;
+
+    match sentiment {
+        1 => {
+            voter.used_plus = voter.used_plus.checked_add(1).ok_or(D21Error::MathOverflow)?;
+            option.plus_votes = option.plus_votes.checked_add(1).ok_or(D21Error::MathOverflow)?;
+            poll.total_plus = poll.total_plus.checked_add(1).ok_or(D21Error::MathOverflow)?;
+        }
+        -1 => {
+            voter.used_minus = voter.used_minus.checked_add(1).ok_or(D21Error::MathOverflow)?;
+            option.minus_votes = option.minus_votes.checked_add(1).ok_or(D21Error::MathOverflow)?;
+            poll.total_minus = poll.total_minus.checked_add(1).ok_or(D21Error::MathOverflow)?;
+        }
+        0 => {
+            option.abstain_votes = option.abstain_votes.checked_add(1).ok_or(D21Error::MathOverflow)?;
+        }
+        _ => unreachable!(),
+    }
+
+    ranking::track_leader(poll, Standing {
+        index: option.index,
+        plus_votes: option.plus_votes,
+        minus_votes: option.minus_votes,
+    });
+
+    receipt.poll = poll.key();
+    receipt.voter = voter.voter;
+    receipt.option_index = option.index;
+    receipt.sentiment = sentiment;
+    receipt.note = None;
+    receipt.weight = 1;
+    voter.last_vote_ts = now;
+    voter.options_voted = voter.options_voted.checked_add(1).ok_or(D21Error::MathOverflow)?;
+
+    emit!(VoteCast {
+        poll: poll.key(),
+        voter: voter.voter,
+        option_index: option.index,
+        sentiment,
+        used_plus: voter.used_plus,
+        used_minus: voter.used_minus,
+        note: None,
+        option_plus_votes: option.plus_votes,
+        option_minus_votes: option.minus_votes,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(index: u16)]
+pub struct RevealVote<'info> {
+    #[account(mut)]
+    pub voter_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+        constraint = !poll.ended @ D21Error::VotingClosed,
+    )]
+    pub poll: Account<'info, Poll>,
+
+    #[account(
+        mut,
+        seeds = [b"option", poll.key().as_ref(), &index.to_le_bytes()],
+        bump,
+        constraint = option_node.poll == poll.key() @ D21Error::PollMismatch,
+    )]
+    pub option_node: Account<'info, OptionNode>,
+
+    #[account(
+        mut,
+        seeds = [b"commitment", poll.key().as_ref(), voter_authority.key().as_ref()],
+        bump,
+        close = voter_authority,
+    )]
+    pub commitment: Account<'info, Commitment>,
+
+    // voter_authority's allowlist entry, only needed while poll.allowed_count > 0
+    pub allowed_voter: Option<Account<'info, AllowedVoter>>,
+
+    #[account(
+        init_if_needed,
+        payer = voter_authority,
+        space = 8 + Voter::INIT_SPACE,
+        seeds = [b"voter", poll.key().as_ref(), voter_authority.key().as_ref()],
+        bump
+    )]
+    pub voter: Account<'info, Voter>,
+
+    #[account(
+        init_if_needed,
+        payer = voter_authority,
+        space = 8 + Receipt::INIT_SPACE,
+        seeds = [b"receipt", poll.key().as_ref(), &index.to_le_bytes(), voter_authority.key().as_ref()],
+        bump
+    )]
+    pub receipt: Account<'info, Receipt>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::states::{Delegation, Poll, Versioned, ACCOUNT_VERSION};
+
+/// Closes the delegator's Delegation, refunding its rent. Votes the delegate
+/// already cast stay counted.
+pub fn handler(ctx: Context<RevokeDelegation>) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    emit!(DelegationRevoked {
+        poll: ctx.accounts.poll.key(),
+        delegator: ctx.accounts.delegator.key(),
+        delegate: ctx.accounts.delegation.delegate,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct DelegationRevoked {
+    pub poll: Pubkey,
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct RevokeDelegation<'info> {
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+
+    #[account(
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+    )]
+    pub poll: Account<'info, Poll>,
+
+    #[account(
+        mut,
+        seeds = [b"delegation", poll.key().as_ref(), delegator.key().as_ref()],
+        bump,
+        close = delegator,
+    )]
+    pub delegation: Account<'info, Delegation>,
+}
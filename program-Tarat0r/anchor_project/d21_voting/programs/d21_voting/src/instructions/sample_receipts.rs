@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::states::{Poll, Receipt, Versioned, ACCOUNT_VERSION};
+
+/// Validates every `stride`-th receipt of `remaining_accounts`, starting at
+/// `offset`, so an auditor can replay the exact same sample. A sampled
+/// receipt is valid when it belongs to the poll, sits at its
+/// `["receipt", poll, option_index, voter]` address, names an existing
+/// option and carries a +1/0/-1 sentiment. Mutates nothing.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SampleReceipts<'info>>,
+    stride: u16,
+    offset: u16,
+) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    require!(stride > 0, D21Error::InvalidStride);
+    let poll = &ctx.accounts.poll;
+    let poll_key = poll.key();
+
+    let mut checked: u32 = 0;
+    let mut all_valid = true;
+    for info in ctx.remaining_accounts.iter().skip(offset as usize).step_by(stride as usize) {
+        let receipt = Account::<Receipt>::try_from(info)?;
+        let (expected, _) = Pubkey::find_program_address(
+            &[b"receipt", poll_key.as_ref(), &receipt.option_index.to_le_bytes(), receipt.voter.as_ref()],
+            &crate::ID,
+        );
+        all_valid &= receipt.poll == poll_key
+            && info.key() == expected
+            && receipt.option_index < poll.options_count
+            && matches!(receipt.sentiment, 1 | 0 | -1);
+        checked += 1;
+    }
+
+    emit!(SampleResult { poll: poll_key, stride, offset, checked, all_valid });
+    Ok(())
+}
+
+#[event]
+pub struct SampleResult {
+    pub poll: Pubkey,
+    pub stride: u16,
+    pub offset: u16,
+    pub checked: u32,
+    pub all_valid: bool,
+}
+
+#[derive(Accounts)]
+pub struct SampleReceipts<'info> {
+    #[account(
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+    )]
+    pub poll: Account<'info, Poll>,
+}
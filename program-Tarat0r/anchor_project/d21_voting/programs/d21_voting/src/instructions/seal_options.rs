@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::states::{Admins, Poll, Role, Versioned, ACCOUNT_VERSION};
+
+/// Declares the ballot final: no more options can be added, even before
+/// `start_ts`. Requires OptionManager.
+pub fn handler(ctx: Context<SealOptions>) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    let poll_key = ctx.accounts.poll.key();
+    let signer = ctx.accounts.signer.key();
+    let poll = &mut ctx.accounts.poll;
+    poll.require_role(&poll_key, ctx.accounts.admins.as_deref(), &signer, Role::OptionManager)?;
+
+    poll.options_sealed = true;
+
+    emit!(BallotSealed { poll: poll_key, options_count: poll.options_count });
+    Ok(())
+}
+
+#[event]
+pub struct BallotSealed {
+    pub poll: Pubkey,
+    pub options_count: u16,
+}
+
+#[derive(Accounts)]
+pub struct SealOptions<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+        constraint = !poll.options_sealed @ D21Error::OptionsSealed
+    )]
+    pub poll: Account<'info, Poll>,
+
+    // only needed when the signer is not poll.authority
+    pub admins: Option<Account<'info, Admins>>,
+}
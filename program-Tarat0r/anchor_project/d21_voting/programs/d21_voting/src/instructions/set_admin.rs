@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::states::{AdminEntry, Admins, Poll, Role, Versioned, ACCOUNT_VERSION, MAX_ADMINS};
+
+/// Grants `key` the given role, replacing any role it already had.
+/// Only the poll authority manages the admin list.
+pub fn handler(ctx: Context<SetAdmin>, key: Pubkey, role: Role) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    let poll_key = ctx.accounts.poll.key();
+    let admins = &mut ctx.accounts.admins;
+    if admins.poll == Pubkey::default() {
+        admins.poll = poll_key;
+    }
+    require_keys_eq!(ctx.accounts.signer.key(), ctx.accounts.poll.authority, D21Error::Unauthorized);
+
+    match admins.entries.iter_mut().find(|e| e.key == key) {
+        Some(entry) => entry.role = role,
+        None => {
+            require!(admins.entries.len() < MAX_ADMINS, D21Error::TooManyAdmins);
+            admins.entries.push(AdminEntry { key, role });
+        }
+    }
+
+    emit!(AdminSet { poll: poll_key, key, role });
+    Ok(())
+}
+
+#[event]
+pub struct AdminSet {
+    pub poll: Pubkey,
+    pub key: Pubkey,
+    pub role: Role,
+}
+
+#[derive(Accounts)]
+pub struct SetAdmin<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+    )]
+    pub poll: Account<'info, Poll>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + Admins::INIT_SPACE,
+        seeds = [b"admins", poll.key().as_ref()],
+        bump
+    )]
+    pub admins: Account<'info, Admins>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::instructions::initialize_poll::{bump_tag_index, drop_tag_index, validate_tags};
+use crate::states::{Poll, Versioned, ACCOUNT_VERSION};
+
+/// Replaces the poll's tags before voting starts, under the same limits as
+/// `initialize_poll`. The tag indexes are kept in step: `remaining_accounts`
+/// holds the TagIndex of every dropped tag (in the old order) followed by
+/// that of every new tag (in the given order). Tags kept on both sides need
+/// no account.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, SetTags<'info>>, tags: Vec<String>) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    let poll = &mut ctx.accounts.poll;
+    require!(Clock::get()?.unix_timestamp < poll.start_ts, D21Error::VotingStarted);
+    validate_tags(&tags)?;
+
+    let dropped: Vec<String> = poll.tags.iter().filter(|t| !tags.contains(t)).cloned().collect();
+    let added: Vec<String> = tags.iter().filter(|t| !poll.tags.contains(t)).cloned().collect();
+    require!(
+        ctx.remaining_accounts.len() == dropped.len() + added.len(),
+        D21Error::TagIndexMismatch
+    );
+    let (dropped_indexes, added_indexes) = ctx.remaining_accounts.split_at(dropped.len());
+    for (tag, info) in dropped.iter().zip(dropped_indexes.iter()) {
+        drop_tag_index(tag, info)?;
+    }
+    let payer = ctx.accounts.authority.to_account_info();
+    let system = ctx.accounts.system_program.to_account_info();
+    for (tag, info) in added.iter().zip(added_indexes.iter()) {
+        bump_tag_index(tag, info, &payer, &system)?;
+    }
+
+    poll.tags = tags;
+
+    emit!(PollTagsUpdated { poll: poll.key(), tags: poll.tags.clone() });
+    Ok(())
+}
+
+#[event]
+pub struct PollTagsUpdated {
+    pub poll: Pubkey,
+    pub tags: Vec<String>,
+}
+
+#[derive(Accounts)]
+pub struct SetTags<'info> {
+    // pays for TagIndex accounts of tags nobody used before
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+        has_one = authority @ D21Error::Unauthorized,
+    )]
+    pub poll: Account<'info, Poll>,
+
+    pub system_program: Program<'info, System>,
+}
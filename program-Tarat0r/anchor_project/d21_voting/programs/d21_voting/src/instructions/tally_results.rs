@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::ranking;
+use crate::states::{Poll, Versioned, ACCOUNT_VERSION};
+
+/// Emits one OptionTallied per option followed by the poll totals in
+/// ResultsTallied. All of the poll's options must be passed in
+/// `remaining_accounts`, in any order; they are reported in index order.
+/// Mutates nothing.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, TallyResults<'info>>) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    let poll = &ctx.accounts.poll;
+    let mut standings = ranking::load_standings(&poll.key(), ctx.remaining_accounts)?;
+    require!(
+        standings.len() == poll.options_count as usize,
+        D21Error::OptionCountMismatch
+    );
+    standings.sort_by_key(|s| s.index);
+
+    let (mut total_plus, mut total_minus) = (0u64, 0u64);
+    for s in standings.iter() {
+        total_plus += s.plus_votes as u64;
+        total_minus += s.minus_votes as u64;
+        emit!(OptionTallied {
+            poll: poll.key(),
+            index: s.index,
+            plus_votes: s.plus_votes,
+            minus_votes: s.minus_votes,
+            net: s.net(),
+        });
+    }
+
+    emit!(ResultsTallied {
+        poll: poll.key(),
+        total_plus,
+        total_minus,
+        option_count: poll.options_count,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct OptionTallied {
+    pub poll: Pubkey,
+    pub index: u16,
+    pub plus_votes: u32,
+    pub minus_votes: u32,
+    pub net: i64,
+}
+
+#[event]
+pub struct ResultsTallied {
+    pub poll: Pubkey,
+    pub total_plus: u64,
+    pub total_minus: u64,
+    pub option_count: u16,
+}
+
+#[derive(Accounts)]
+pub struct TallyResults<'info> {
+    #[account(
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+    )]
+    pub poll: Account<'info, Poll>,
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::states::{Poll, Versioned, ACCOUNT_VERSION};
+
+/// Hands the poll to `new_authority`. The poll address does not change: its
+/// seeds use `poll.creator`, which stays the original authority, so clients
+/// keep deriving it from the creator's key.
+pub fn handler(ctx: Context<TransferPollAuthority>, new_authority: Pubkey) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    let poll = &mut ctx.accounts.poll;
+    let old = poll.authority;
+    poll.authority = new_authority;
+
+    emit!(AuthorityTransferred { poll: poll.key(), old, new: new_authority });
+    Ok(())
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub poll: Pubkey,
+    pub old: Pubkey,
+    pub new: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct TransferPollAuthority<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+        has_one = authority @ D21Error::Unauthorized,
+    )]
+    pub poll: Account<'info, Poll>,
+}
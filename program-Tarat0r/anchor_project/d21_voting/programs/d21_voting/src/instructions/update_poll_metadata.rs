@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::errors::D21Error;
+use crate::states::{Poll, Versioned, ACCOUNT_VERSION, MAX_DESC, MAX_TITLE};
+
+/// Edits the title and/or description before voting starts. Fields passed as
+/// `None` are left as they are.
+pub fn handler(ctx: Context<UpdatePollMetadata>, new_title: Option<String>, new_desc: Option<String>) -> Result<()> {
+    ctx.accounts.poll.assert_version(ACCOUNT_VERSION)?;
+    let poll = &mut ctx.accounts.poll;
+    require!(Clock::get()?.unix_timestamp < poll.start_ts, D21Error::VotingStarted);
+
+    let title_changed = new_title.is_some();
+    let description_changed = new_desc.is_some();
+    if let Some(title) = new_title {
+        require!(title.len() <= MAX_TITLE, D21Error::TitleTooLong);
+        poll.title = title;
+    }
+    if let Some(desc) = new_desc {
+        require!(desc.len() <= MAX_DESC, D21Error::DescriptionTooLong);
+        poll.description = desc;
+    }
+
+    emit!(PollMetadataUpdated { poll: poll.key(), title_changed, description_changed });
+    Ok(())
+}
+
+#[event]
+pub struct PollMetadataUpdated {
+    pub poll: Pubkey,
+    pub title_changed: bool,
+    pub description_changed: bool,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePollMetadata<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"poll", poll.creator.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump,
+        has_one = authority @ D21Error::Unauthorized,
+    )]
+    pub poll: Account<'info, Poll>,
+}
@@ -0,0 +1,15 @@
+use anchor_lang::solana_program::hash;
+use unicode_normalization::UnicodeNormalization;
+
+/// Canonical form of an option label: NFC-normalized, surrounding whitespace
+/// trimmed, inner whitespace runs collapsed to a single space, lowercased.
+/// Clients must hash exactly this string to build `label_seed`.
+pub fn canonicalize_label(label: &str) -> String {
+    let normalized: String = label.nfc().collect();
+    normalized.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+/// sha256 of the canonical label, used as the LabelGuard seed.
+pub fn label_seed(label: &str) -> [u8; 32] {
+    hash::hash(canonicalize_label(label).as_bytes()).to_bytes()
+}
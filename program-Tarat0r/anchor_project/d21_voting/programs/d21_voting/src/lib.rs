@@ -3,6 +3,9 @@ use anchor_lang::prelude::*;
 pub mod errors;
 pub mod states;
 pub mod instructions;
+pub mod labels;
+pub mod merkle;
+pub mod ranking;
 
 use instructions::*;
 
@@ -12,8 +15,8 @@ declare_id!("5K9LFpBoVfzaw6hjfL4XnuwC88p5xt3UJYX5LTfRrvkE");
 pub mod d21_voting {
     use super::*;
 
-    pub fn initialize_poll(
-        ctx: Context<InitializePoll>,
+    pub fn initialize_poll<'info>(
+        ctx: Context<'_, '_, 'info, 'info, InitializePoll<'info>>,
         cfg: PollConfig
     ) -> Result<()> {
         initialize_poll::handler(
@@ -23,12 +26,210 @@ pub mod d21_voting {
     }
 
 
-    pub fn add_option(ctx: Context<AddOption>, index: u16, label: String, label_seed: [u8; 32]) -> Result<()> {
-        add_option::handler(ctx, index, label, label_seed)
+    pub fn add_option(
+        ctx: Context<AddOption>,
+        index: u16,
+        label: String,
+        label_seed: [u8; 32],
+        description: String,
+        image_uri: String,
+    ) -> Result<()> {
+        add_option::handler(ctx, index, label, label_seed, description, image_uri)
+    }
+
+    pub fn cast_vote(
+        ctx: Context<CastVote>,
+        index: u16,
+        sentiment: i8,
+        note: Option<String>,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        cast_vote::handler(ctx, index, sentiment, note, proof)
+    }
+
+    pub fn canonicalize_label(ctx: Context<CanonicalizeLabel>, label: String) -> Result<()> {
+        canonicalize_label::handler(ctx, label)
+    }
+
+    pub fn my_receipts<'info>(ctx: Context<'_, '_, 'info, 'info, MyReceipts<'info>>) -> Result<()> {
+        my_receipts::handler(ctx)
+    }
+
+    pub fn compute_winner<'info>(ctx: Context<'_, '_, 'info, 'info, ComputeWinner<'info>>) -> Result<()> {
+        compute_winner::handler(ctx)
+    }
+
+    pub fn set_admin(ctx: Context<SetAdmin>, key: Pubkey, role: states::Role) -> Result<()> {
+        set_admin::handler(ctx, key, role)
+    }
+
+    pub fn remove_admin(ctx: Context<RemoveAdmin>, key: Pubkey) -> Result<()> {
+        remove_admin::handler(ctx, key)
+    }
+
+    pub fn end_poll(ctx: Context<EndPoll>) -> Result<()> {
+        end_poll::handler(ctx)
+    }
+
+    pub fn audit_credits<'info>(ctx: Context<'_, '_, 'info, 'info, AuditCredits<'info>>) -> Result<()> {
+        audit_credits::handler(ctx)
+    }
+
+    pub fn seal_options(ctx: Context<SealOptions>) -> Result<()> {
+        seal_options::handler(ctx)
+    }
+
+    pub fn add_allowed_voter(ctx: Context<AddAllowedVoter>, voter: Pubkey) -> Result<()> {
+        add_allowed_voter::handler(ctx, voter)
+    }
+
+    pub fn participation_rate(ctx: Context<ParticipationRate>) -> Result<()> {
+        participation_rate::handler(ctx)
+    }
+
+    pub fn sample_receipts<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SampleReceipts<'info>>,
+        stride: u16,
+        offset: u16,
+    ) -> Result<()> {
+        sample_receipts::handler(ctx, stride, offset)
+    }
+
+    pub fn close_poll_full<'info>(ctx: Context<'_, '_, 'info, 'info, ClosePollFull<'info>>) -> Result<()> {
+        close_poll_full::handler(ctx)
+    }
+
+    pub fn attest_result<'info>(ctx: Context<'_, '_, 'info, 'info, AttestResult<'info>>) -> Result<()> {
+        attest_result::handler(ctx)
+    }
+
+    pub fn precheck_vote(
+        ctx: Context<PrecheckVote>,
+        index: u16,
+        sentiment: i8,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        precheck_vote::handler(ctx, index, sentiment, proof)
+    }
+
+    pub fn close_poll(ctx: Context<ClosePoll>) -> Result<()> {
+        close_poll::handler(ctx)
+    }
+
+    pub fn finalize_poll<'info>(ctx: Context<'_, '_, 'info, 'info, FinalizePoll<'info>>) -> Result<()> {
+        finalize_poll::handler(ctx)
+    }
+
+    pub fn change_vote(ctx: Context<ChangeVote>, index: u16, sentiment: i8) -> Result<()> {
+        change_vote::handler(ctx, index, sentiment)
+    }
+
+    pub fn retract_vote(ctx: Context<RetractVote>, index: u16) -> Result<()> {
+        retract_vote::handler(ctx, index)
+    }
+
+    pub fn tally_results<'info>(ctx: Context<'_, '_, 'info, 'info, TallyResults<'info>>) -> Result<()> {
+        tally_results::handler(ctx)
+    }
+
+    pub fn remove_option(ctx: Context<RemoveOption>, index: u16) -> Result<()> {
+        remove_option::handler(ctx, index)
+    }
+
+    pub fn update_poll_metadata(
+        ctx: Context<UpdatePollMetadata>,
+        new_title: Option<String>,
+        new_desc: Option<String>,
+    ) -> Result<()> {
+        update_poll_metadata::handler(ctx, new_title, new_desc)
+    }
+
+    pub fn extend_voting_window(ctx: Context<ExtendVotingWindow>, new_end_ts: i64) -> Result<()> {
+        extend_voting_window::handler(ctx, new_end_ts)
+    }
+
+    pub fn cast_votes_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CastVotesBatch<'info>>,
+        votes: Vec<BatchVote>,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        cast_votes_batch::handler(ctx, votes, proof)
+    }
+
+    pub fn transfer_poll_authority(ctx: Context<TransferPollAuthority>, new_authority: Pubkey) -> Result<()> {
+        transfer_poll_authority::handler(ctx, new_authority)
+    }
+
+    pub fn close_receipt(ctx: Context<CloseReceipt>, index: u16) -> Result<()> {
+        close_receipt::handler(ctx, index)
+    }
+
+    pub fn close_voter(ctx: Context<CloseVoter>) -> Result<()> {
+        close_voter::handler(ctx)
+    }
+
+    pub fn commit_vote(ctx: Context<CommitVote>, commitment: [u8; 32]) -> Result<()> {
+        commit_vote::handler(ctx, commitment)
+    }
+
+    pub fn reveal_vote(
+        ctx: Context<RevealVote>,
+        index: u16,
+        sentiment: i8,
+        nonce: [u8; 32],
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        reveal_vote::handler(ctx, index, sentiment, nonce, proof)
     }
 
-    pub fn cast_vote(ctx: Context<CastVote>, index: u16, sentiment: i8) -> Result<()> {
-        cast_vote::handler(ctx, index, sentiment)
+    pub fn delegate_votes(ctx: Context<DelegateVotes>, delegate: Pubkey) -> Result<()> {
+        delegate_votes::handler(ctx, delegate)
+    }
+
+    pub fn revoke_delegation(ctx: Context<RevokeDelegation>) -> Result<()> {
+        revoke_delegation::handler(ctx)
+    }
+
+    pub fn begin_poll(ctx: Context<BeginPoll>) -> Result<()> {
+        begin_poll::handler(ctx)
+    }
+
+    pub fn initialize_registry(ctx: Context<InitializeRegistry>) -> Result<()> {
+        initialize_registry::handler(ctx)
+    }
+
+    pub fn initialize_poll_auto<'info>(
+        ctx: Context<'_, '_, 'info, 'info, InitializePollAuto<'info>>,
+        cfg: PollConfig,
+    ) -> Result<()> {
+        initialize_poll_auto::handler(ctx, cfg)
+    }
+
+    pub fn expand_poll_text(
+        ctx: Context<ExpandPollText>,
+        new_title: Option<String>,
+        new_desc: Option<String>,
+    ) -> Result<()> {
+        expand_poll_text::handler(ctx, new_title, new_desc)
+    }
+
+    pub fn rank_options<'info>(ctx: Context<'_, '_, 'info, 'info, RankOptions<'info>>) -> Result<()> {
+        rank_options::handler(ctx)
+    }
+
+    pub fn pause_poll(ctx: Context<PausePoll>) -> Result<()> {
+        pause_poll::handler(ctx)
+    }
+
+    pub fn resume_poll(ctx: Context<ResumePoll>) -> Result<()> {
+        resume_poll::handler(ctx)
+    }
+
+    pub fn set_tags<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SetTags<'info>>,
+        tags: Vec<String>,
+    ) -> Result<()> {
+        set_tags::handler(ctx, tags)
     }
 }
 
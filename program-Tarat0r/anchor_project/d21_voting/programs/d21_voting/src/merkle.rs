@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+/// Allowlist leaf of a voter: keccak256 of the public key bytes.
+pub fn leaf(voter: &Pubkey) -> [u8; 32] {
+    keccak::hash(voter.as_ref()).to_bytes()
+}
+
+/// Sorted-pair keccak256 merkle proof check: each step hashes the smaller
+/// of (node, sibling) first, so proofs carry no left/right flags.
+pub fn verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            keccak::hashv(&[&node, sibling]).to_bytes()
+        } else {
+            keccak::hashv(&[sibling, &node]).to_bytes()
+        }
+    });
+    computed == *root
+}
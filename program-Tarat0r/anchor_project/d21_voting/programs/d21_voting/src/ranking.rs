@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash;
+use std::cmp::Reverse;
+use crate::errors::D21Error;
+use crate::states::{OptionNode, Poll, Versioned, ACCOUNT_VERSION};
+
+/// Vote counts of one option, detached from its account.
+#[derive(Clone, Copy)]
+pub struct Standing {
+    pub index: u16,
+    pub plus_votes: u32,
+    pub minus_votes: u32,
+}
+impl Standing {
+    pub fn net(&self) -> i64 {
+        self.plus_votes as i64 - self.minus_votes as i64
+    }
+}
+
+/// Loads the OptionNode accounts of `poll` from `accounts`. Each option may
+/// appear only once; their order does not matter.
+pub fn load_standings<'info>(
+    poll: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+) -> Result<Vec<Standing>> {
+    let mut standings: Vec<Standing> = Vec::with_capacity(accounts.len());
+    for info in accounts.iter() {
+        let option = Account::<OptionNode>::try_from(info)?;
+        option.assert_version(ACCOUNT_VERSION)?;
+        require_keys_eq!(option.poll, *poll, D21Error::PollMismatch);
+        require!(
+            standings.iter().all(|s| s.index != option.index),
+            D21Error::DuplicateOptionAccount
+        );
+        standings.push(Standing {
+            index: option.index,
+            plus_votes: option.plus_votes,
+            minus_votes: option.minus_votes,
+        });
+    }
+    Ok(standings)
+}
+
+/// Sorts best first by `(net desc, plus_votes desc, index asc)`, so the
+/// result is the same whatever order the options were passed in.
+pub fn rank(standings: &mut [Standing]) {
+    standings.sort_by_key(sort_key);
+}
+
+fn sort_key(s: &Standing) -> (Reverse<i64>, Reverse<u32>, u16) {
+    (Reverse(s.net()), Reverse(s.plus_votes), s.index)
+}
+
+/// Updates the poll's cached leader after `changed` received a vote.
+///
+/// Only the changed option is compared against the cached leader, so this is
+/// exact for plus votes. A minus vote on the leader itself just lowers its
+/// cached score; another option may now rank higher without taking over
+/// until it receives a vote. `compute_winner` gives the exact answer.
+pub fn track_leader(poll: &mut Poll, changed: Standing) {
+    if changed.index == poll.leader_index {
+        poll.leader_net = changed.net();
+        poll.leader_plus = changed.plus_votes;
+        return;
+    }
+    let leader = Standing {
+        index: poll.leader_index,
+        plus_votes: poll.leader_plus,
+        minus_votes: (poll.leader_plus as i64 - poll.leader_net) as u32,
+    };
+    if sort_key(&changed) < sort_key(&leader) {
+        poll.leader_index = changed.index;
+        poll.leader_net = changed.net();
+        poll.leader_plus = changed.plus_votes;
+    }
+}
+
+/// sha256 over `(index, net)` of every option in index order, each as
+/// little-endian u16 then i64, so it does not depend on the account order.
+pub fn scores_hash(standings: &[Standing]) -> [u8; 32] {
+    let mut sorted = standings.to_vec();
+    sorted.sort_by_key(|s| s.index);
+    let mut bytes = Vec::with_capacity(sorted.len() * 10);
+    for s in sorted.iter() {
+        bytes.extend_from_slice(&s.index.to_le_bytes());
+        bytes.extend_from_slice(&s.net().to_le_bytes());
+    }
+    hash::hash(&bytes).to_bytes()
+}
+
+/// Top-ranked option, `None` when there are no options.
+pub fn winner(standings: &[Standing]) -> Option<Standing> {
+    let mut ranked = standings.to_vec();
+    rank(&mut ranked);
+    ranked.first().copied()
+}
@@ -2,14 +2,34 @@ use anchor_lang::prelude::*;
 
 pub const MAX_TITLE: usize = 64;
 pub const MAX_DESC: usize = 256;
+// text limits once expand_poll_text has grown the account
+pub const MAX_TITLE_EXTENDED: usize = 256;
+pub const MAX_DESC_EXTENDED: usize = 1024;
 pub const MAX_LABEL: usize = 64;
+// MAX_LABEL counts chars; a char takes at most 4 bytes in UTF-8
+pub const MAX_LABEL_BYTES: usize = MAX_LABEL * 4;
+pub const MAX_OPTION_DESC: usize = 256;
+pub const MAX_URI: usize = 200;
+pub const MAX_TAGS: usize = 3;
+pub const MAX_TAG_LEN: usize = 32;
+pub const MAX_NOTE: usize = 128;
+pub const MAX_ADMINS: usize = 8;
+pub const MAX_BATCH: usize = 10;
+// layout version written into Poll, OptionNode and Voter and checked by
+// every instruction that loads them. Nothing was deployed while the fields
+// were still being added, so 1 is the layout as defined below; bump it with
+// any later change to these three structs.
+pub const ACCOUNT_VERSION: u8 = 1;
 
 
 #[account]
+#[derive(InitSpace)]
 pub struct Poll {
     pub authority: Pubkey,
     pub poll_id: u64,
+    #[max_len(MAX_TITLE)]
     pub title: String,
+    #[max_len(MAX_DESC)]
     pub description: String,
     pub plus_credits: u8,
     pub minus_credits: u8,
@@ -17,55 +37,339 @@ pub struct Poll {
     pub end_ts: i64,
     pub options_count: u16,
     pub ended: bool,
+    #[max_len(MAX_TAGS, MAX_TAG_LEN)]
+    pub tags: Vec<String>,
+    pub finalize_grace: i64,
+    pub options_sealed: bool,
+    // streaming argmax maintained by cast_vote, see ranking::track_leader
+    pub leader_index: u16,
+    pub leader_net: i64,
+    pub leader_plus: u32,
+    // size of the AllowedVoter allowlist, see add_allowed_voter and
+    // check_allowlist; 0 for open and merkle-gated polls
+    pub allowed_count: u32,
+    // distinct voters, bumped when cast_vote creates a Voter
+    pub total_voters: u32,
+    // shortest trimmed option label (in chars) accepted by add_option, 1..=MAX_LABEL
+    pub min_label_len: u16,
+    // set by finalize_poll
+    pub winner_index: Option<u16>,
+    pub winner_score: i64,
+    // plus votes needed per minus vote, P >= neg_ratio*(M+1); D21 uses 2
+    pub neg_ratio: u8,
+    // distinct voters (total_voters) finalize_poll needs for a valid result
+    pub quorum: u32,
+    pub quorum_met: bool,
+    // authority at creation; the poll PDA seeds keep using it after
+    // transfer_poll_authority hands `authority` to someone else
+    pub creator: Pubkey,
+    // options the ballot needs before any vote is accepted
+    pub min_options: u16,
+    pub mode: VotingMode,
+    // commit-reveal phases, both 0 for a public poll: commit_vote runs in
+    // [start_ts, commit_end_ts], reveal_vote in (commit_end_ts, reveal_end_ts]
+    pub commit_end_ts: i64,
+    pub reveal_end_ts: i64,
+    // merkle root of eligible voters, see merkle::verify; None keeps the poll open
+    pub allowlist_root: Option<[u8; 32]>,
+    // set once by begin_poll; votes need it, option edits need it unset
+    pub active: bool,
+    pub version: u8,
+    // votes still count up to end_ts + grace_secs, see vote_deadline
+    pub grace_secs: i64,
+    // set by pause_poll; voting halts but end_ts keeps running
+    pub paused: bool,
+    // minimum seconds between two votes of one voter, 0 disables it
+    pub vote_cooldown_secs: i64,
+    // when initialize_poll ran, for auditing
+    pub created_ts: i64,
+    // receipts one voter may hold, 0 for no cap
+    pub max_votes_per_voter: u16,
+    // sums of plus_votes/minus_votes over every option
+    pub total_plus: u64,
+    pub total_minus: u64,
 }
 impl Poll {
-    pub const SPACE: usize = 8 + 32 + 8 + (4 + MAX_TITLE) + (4 + MAX_DESC)
-        + 1 + 1 + 8 + 8 + 2 + 1;
+    pub const EXTENDED_SPACE: usize =
+        8 + Self::INIT_SPACE + (MAX_TITLE_EXTENDED - MAX_TITLE) + (MAX_DESC_EXTENDED - MAX_DESC);
+
+    /// Last second a vote is accepted. `grace_secs` only stretches the end of
+    /// the window so a vote landing right at the cutoff is not lost to clock
+    /// jitter; `start_ts` stays strict.
+    pub fn vote_deadline(&self) -> i64 {
+        self.end_ts.saturating_add(self.grace_secs)
+    }
+
+    /// Settlement window `(deadline, deadline + finalize_grace]` in which
+    /// results may be finalized/tallied but no votes are accepted.
+    /// A `finalize_grace` of 0 leaves the window open indefinitely.
+    pub fn finalize_window_open(&self, now: i64) -> bool {
+        let deadline = self.vote_deadline();
+        now > deadline
+            && (self.finalize_grace == 0 || now <= deadline.saturating_add(self.finalize_grace))
+    }
+
+    pub fn is_commit_reveal(&self) -> bool {
+        self.commit_end_ts != 0
+    }
+
+    /// Checks `voter` against the poll's electorate; always passes on open
+    /// polls. The electorate is either the merkle `allowlist_root`, proven
+    /// with `proof`, or the `allowed_count` AllowedVoter entries, proven by
+    /// passing the voter's entry. add_allowed_voter refuses polls with a
+    /// root, so a poll never has both.
+    pub fn check_allowlist(
+        &self,
+        poll: &Pubkey,
+        voter: &Pubkey,
+        proof: &[[u8; 32]],
+        allowed_voter: Option<&AllowedVoter>,
+    ) -> std::result::Result<(), crate::errors::D21Error> {
+        let allowed = if let Some(root) = &self.allowlist_root {
+            crate::merkle::verify(proof, root, crate::merkle::leaf(voter))
+        } else if self.allowed_count > 0 {
+            allowed_voter.is_some_and(|entry| entry.poll == *poll && entry.voter == *voter)
+        } else {
+            true
+        };
+        if allowed { Ok(()) } else { Err(crate::errors::D21Error::NotOnAllowlist) }
+    }
+
+    /// Gate of close_voter/close_receipt on the poll behind `info`: no vote
+    /// can be cast any more, either because voting is over or because
+    /// close_poll already removed the account (its ClosedPoll keeps the
+    /// address from being reused).
+    pub fn require_cleanup_allowed(info: &AccountInfo) -> Result<()> {
+        if info.data_is_empty() {
+            return Ok(());
+        }
+        require_keys_eq!(*info.owner, crate::ID, crate::errors::D21Error::PollMismatch);
+        let poll = Poll::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        poll.assert_version(ACCOUNT_VERSION)?;
+        let now = Clock::get()?.unix_timestamp;
+        require!(poll.ended || now > poll.vote_deadline(), crate::errors::D21Error::VotingNotOver);
+        Ok(())
+    }
+}
+
+impl Poll {
+    /// `poll.authority` holds every role implicitly; anyone else needs an
+    /// Admins entry with the role itself or FullAdmin.
+    pub fn require_role(&self, poll: &Pubkey, admins: Option<&Admins>, who: &Pubkey, role: Role) -> Result<()> {
+        if *who == self.authority {
+            return Ok(());
+        }
+        if let Some(admins) = admins {
+            require_keys_eq!(admins.poll, *poll, crate::errors::D21Error::PollMismatch);
+            if matches!(admins.role_of(who), Some(r) if r == role || r == Role::FullAdmin) {
+                return Ok(());
+            }
+        }
+        err!(crate::errors::D21Error::InsufficientRole)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum Role {
+    FullAdmin,
+    OptionManager,
+    Closer,
+}
+
+/// How `cast_vote` counts a ballot.
+/// - `D21`: plus/minus credits with the negative vote ratio gate.
+/// - `Approval`: plus votes only, on as many options as the voter likes.
+/// - `Plurality`: a single plus vote per voter across the whole poll.
+/// - `Quadratic`: repeat plus votes on an option, the n-th costing n² of
+///   the voter's `plus_credits` budget.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum VotingMode {
+    D21,
+    Approval,
+    Plurality,
+    Quadratic,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct AdminEntry {
+    pub key: Pubkey,
+    pub role: Role,
+}
+
+/// Extra admins of a poll besides `poll.authority`, one role each.
+#[account]
+#[derive(InitSpace)]
+pub struct Admins {
+    pub poll: Pubkey,
+    #[max_len(MAX_ADMINS)]
+    pub entries: Vec<AdminEntry>,
+}
+impl Admins {
+    pub fn role_of(&self, key: &Pubkey) -> Option<Role> {
+        self.entries.iter().find(|e| e.key == *key).map(|e| e.role)
+    }
 }
 
 #[account]
+#[derive(InitSpace)]
 pub struct OptionNode {
     pub poll: Pubkey,
     pub index: u16,
+    #[max_len(MAX_LABEL_BYTES)]
     pub label: String,
     pub plus_votes: u32,
     pub minus_votes: u32,
+    pub abstain_votes: u32,
+    // optional ballot text and artwork, empty when unset
+    #[max_len(MAX_OPTION_DESC)]
+    pub description: String,
+    #[max_len(MAX_URI)]
+    pub image_uri: String,
+    pub version: u8,
 }
 impl OptionNode {
-    pub const SPACE: usize = 8 + 32 + 2 + (4 + MAX_LABEL) + 4 + 4;
+    pub fn net_score(&self) -> i64 {
+        self.plus_votes as i64 - self.minus_votes as i64
+    }
 }
 
 #[account]
+#[derive(InitSpace)]
 pub struct LabelGuard {
     pub poll: Pubkey,
     pub label_hash: [u8; 32],
 }
-impl LabelGuard {
-    // 8 discriminator + 32 + 32
-    pub const SPACE: usize = 8 + 32 + 32;
+
+/// Left behind by `close_poll`, `["closed", poll]`. Its presence keeps the
+/// poll id from being initialized again, so a new poll cannot inherit the
+/// closed one's Voter and Receipt accounts.
+#[account]
+#[derive(InitSpace)]
+pub struct ClosedPoll {
+    pub poll: Pubkey,
+}
+
+/// Program-wide poll id counter for `initialize_poll_auto`, `["registry"]`.
+#[account]
+#[derive(InitSpace)]
+pub struct Registry {
+    pub next_poll_id: u64,
+}
+
+/// Per-tag counter so clients can discover polls by category.
+#[account]
+#[derive(InitSpace)]
+pub struct TagIndex {
+    #[max_len(MAX_TAG_LEN)]
+    pub tag: String,
+    pub poll_count: u64,
+}
+
+/// Allowlist entry, `["allowed", poll, voter]`.
+#[account]
+#[derive(InitSpace)]
+pub struct AllowedVoter {
+    pub poll: Pubkey,
+    pub voter: Pubkey,
 }
 
 #[account]
+#[derive(InitSpace, Default)]
 pub struct Voter {
     pub poll: Pubkey,
     pub voter: Pubkey,
     pub used_plus: u8,
     pub used_minus: u8,
+    // quadratic budget, starts at poll.plus_credits
+    pub credits_remaining: u32,
+    pub version: u8,
+    // time of the latest vote, checked against poll.vote_cooldown_secs
+    pub last_vote_ts: i64,
+    // receipts this voter holds, capped by poll.max_votes_per_voter
+    pub options_voted: u16,
+}
+
+/// Lets `delegate` vote with the delegator's credits, `["delegation", poll, delegator]`.
+#[account]
+#[derive(InitSpace)]
+pub struct Delegation {
+    pub poll: Pubkey,
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+}
+
+/// Hidden vote of a commit-reveal poll, `["commitment", poll, voter]`.
+#[account]
+#[derive(InitSpace)]
+pub struct Commitment {
+    pub poll: Pubkey,
+    pub voter: Pubkey,
+    pub commitment: [u8; 32],
 }
-impl Voter {
-    pub const SPACE: usize = 8 + 32 + 32 + 1 + 1;
+impl Commitment {
+    /// sha256(index as u16 LE, sentiment, nonce)
+    pub fn hash(index: u16, sentiment: i8, nonce: &[u8; 32]) -> [u8; 32] {
+        anchor_lang::solana_program::hash::hashv(&[
+            &index.to_le_bytes(),
+            &sentiment.to_le_bytes(),
+            nonce,
+        ])
+        .to_bytes()
+    }
 }
 
 #[account]
+#[derive(InitSpace)]
 pub struct Receipt {
     pub poll: Pubkey,
     pub voter: Pubkey,
     pub option_index: u16,
-    pub sentiment: i8, // 1 or -1
+    pub sentiment: i8, // 1, -1, or 0 for an abstention
+    #[max_len(MAX_NOTE)]
+    pub note: Option<String>,
+    // votes behind this receipt, above 1 only in quadratic mode
+    pub weight: u16,
 }
-impl Receipt {
-    // 8 discriminator + 32 + 32 + 2 + 1
-    pub const SPACE: usize = 8 + 32 + 32 + 2 + 1;
+
+/// Totals of a finalized poll, `["snapshot", poll]`. Written once by
+/// `finalize_poll` so clients can read the result without the options.
+#[account]
+#[derive(InitSpace)]
+pub struct ResultsSnapshot {
+    pub poll: Pubkey,
+    pub total_plus: u64,
+    pub total_minus: u64,
+    pub winner_index: u16,
+    pub winner_score: i64,
+    pub option_count: u16,
+    // when finalize_poll ran
+    pub finalized_ts: i64,
+}
+
+/// Immutable record of a finished poll's result, `["attestation", poll]`.
+#[account]
+#[derive(InitSpace)]
+pub struct ResultAttestation {
+    pub poll: Pubkey,
+    pub poll_id: u64,
+    pub winner_index: u16,
+    pub winner_net: i64,
+    pub scores_hash: [u8; 32],
+    pub finalized_ts: i64,
+    // sha256(program id, poll, scores_hash, winner_index, finalized_ts)
+    pub seal: [u8; 32],
+}
+impl ResultAttestation {
+    pub fn compute_seal(&self) -> [u8; 32] {
+        anchor_lang::solana_program::hash::hashv(&[
+            crate::ID.as_ref(),
+            self.poll.as_ref(),
+            &self.scores_hash,
+            &self.winner_index.to_le_bytes(),
+            &self.finalized_ts.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
 }
 
 #[event]
@@ -76,4 +380,56 @@ pub struct VoteCast {
     pub sentiment: i8,
     pub used_plus: u8,
     pub used_minus: u8,
+    pub note: Option<String>,
+    // the option's counters after this vote
+    pub option_plus_votes: u32,
+    pub option_minus_votes: u32,
+}
+
+#[event]
+pub struct PollCreated {
+    pub poll: Pubkey,
+    pub authority: Pubkey,
+    pub poll_id: u64,
+    pub tags: Vec<String>,
+}
+
+/// Voting parameters of a new poll, emitted after PollCreated.
+#[event]
+pub struct PollInitialized {
+    pub poll: Pubkey,
+    pub authority: Pubkey,
+    pub poll_id: u64,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub plus_credits: u8,
+    pub minus_credits: u8,
+    pub created_ts: i64,
+    pub tags: Vec<String>,
+}
+
+/// Accounts that carry a layout `version`, so handlers can refuse layouts
+/// they don't understand.
+pub trait Versioned {
+    fn version(&self) -> u8;
+
+    fn assert_version(&self, expected: u8) -> Result<()> {
+        require!(self.version() == expected, crate::errors::D21Error::UnsupportedVersion);
+        Ok(())
+    }
+}
+impl Versioned for Poll {
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+impl Versioned for OptionNode {
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+impl Versioned for Voter {
+    fn version(&self) -> u8 {
+        self.version
+    }
 }
@@ -0,0 +1,45 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode,
+  pollCfg, createPoll, beginPoll, addOption, castVote, optionPda, voterPda, receiptPda,
+} from "./helpers";
+
+describe("abstain", () => {
+  const authority = Keypair.generate();
+  const voter = Keypair.generate();
+  let poll: PublicKey;
+
+  before(async () => {
+    for (const k of [authority, voter]) await airdrop(k.publicKey);
+
+    const start = nowSec() + 3;
+    poll = await createPoll(authority, pollCfg(3501, start));
+    await addOption(authority, poll, 0, "Alpha");
+    await addOption(authority, poll, 1, "Beta");
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+  });
+
+  it("records an abstention without using credits", async () => {
+    await castVote(voter, poll, 0, 0);
+
+    const v = await program.account.voter.fetch(voterPda(poll, voter.publicKey));
+    expect(v.usedPlus).to.eq(0);
+    expect(v.usedMinus).to.eq(0);
+    const o = await program.account.optionNode.fetch(optionPda(poll, 0));
+    expect(o.abstainVotes).to.eq(1);
+    expect(o.plusVotes).to.eq(0);
+    expect(o.minusVotes).to.eq(0);
+    const r = await program.account.receipt.fetch(receiptPda(poll, 0, voter.publicKey));
+    expect(r.sentiment).to.eq(0);
+  });
+
+  it("rejects abstaining twice on the same option", async () => {
+    await expectAnchorErrCode(castVote(voter, poll, 0, 0), "AlreadyVotedThisOption");
+  });
+
+  it("still rejects other sentiments", async () => {
+    await expectAnchorErrCode(castVote(voter, poll, 1, 2), "InvalidSentiment");
+  });
+});
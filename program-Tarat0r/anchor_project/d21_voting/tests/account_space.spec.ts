@@ -0,0 +1,41 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, waitUntilChainTime,
+  pollCfg, createPoll, beginPoll, addOption, castVote, optionPda, voterPda, receiptPda,
+} from "./helpers";
+
+// the old hand-computed SPACE values plus fields added since; catches a
+// max_len that drifts from its constant
+const SIZES = { poll: 688, optionNode: 779, voter: 89, receipt: 210 };
+
+describe("account space", () => {
+  const authority = Keypair.generate();
+  const voter = Keypair.generate();
+  let poll: PublicKey;
+
+  const dataLen = async (key: PublicKey) =>
+    (await program.provider.connection.getAccountInfo(key, "confirmed"))!.data.length;
+
+  before(async () => {
+    for (const k of [authority, voter]) await airdrop(k.publicKey);
+
+    const start = nowSec() + 3;
+    poll = await createPoll(authority, pollCfg(5401, start));
+    await addOption(authority, poll, 0, "Alpha");
+    await addOption(authority, poll, 1, "Beta");
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+    await castVote(voter, poll, 0, 1);
+  });
+
+  it("keeps the poll and option sizes", async () => {
+    expect(await dataLen(poll)).to.eq(SIZES.poll);
+    expect(await dataLen(optionPda(poll, 0))).to.eq(SIZES.optionNode);
+  });
+
+  it("keeps the voter and receipt sizes", async () => {
+    expect(await dataLen(voterPda(poll, voter.publicKey))).to.eq(SIZES.voter);
+    expect(await dataLen(receiptPda(poll, 0, voter.publicKey))).to.eq(SIZES.receipt);
+  });
+});
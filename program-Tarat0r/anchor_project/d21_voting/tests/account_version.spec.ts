@@ -0,0 +1,34 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, waitUntilChainTime,
+  pollCfg, createPoll, beginPoll, addOption, castVote, optionPda, voterPda,
+} from "./helpers";
+
+describe("account version", () => {
+  const authority = Keypair.generate();
+  const voter = Keypair.generate();
+  let poll: PublicKey;
+
+  before(async () => {
+    for (const k of [authority, voter]) await airdrop(k.publicKey);
+
+    const start = nowSec() + 3;
+    poll = await createPoll(authority, pollCfg(5301, start));
+    await addOption(authority, poll, 0, "Alpha");
+    await addOption(authority, poll, 1, "Beta");
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+  });
+
+  it("writes version 1 into new polls and options", async () => {
+    expect((await program.account.poll.fetch(poll)).version).to.eq(1);
+    expect((await program.account.optionNode.fetch(optionPda(poll, 0))).version).to.eq(1);
+  });
+
+  it("accepts votes on v1 accounts, including an existing v1 voter", async () => {
+    await castVote(voter, poll, 0, 1);
+    expect((await program.account.voter.fetch(voterPda(poll, voter.publicKey))).version).to.eq(1);
+    await castVote(voter, poll, 1, 1);
+  });
+});
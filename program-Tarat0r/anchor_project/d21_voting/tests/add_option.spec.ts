@@ -46,7 +46,7 @@ async function expectIxFail(p: Promise<any>, re: RegExp) {
   }
 }
 function labelSeed(label: string): Buffer {
-  const canonical = label.trim().toLowerCase();
+  const canonical = label.trim().split(/\s+/).join(" ").toLowerCase();
   return createHash("sha256").update(Buffer.from(canonical, "utf8")).digest(); // 32 bytes
 }
 
@@ -69,6 +69,19 @@ describe("add_option", () => {
       minusCredits: 1,
       startTs: new BN(nowSec() + 600),
       endTs: new BN(nowSec() + 3600),
+      finalizeGrace: new BN(0),
+      minLabelLen: 1,
+      negRatio: 2,
+      quorum: 0,
+      minOptions: 2,
+      mode: { d21: {} },
+      commitEndTs: new BN(0),
+      revealEndTs: new BN(0),
+      allowlistRoot: null,
+      graceSecs: new BN(0),
+      voteCooldownSecs: new BN(0),
+      maxVotesPerVoter: 0,
+      tags: [],
     };
 
     pollPda = PublicKey.findProgramAddressSync(
@@ -112,8 +125,8 @@ describe("add_option", () => {
     });
 
     await program.methods
-      // add_option now expects (index, label, label_seed: [u8; 32])
-      .addOption(index, label, [...seed])
+      // add_option expects (index, label, label_seed: [u8; 32], description, image_uri)
+      .addOption(index, label, [...seed], "", "")
       .accountsPartial({
         authority: authority.publicKey,
         poll: pollPda,
@@ -157,7 +170,7 @@ describe("add_option", () => {
 
     await expectIxFail(
       program.methods
-        .addOption(index, bad, [...seed])
+        .addOption(index, bad, [...seed], "", "")
         .accountsPartial({
           authority: authority.publicKey,
           poll: pollPda,
@@ -188,7 +201,7 @@ describe("add_option", () => {
 
     await expectIxFail(
       program.methods
-        .addOption(index, label, [...seed])
+        .addOption(index, label, [...seed], "", "")
         .accountsPartial({
           authority: authority.publicKey,
           poll: pollPda,
@@ -203,7 +216,7 @@ describe("add_option", () => {
   });
 
   it("rejects duplicate index for the same poll", async () => {
-    const index = 3;
+    const index = 1;
     const label = "Dup";
 
     const optionPda = PublicKey.findProgramAddressSync(
@@ -219,7 +232,7 @@ describe("add_option", () => {
 
     // first add
     await program.methods
-      .addOption(index, label, [...seed])
+      .addOption(index, label, [...seed], "", "")
       .accountsPartial({
         authority: authority.publicKey,
         poll: pollPda,
@@ -233,7 +246,7 @@ describe("add_option", () => {
     // second add should fail on account already exists (option_node or label_guard)
     await expectIxFail(
       program.methods
-        .addOption(index, label, [...seed])
+        .addOption(index, label, [...seed], "", "")
         .accountsPartial({
           authority: authority.publicKey,
           poll: pollPda,
@@ -264,7 +277,7 @@ describe("add_option", () => {
 
     await expectIxFail(
       program.methods
-        .addOption(index, label, [...seed])
+        .addOption(index, label, [...seed], "", "")
         .accountsPartial({
           authority: rando.publicKey,      // not the poll.authority
           poll: pollPda,
@@ -274,7 +287,7 @@ describe("add_option", () => {
         })
         .signers([rando])
         .rpc(),
-      /Unauthorized/i
+      /InsufficientRole/i
     );
   });
 
@@ -289,6 +302,19 @@ describe("add_option", () => {
       minusCredits: 0,
       startTs: new BN(nowSec() + 1),
       endTs: new BN(nowSec() + 120),
+      finalizeGrace: new BN(0),
+      minLabelLen: 1,
+      negRatio: 2,
+      quorum: 0,
+      minOptions: 2,
+      mode: { d21: {} },
+      commitEndTs: new BN(0),
+      revealEndTs: new BN(0),
+      allowlistRoot: null,
+      graceSecs: new BN(0),
+      voteCooldownSecs: new BN(0),
+      maxVotesPerVoter: 0,
+      tags: [],
     };
 
     const soonPollPda = PublicKey.findProgramAddressSync(
@@ -324,7 +350,7 @@ describe("add_option", () => {
 
     await expectIxFail(
       program.methods
-        .addOption(idx, "Late", [...seed])
+        .addOption(idx, "Late", [...seed], "", "")
         .accountsPartial({
           authority: authority.publicKey,
           poll: soonPollPda,
@@ -351,6 +377,19 @@ describe("add_option", () => {
       minusCredits: 0,
       startTs: new BN(nowSec() + 600),
       endTs: new BN(nowSec() + 3600),
+      finalizeGrace: new BN(0),
+      minLabelLen: 1,
+      negRatio: 2,
+      quorum: 0,
+      minOptions: 2,
+      mode: { d21: {} },
+      commitEndTs: new BN(0),
+      revealEndTs: new BN(0),
+      allowlistRoot: null,
+      graceSecs: new BN(0),
+      voteCooldownSecs: new BN(0),
+      maxVotesPerVoter: 0,
+      tags: [],
     };
 
     const pollPda2 = PublicKey.findProgramAddressSync(
@@ -383,7 +422,7 @@ describe("add_option", () => {
     )[0];
 
     await program.methods
-      .addOption(idx0, labelA, [...seedA])
+      .addOption(idx0, labelA, [...seedA], "", "")
       .accountsPartial({
         authority: authority2.publicKey,
         poll: pollPda2,
@@ -412,7 +451,7 @@ describe("add_option", () => {
     )[0];
 
     await program.methods
-      .addOption(idx1, labelB, [...seedB])
+      .addOption(idx1, labelB, [...seedB], "", "")
       .accountsPartial({
         authority: authority2.publicKey,
         poll: pollPda2,
@@ -439,6 +478,19 @@ describe("add_option", () => {
       minusCredits: 0,
       startTs: new BN(nowSec() + 600),
       endTs: new BN(nowSec() + 3600),
+      finalizeGrace: new BN(0),
+      minLabelLen: 1,
+      negRatio: 2,
+      quorum: 0,
+      minOptions: 2,
+      mode: { d21: {} },
+      commitEndTs: new BN(0),
+      revealEndTs: new BN(0),
+      allowlistRoot: null,
+      graceSecs: new BN(0),
+      voteCooldownSecs: new BN(0),
+      maxVotesPerVoter: 0,
+      tags: [],
     };
 
     const pollPda3 = PublicKey.findProgramAddressSync(
@@ -471,7 +523,7 @@ describe("add_option", () => {
     )[0];
 
     await program.methods
-      .addOption(idx0, label1, [...seed1])
+      .addOption(idx0, label1, [...seed1], "", "")
       .accountsPartial({
         authority: authority3.publicKey,
         poll: pollPda3,
@@ -500,7 +552,7 @@ describe("add_option", () => {
 
   await expectIxFail(
     program.methods
-      .addOption(idx1, dup, [...seedDup])
+      .addOption(idx1, dup, [...seedDup], "", "")
       .accountsPartial({
         authority: authority.publicKey,
         poll: pollPda,
@@ -528,6 +580,19 @@ describe("add_option", () => {
       minusCredits: 0,
       startTs: new BN(nowSec() + 600),
       endTs: new BN(nowSec() + 3600),
+      finalizeGrace: new BN(0),
+      minLabelLen: 1,
+      negRatio: 2,
+      quorum: 0,
+      minOptions: 2,
+      mode: { d21: {} },
+      commitEndTs: new BN(0),
+      revealEndTs: new BN(0),
+      allowlistRoot: null,
+      graceSecs: new BN(0),
+      voteCooldownSecs: new BN(0),
+      maxVotesPerVoter: 0,
+      tags: [],
     };
     const pollA = PublicKey.findProgramAddressSync(
       [Buffer.from("poll"), authority4.publicKey.toBuffer(), u64LeBytes(cfgA.pollId)],
@@ -577,7 +642,7 @@ describe("add_option", () => {
     )[0];
 
     await program.methods
-      .addOption(0, label, [...seed])
+      .addOption(0, label, [...seed], "", "")
       .accountsPartial({
         authority: authority4.publicKey,
         poll: pollA,
@@ -599,7 +664,7 @@ describe("add_option", () => {
     )[0];
 
     await program.methods
-      .addOption(0, label, [...seed])
+      .addOption(0, label, [...seed], "", "")
       .accountsPartial({
         authority: authority4.publicKey,
         poll: pollB,
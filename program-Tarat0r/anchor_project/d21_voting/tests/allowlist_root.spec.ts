@@ -0,0 +1,76 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { keccak_256 } from "@noble/hashes/sha3";
+import {
+  program, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode,
+  pollCfg, createPoll, beginPoll, addOption, castVote, optionPda,
+} from "./helpers";
+
+// Sorted-pair keccak256 tree, mirrors merkle::verify in the program.
+const hashPair = (a: Buffer, b: Buffer) =>
+  Buffer.from(keccak_256(Buffer.compare(a, b) <= 0 ? Buffer.concat([a, b]) : Buffer.concat([b, a])));
+const leafOf = (pk: PublicKey) => Buffer.from(keccak_256(pk.toBuffer()));
+
+function buildTree(keys: PublicKey[]) {
+  const levels: Buffer[][] = [keys.map(leafOf)];
+  while (levels[levels.length - 1].length > 1) {
+    const prev = levels[levels.length - 1];
+    const next: Buffer[] = [];
+    for (let i = 0; i < prev.length; i += 2) {
+      next.push(i + 1 < prev.length ? hashPair(prev[i], prev[i + 1]) : prev[i]);
+    }
+    levels.push(next);
+  }
+  const proofFor = (pk: PublicKey) => {
+    let idx = keys.findIndex((k) => k.equals(pk));
+    const proof: number[][] = [];
+    for (const level of levels.slice(0, -1)) {
+      const sibling = idx ^ 1;
+      if (sibling < level.length) proof.push([...level[sibling]]);
+      idx >>= 1;
+    }
+    return proof;
+  };
+  return { root: [...levels[levels.length - 1][0]], proofFor };
+}
+
+describe("merkle allowlist", () => {
+  const authority = Keypair.generate();
+  const members = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+  const outsider = Keypair.generate();
+  const tree = buildTree(members.map((k) => k.publicKey));
+  let gated: PublicKey;
+  let open: PublicKey;
+
+  before(async () => {
+    for (const k of [authority, outsider, ...members]) await airdrop(k.publicKey);
+
+    const start = nowSec() + 3;
+    gated = await createPoll(authority, pollCfg(4701, start, { allowlistRoot: tree.root }));
+    open = await createPoll(authority, pollCfg(4702, start));
+    for (const poll of [gated, open]) {
+      await addOption(authority, poll, 0, "Alpha");
+      await addOption(authority, poll, 1, "Beta");
+    }
+    await waitUntilChainTime(start);
+    for (const p of [gated, open]) await beginPoll(authority, p);
+  });
+
+  it("accepts members with a valid proof", async () => {
+    for (const m of members) {
+      await castVote(m, gated, 0, 1, null, m, tree.proofFor(m.publicKey));
+    }
+    expect((await program.account.optionNode.fetch(optionPda(gated, 0))).plusVotes).to.eq(3);
+  });
+
+  it("rejects an invalid proof", async () => {
+    const borrowed = tree.proofFor(members[0].publicKey);
+    await expectAnchorErrCode(castVote(outsider, gated, 0, 1, null, outsider, borrowed), "NotOnAllowlist");
+    await expectAnchorErrCode(castVote(members[0], gated, 1, 1, null, members[0], []), "NotOnAllowlist");
+  });
+
+  it("keeps polls without a root open to everyone", async () => {
+    expect((await program.account.poll.fetch(open)).allowlistRoot).to.eq(null);
+    await castVote(outsider, open, 0, 1);
+  });
+});
@@ -0,0 +1,121 @@
+import { expect } from "chai";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import BN from "bn.js";
+import { createHash } from "crypto";
+import {
+  program, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode, u16LeBytes,
+  pollCfg, createPoll, beginPoll, addOption, castVote, optionPda, snapshotPda,
+} from "./helpers";
+
+describe("attest_result", () => {
+  const authority = Keypair.generate();
+  const voters = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+  let poll: PublicKey;
+  let attestation: PublicKey;
+  let end: number;
+
+  const options = () => [0, 1, 2].map((i) => ({ pubkey: optionPda(poll, i), isSigner: false, isWritable: false }));
+  const attest = () =>
+    program.methods
+      .attestResult()
+      .accountsPartial({
+        payer: authority.publicKey,
+        poll,
+        snapshot: snapshotPda(poll),
+        attestation,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(options())
+      .signers([authority])
+      .rpc({ commitment: "confirmed" });
+  const finalize = () =>
+    program.methods
+      .finalizePoll()
+      .accountsPartial({
+        payer: authority.publicKey,
+        poll,
+        snapshot: snapshotPda(poll),
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(options())
+      .signers([authority])
+      .rpc({ commitment: "confirmed" });
+
+  // mirrors ranking::scores_hash: (index u16 LE, net i64 LE) per option in index order
+  const scoresHash = (nets: number[]) => {
+    const parts = nets.map((net, i) => {
+      const b = Buffer.alloc(8);
+      b.writeBigInt64LE(BigInt(net));
+      return Buffer.concat([u16LeBytes(i), b]);
+    });
+    return createHash("sha256").update(Buffer.concat(parts)).digest();
+  };
+
+  before(async () => {
+    for (const k of [authority, ...voters]) await airdrop(k.publicKey);
+
+    const start = nowSec() + 3;
+    end = start + 6;
+    poll = await createPoll(authority, pollCfg(1901, start, { endTs: new BN(end) }));
+    attestation = PublicKey.findProgramAddressSync(
+      [Buffer.from("attestation"), poll.toBuffer()],
+      program.programId
+    )[0];
+    await addOption(authority, poll, 0, "Alpha");
+    await addOption(authority, poll, 1, "Beta");
+    await addOption(authority, poll, 2, "Gamma");
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+
+    // Alpha +2, Beta +3, Gamma +1 -1
+    await castVote(voters[0], poll, 0, 1);
+    await castVote(voters[0], poll, 1, 1);
+    await castVote(voters[1], poll, 1, 1);
+    await castVote(voters[1], poll, 0, 1);
+    await castVote(voters[2], poll, 1, 1);
+    await castVote(voters[2], poll, 2, 1);
+    await castVote(voters[1], poll, 2, -1);
+  });
+
+  it("rejects attesting while voting is still open", async () => {
+    await expectAnchorErrCode(attest(), "NotFinalized");
+  });
+
+  it("rejects attesting before finalize_poll", async () => {
+    await waitUntilChainTime(end + 1);
+    await expectAnchorErrCode(attest(), "NotFinalized");
+  });
+
+  it("records the final tally in the attestation", async () => {
+    await finalize();
+    await attest();
+
+    const acc = await program.account.resultAttestation.fetch(attestation);
+    expect(acc.poll.toBase58()).to.eq(poll.toBase58());
+    expect(acc.pollId.toNumber()).to.eq(1901);
+    expect(acc.winnerIndex).to.eq(1);
+    expect(acc.winnerNet.toNumber()).to.eq(3);
+    expect(Buffer.from(acc.scoresHash)).to.deep.eq(scoresHash([2, 3, 0]));
+    const snap = await program.account.resultsSnapshot.fetch(snapshotPda(poll));
+    expect(acc.finalizedTs.toNumber()).to.eq(snap.finalizedTs.toNumber());
+    expect(acc.finalizedTs.toNumber()).to.be.greaterThan(end);
+
+    const ts = Buffer.alloc(8);
+    ts.writeBigInt64LE(BigInt(acc.finalizedTs.toString()));
+    const seal = createHash("sha256")
+      .update(Buffer.concat([program.programId.toBuffer(), poll.toBuffer(), Buffer.from(acc.scoresHash), u16LeBytes(1), ts]))
+      .digest();
+    expect(Buffer.from(acc.seal)).to.deep.eq(seal);
+  });
+
+  it("cannot be attested twice", async () => {
+    let failed = false;
+    try {
+      await attest();
+    } catch (e: any) {
+      failed = true;
+      expect(String(e)).to.match(/(already in use|AccountInUse|account exists)/i);
+    }
+    expect(failed).to.eq(true);
+  });
+});
@@ -0,0 +1,64 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode, eventsOf,
+  pollCfg, createPoll, beginPoll, addOption, castVote, voterPda,
+} from "./helpers";
+
+describe("audit_credits", () => {
+  const authority = Keypair.generate();
+  const alice = Keypair.generate();
+  const bob = Keypair.generate();
+  let poll: PublicKey;
+  let otherPoll: PublicKey;
+
+  const audit = (target: PublicKey, voters: PublicKey[]) =>
+    program.methods
+      .auditCredits()
+      .accountsPartial({ poll: target })
+      .remainingAccounts(voters.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })))
+      .rpc({ commitment: "confirmed" });
+
+  before(async () => {
+    for (const k of [authority, alice, bob]) await airdrop(k.publicKey);
+
+    const start = nowSec() + 3;
+    poll = await createPoll(authority, pollCfg(1101, start));
+    otherPoll = await createPoll(authority, pollCfg(1102, start));
+    for (const p of [poll, otherPoll]) {
+      await addOption(authority, p, 0, "Alpha");
+      await addOption(authority, p, 1, "Beta");
+      await addOption(authority, p, 2, "Gamma");
+    }
+    await waitUntilChainTime(start);
+    for (const p of [poll, otherPoll]) await beginPoll(authority, p);
+
+    await castVote(alice, poll, 0, 1);
+    await castVote(alice, poll, 1, 1);
+    await castVote(alice, poll, 2, -1);
+    await castVote(bob, poll, 0, 1);
+    await castVote(bob, otherPoll, 0, 1);
+  });
+
+  it("sums used credits and reports them as consistent", async () => {
+    const sig = await audit(poll, [voterPda(poll, alice.publicKey), voterPda(poll, bob.publicKey)]);
+    const ev = (await eventsOf(sig)).find((e) => e.name === "creditAudit");
+    expect(ev, "CreditAudit not emitted").to.exist;
+    expect(ev!.data.voterCount.toNumber()).to.eq(2);
+    expect(ev!.data.totalPlus.toNumber()).to.eq(3);
+    expect(ev!.data.totalMinus.toNumber()).to.eq(1);
+    expect(ev!.data.consistent).to.eq(true);
+  });
+
+  it("rejects a voter of another poll", async () => {
+    await expectAnchorErrCode(
+      audit(poll, [voterPda(poll, alice.publicKey), voterPda(otherPoll, bob.publicKey)]),
+      "PollMismatch"
+    );
+  });
+
+  it("rejects the same voter passed twice", async () => {
+    const v = voterPda(poll, alice.publicKey);
+    await expectAnchorErrCode(audit(poll, [v, v]), "DuplicateVoterAccount");
+  });
+});
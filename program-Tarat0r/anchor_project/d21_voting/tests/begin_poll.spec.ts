@@ -0,0 +1,63 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode, eventsOf,
+  pollCfg, createPoll, addOption, castVote,
+} from "./helpers";
+
+describe("begin_poll", () => {
+  const authority = Keypair.generate();
+  const outsider = Keypair.generate();
+  const voter = Keypair.generate();
+  let poll: PublicKey;
+  let start: number;
+
+  const begin = (signer: Keypair) =>
+    program.methods
+      .beginPoll()
+      .accountsPartial({ authority: signer.publicKey, poll })
+      .signers([signer])
+      .rpc({ commitment: "confirmed" });
+
+  before(async () => {
+    for (const k of [authority, outsider, voter]) await airdrop(k.publicKey);
+
+    start = nowSec() + 4;
+    poll = await createPoll(authority, pollCfg(4901, start));
+    await addOption(authority, poll, 0, "Alpha");
+    await addOption(authority, poll, 1, "Beta");
+  });
+
+  it("rejects activation before start_ts", async () => {
+    await expectAnchorErrCode(begin(authority), "VotingNotStarted");
+  });
+
+  it("rejects votes until the poll is activated", async () => {
+    await waitUntilChainTime(start);
+    await expectAnchorErrCode(castVote(voter, poll, 0, 1), "PollNotActive");
+  });
+
+  it("rejects activation by someone other than the authority", async () => {
+    await expectAnchorErrCode(begin(outsider), "Unauthorized");
+  });
+
+  it("activates the poll and emits PollActivated", async () => {
+    const sig = await begin(authority);
+    expect((await program.account.poll.fetch(poll)).active).to.eq(true);
+    const ev = (await eventsOf(sig)).find((e) => e.name === "pollActivated");
+    expect(ev, "PollActivated emitted").to.not.be.undefined;
+    expect(ev!.data.poll.toBase58()).to.eq(poll.toBase58());
+  });
+
+  it("accepts votes once active", async () => {
+    await castVote(voter, poll, 0, 1);
+  });
+
+  it("rejects adding options once active", async () => {
+    await expectAnchorErrCode(addOption(authority, poll, 2, "Gamma"), "VotingStarted");
+  });
+
+  it("rejects a second activation", async () => {
+    await expectAnchorErrCode(begin(authority), "PollAlreadyActive");
+  });
+});
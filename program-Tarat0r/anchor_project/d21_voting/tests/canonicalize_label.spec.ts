@@ -0,0 +1,53 @@
+import { expect } from "chai";
+import { Keypair } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, eventsOf, labelSeed, pollCfg, createPoll, addOption, optionPda,
+  expectAnchorErrCode,
+} from "./helpers";
+
+async function canonicalize(label: string) {
+  const sig = await program.methods.canonicalizeLabel(label).rpc({ commitment: "confirmed" });
+  const ev = (await eventsOf(sig)).find((e) => e.name === "labelCanonicalized");
+  expect(ev, "LabelCanonicalized emitted").to.not.be.undefined;
+  return ev!.data;
+}
+
+describe("canonicalize_label", () => {
+  const authority = Keypair.generate();
+
+  before(async () => {
+    await airdrop(authority.publicKey);
+  });
+
+  it("trims, collapses inner whitespace and lowercases", async () => {
+    const ev = await canonicalize("  Hello \t  World  ");
+    expect(ev.canonical).to.eq("hello world");
+    expect(Buffer.from(ev.labelSeed).equals(labelSeed("hello world"))).to.eq(true);
+  });
+
+  it("returns the seed add_option expects", async () => {
+    const poll = await createPoll(authority, pollCfg(601, nowSec() + 60));
+    const label = "  Green   Party ";
+    const ev = await canonicalize(label);
+    expect(Buffer.from(ev.labelSeed).equals(labelSeed(label))).to.eq(true);
+
+    // add_option accepts the label together with the program-computed seed
+    await addOption(authority, poll, 0, label);
+    const opt = await program.account.optionNode.fetch(optionPda(poll, 0));
+    expect(opt.label).to.eq(label.trim());
+  });
+
+  it("normalizes composed and decomposed accents to the same form", async () => {
+    const composed = await canonicalize("Caf\u00e9");
+    const decomposed = await canonicalize("Cafe\u0301");
+    expect(decomposed.canonical).to.eq(composed.canonical);
+    expect(Buffer.from(decomposed.labelSeed).equals(Buffer.from(composed.labelSeed))).to.eq(true);
+    expect(labelSeed("Cafe\u0301").equals(labelSeed("Caf\u00e9"))).to.eq(true);
+  });
+
+  it("rejects a decomposed duplicate of a composed label", async () => {
+    const poll = await createPoll(authority, pollCfg(4201, nowSec() + 60));
+    await addOption(authority, poll, 0, "Caf\u00e9");
+    await expectAnchorErrCode(addOption(authority, poll, 1, "CAFE\u0301"), "LabelAlreadyUsed");
+  });
+});
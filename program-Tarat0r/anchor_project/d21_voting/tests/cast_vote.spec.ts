@@ -48,7 +48,7 @@ async function expectAnchorErrCode(p: Promise<any>, code: string) {
 
 // label uniqueness helpers
 function labelSeed(label: string): Buffer {
-  const canonical = label.trim().toLowerCase();
+  const canonical = label.trim().split(/\s+/).join(" ").toLowerCase();
   return createHash("sha256").update(Buffer.from(canonical, "utf8")).digest(); // 32 bytes
 }
 function labelGuardPda(poll: PublicKey, seed: Buffer): PublicKey {
@@ -88,6 +88,19 @@ describe("cast_vote", () => {
       minusCredits: 1,     // single -1 allowed in this poll (subject to ratio rules)
       startTs: new BN(start),
       endTs: new BN(start + 3600),
+      finalizeGrace: new BN(0),
+      minLabelLen: 1,
+      negRatio: 2,
+      quorum: 0,
+      minOptions: 2,
+      mode: { d21: {} },
+      commitEndTs: new BN(0),
+      revealEndTs: new BN(0),
+      allowlistRoot: null,
+      graceSecs: new BN(0),
+      voteCooldownSecs: new BN(0),
+      maxVotesPerVoter: 0,
+      tags: [],
     };
 
     pollPda = PublicKey.findProgramAddressSync(
@@ -116,7 +129,7 @@ describe("cast_vote", () => {
       const seed = labelSeed(label);
       const guard = labelGuardPda(pollPda, seed);
       await program.methods
-        .addOption(0, label, [...seed])
+        .addOption(0, label, [...seed], "", "")
         .accountsPartial({
           authority: authority.publicKey,
           poll: pollPda,
@@ -137,7 +150,7 @@ describe("cast_vote", () => {
       const seed = labelSeed(label);
       const guard = labelGuardPda(pollPda, seed);
       await program.methods
-        .addOption(1, label, [...seed])
+        .addOption(1, label, [...seed], "", "")
         .accountsPartial({
           authority: authority.publicKey,
           poll: pollPda,
@@ -151,6 +164,7 @@ describe("cast_vote", () => {
 
     // wait until poll is open
     await waitUntilChainTime(start);
+    await program.methods.beginPoll().accountsPartial({ authority: authority.publicKey, poll: pollPda }).signers([authority]).rpc();
   });
 
   it("casts +1, initializes voter, updates counters, emits event", async () => {
@@ -171,9 +185,10 @@ describe("cast_vote", () => {
     });
 
     await program.methods
-      .castVote(index, 1)
+      .castVote(index, 1, null, [])
       .accountsPartial({
         voterAuthority: voter.publicKey,
+        payer: voter.publicKey,
         poll: pollPda,
         optionNode: option0Pda,
         voter: voterPda,
@@ -204,9 +219,10 @@ describe("cast_vote", () => {
 
     await expectAnchorErrCode(
       program.methods
-        .castVote(0, 1)
+        .castVote(0, 1, null, [])
         .accountsPartial({
           voterAuthority: voter.publicKey,
+          payer: voter.publicKey,
           poll: pollPda,
           optionNode: option0Pda,
           voter: voterPda,
@@ -228,9 +244,10 @@ describe("cast_vote", () => {
 
     await expectAnchorErrCode(
       program.methods
-        .castVote(1, 1) // different option, but we only had 1 + credit
+        .castVote(1, 1, null, []) // different option, but we only had 1 + credit
         .accountsPartial({
           voterAuthority: voter.publicKey,
+          payer: voter.publicKey,
           poll: pollPda,
           optionNode: option1Pda,
           voter: voterPda,
@@ -258,6 +275,19 @@ describe("cast_vote", () => {
       minusCredits: 1,
       startTs: new BN(start),
       endTs: new BN(start + 300),
+      finalizeGrace: new BN(0),
+      minLabelLen: 1,
+      negRatio: 2,
+      quorum: 0,
+      minOptions: 2,
+      mode: { d21: {} },
+      commitEndTs: new BN(0),
+      revealEndTs: new BN(0),
+      allowlistRoot: null,
+      graceSecs: new BN(0),
+      voteCooldownSecs: new BN(0),
+      maxVotesPerVoter: 0,
+      tags: [],
     };
 
     const poll = PublicKey.findProgramAddressSync(
@@ -291,7 +321,7 @@ describe("cast_vote", () => {
 
     {
       const seed = labelSeed("A");
-      await program.methods.addOption(0, "A", [...seed]).accountsPartial({
+      await program.methods.addOption(0, "A", [...seed], "", "").accountsPartial({
         authority: authority2.publicKey,
         poll,
         optionNode: opt0,
@@ -301,7 +331,7 @@ describe("cast_vote", () => {
     }
     {
       const seed = labelSeed("B");
-      await program.methods.addOption(1, "B", [...seed]).accountsPartial({
+      await program.methods.addOption(1, "B", [...seed], "", "").accountsPartial({
         authority: authority2.publicKey,
         poll,
         optionNode: opt1,
@@ -311,7 +341,7 @@ describe("cast_vote", () => {
     }
     {
       const seed = labelSeed("C");
-      await program.methods.addOption(2, "C", [...seed]).accountsPartial({
+      await program.methods.addOption(2, "C", [...seed], "", "").accountsPartial({
         authority: authority2.publicKey,
         poll,
         optionNode: opt2,
@@ -321,6 +351,7 @@ describe("cast_vote", () => {
     }
 
     await waitUntilChainTime(start);
+    await program.methods.beginPoll().accountsPartial({ authority: authority2.publicKey, poll }).signers([authority2]).rpc();
 
     const voterPda = PublicKey.findProgramAddressSync(
       [Buffer.from("voter"), poll.toBuffer(), voter2.publicKey.toBuffer()],
@@ -328,8 +359,9 @@ describe("cast_vote", () => {
     )[0];
 
     // +1 on two distinct options
-    await program.methods.castVote(0, 1).accountsPartial({
+    await program.methods.castVote(0, 1, null, []).accountsPartial({
       voterAuthority: voter2.publicKey,
+      payer: voter2.publicKey,
       poll,
       optionNode: opt0,
       voter: voterPda,
@@ -337,8 +369,9 @@ describe("cast_vote", () => {
       systemProgram: SystemProgram.programId,
     }).signers([voter2]).rpc();
 
-    await program.methods.castVote(1, 1).accountsPartial({
+    await program.methods.castVote(1, 1, null, []).accountsPartial({
       voterAuthority: voter2.publicKey,
+      payer: voter2.publicKey,
       poll,
       optionNode: opt1,
       voter: voterPda,
@@ -347,8 +380,9 @@ describe("cast_vote", () => {
     }).signers([voter2]).rpc();
 
     // Now −1 should pass
-    await program.methods.castVote(2, -1).accountsPartial({
+    await program.methods.castVote(2, -1, null, []).accountsPartial({
       voterAuthority: voter2.publicKey,
+      payer: voter2.publicKey,
       poll,
       optionNode: opt2,
       voter: voterPda,
@@ -370,6 +404,19 @@ describe("cast_vote", () => {
       minusCredits: 1,
       startTs: new BN(start),
       endTs: new BN(start + 120),
+      finalizeGrace: new BN(0),
+      minLabelLen: 1,
+      negRatio: 2,
+      quorum: 0,
+      minOptions: 1,
+      mode: { d21: {} },
+      commitEndTs: new BN(0),
+      revealEndTs: new BN(0),
+      allowlistRoot: null,
+      graceSecs: new BN(0),
+      voteCooldownSecs: new BN(0),
+      maxVotesPerVoter: 0,
+      tags: [],
     };
     const poll = PublicKey.findProgramAddressSync(
       [Buffer.from("poll"), auth.publicKey.toBuffer(), u64LeBytes(cfg.pollId)],
@@ -383,12 +430,13 @@ describe("cast_vote", () => {
     const opt0 = PublicKey.findProgramAddressSync([Buffer.from("option"), poll.toBuffer(), u16LeBytes(0)], program.programId)[0];
     {
       const seed = labelSeed("Only");
-      await program.methods.addOption(0, "Only", [...seed]).accountsPartial({
+      await program.methods.addOption(0, "Only", [...seed], "", "").accountsPartial({
         authority: auth.publicKey, poll, optionNode: opt0, labelGuard: labelGuardPda(poll, seed), systemProgram: SystemProgram.programId,
       }).signers([auth]).rpc();
     }
 
     await waitUntilChainTime(start);
+    await program.methods.beginPoll().accountsPartial({ authority: auth.publicKey, poll }).signers([auth]).rpc();
 
     const voterPda = PublicKey.findProgramAddressSync(
       [Buffer.from("voter"), poll.toBuffer(), voter.publicKey.toBuffer()],
@@ -396,8 +444,8 @@ describe("cast_vote", () => {
     )[0];
 
     await expectAnchorErrCode(
-      program.methods.castVote(0, -1).accountsPartial({
-        voterAuthority: voter.publicKey, poll, optionNode: opt0, voter: voterPda, receipt: receiptPda(poll, 0, voter.publicKey), systemProgram: SystemProgram.programId,
+      program.methods.castVote(0, -1, null, []).accountsPartial({
+        voterAuthority: voter.publicKey, payer: voter.publicKey, poll, optionNode: opt0, voter: voterPda, receipt: receiptPda(poll, 0, voter.publicKey), systemProgram: SystemProgram.programId,
       }).signers([voter]).rpc(),
       "InsufficientPositivesForNegative"
     );
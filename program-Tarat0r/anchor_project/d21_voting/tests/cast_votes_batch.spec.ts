@@ -0,0 +1,84 @@
+import { expect } from "chai";
+import { Keypair, PublicKey, SystemProgram, Transaction } from "@solana/web3.js";
+import {
+  program, provider, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode,
+  pollCfg, createPoll, beginPoll, addOption, optionPda, voterPda, receiptPda,
+} from "./helpers";
+
+describe("cast_votes_batch", () => {
+  const authority = Keypair.generate();
+  const voter = Keypair.generate();
+  const other = Keypair.generate();
+  const gifted = Keypair.generate();
+  let poll: PublicKey;
+
+  const batch = (who: Keypair, votes: [number, number][]) =>
+    program.methods
+      .castVotesBatch(votes.map(([index, sentiment]) => ({ index, sentiment })), [])
+      .accountsPartial({
+        payer: who.publicKey,
+        voterAuthority: who.publicKey,
+        poll,
+        voter: voterPda(poll, who.publicKey),
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(votes.flatMap(([index]) => [
+        { pubkey: optionPda(poll, index), isSigner: false, isWritable: true },
+        { pubkey: receiptPda(poll, index, who.publicKey), isSigner: false, isWritable: true },
+      ]))
+      .signers([who])
+      .rpc({ commitment: "confirmed" });
+
+  before(async () => {
+    for (const k of [authority, voter, other, gifted]) await airdrop(k.publicKey);
+
+    const start = nowSec() + 3;
+    poll = await createPoll(authority, pollCfg(3101, start));
+    for (let i = 0; i < 3; i++) await addOption(authority, poll, i, `Option ${i}`);
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+  });
+
+  it("applies the ratio gate in order within the batch", async () => {
+    await batch(voter, [[0, 1], [1, 1], [2, -1]]);
+
+    const v = await program.account.voter.fetch(voterPda(poll, voter.publicKey));
+    expect(v.usedPlus).to.eq(2);
+    expect(v.usedMinus).to.eq(1);
+    const r = await program.account.receipt.fetch(receiptPda(poll, 2, voter.publicKey));
+    expect(r.sentiment).to.eq(-1);
+    expect((await program.account.optionNode.fetch(optionPda(poll, 2))).minusVotes).to.eq(1);
+  });
+
+  it("rolls back the whole batch when one vote is invalid", async () => {
+    await expectAnchorErrCode(batch(other, [[0, 1], [1, -1]]), "InsufficientPositivesForNegative");
+
+    expect(await provider.connection.getAccountInfo(receiptPda(poll, 0, other.publicKey))).to.be.null;
+    expect(await provider.connection.getAccountInfo(voterPda(poll, other.publicKey))).to.be.null;
+    expect((await program.account.optionNode.fetch(optionPda(poll, 0))).plusVotes).to.eq(1);
+  });
+
+  it("rejects the same option twice in one batch", async () => {
+    await expectAnchorErrCode(batch(other, [[0, 1], [0, 1]]), "AlreadyVotedThisOption");
+  });
+
+  it("rejects a batch over the cap", async () => {
+    const votes: [number, number][] = Array.from({ length: 11 }, () => [0, 1]);
+    await expectAnchorErrCode(batch(other, votes), "InvalidBatchSize");
+  });
+
+  it("creates a receipt whose address was funded beforehand", async () => {
+    const receipt = receiptPda(poll, 1, gifted.publicKey);
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        SystemProgram.transfer({ fromPubkey: authority.publicKey, toPubkey: receipt, lamports: 1_000 })
+      ),
+      [authority]
+    );
+
+    await batch(gifted, [[1, 1]]);
+    const r = await program.account.receipt.fetch(receipt);
+    expect(r.voter.toBase58()).to.eq(gifted.publicKey.toBase58());
+    expect(r.sentiment).to.eq(1);
+  });
+});
@@ -0,0 +1,78 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode,
+  pollCfg, createPoll, beginPoll, addOption, castVote, optionPda, voterPda, receiptPda,
+} from "./helpers";
+
+describe("change_vote", () => {
+  const authority = Keypair.generate();
+  const voter = Keypair.generate();
+  const gated = Keypair.generate();
+  let poll: PublicKey;
+
+  const changeVote = (who: Keypair, index: number, sentiment: number) =>
+    program.methods
+      .changeVote(index, sentiment)
+      .accountsPartial({
+        voterAuthority: who.publicKey,
+        poll,
+        optionNode: optionPda(poll, index),
+        voter: voterPda(poll, who.publicKey),
+        receipt: receiptPda(poll, index, who.publicKey),
+      })
+      .signers([who])
+      .rpc({ commitment: "confirmed" });
+
+  before(async () => {
+    for (const k of [authority, voter, gated]) await airdrop(k.publicKey);
+
+    const start = nowSec() + 3;
+    poll = await createPoll(authority, pollCfg(2301, start));
+    for (let i = 0; i < 3; i++) await addOption(authority, poll, i, `Option ${i}`);
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+
+    for (let i = 0; i < 3; i++) await castVote(voter, poll, i, 1);
+    await castVote(gated, poll, 0, 1);
+    await castVote(gated, poll, 1, 1);
+    await castVote(gated, poll, 2, -1);
+  });
+
+  it("rejects changing to the same sentiment", async () => {
+    await expectAnchorErrCode(changeVote(voter, 2, 1), "AlreadyVotedThisOption");
+  });
+
+  it("flips +1 to -1", async () => {
+    await changeVote(voter, 2, -1);
+
+    const v = await program.account.voter.fetch(voterPda(poll, voter.publicKey));
+    expect(v.usedPlus).to.eq(2);
+    expect(v.usedMinus).to.eq(1);
+    const o = await program.account.optionNode.fetch(optionPda(poll, 2));
+    expect(o.plusVotes).to.eq(0);
+    expect(o.minusVotes).to.eq(2);
+    const r = await program.account.receipt.fetch(receiptPda(poll, 2, voter.publicKey));
+    expect(r.sentiment).to.eq(-1);
+  });
+
+  it("flips -1 back to +1", async () => {
+    await changeVote(voter, 2, 1);
+
+    const v = await program.account.voter.fetch(voterPda(poll, voter.publicKey));
+    expect(v.usedPlus).to.eq(3);
+    expect(v.usedMinus).to.eq(0);
+    const o = await program.account.optionNode.fetch(optionPda(poll, 2));
+    expect(o.plusVotes).to.eq(1);
+    expect(o.minusVotes).to.eq(1);
+  });
+
+  it("applies the ratio gate to an illegal flip", async () => {
+    // P=2, M=1: turning a plus into a minus would leave P=1 for M=2
+    await expectAnchorErrCode(changeVote(gated, 0, -1), "InsufficientPositivesForNegative");
+
+    const v = await program.account.voter.fetch(voterPda(poll, gated.publicKey));
+    expect(v.usedPlus).to.eq(2);
+    expect(v.usedMinus).to.eq(1);
+  });
+});
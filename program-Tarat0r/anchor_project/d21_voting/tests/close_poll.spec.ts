@@ -0,0 +1,87 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
+import {
+  program, provider, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode,
+  pollCfg, createPoll, beginPoll, addOption, castVote, voterPda, receiptPda,
+} from "./helpers";
+
+describe("close_poll", () => {
+  const authority = Keypair.generate();
+  const outsider = Keypair.generate();
+  const voter = Keypair.generate();
+  const lateVoter = Keypair.generate();
+  let poll: PublicKey;
+  let end: number;
+
+  const close = (signer: Keypair) =>
+    program.methods
+      .closePoll()
+      .accountsPartial({ authority: signer.publicKey, poll })
+      .signers([signer])
+      .rpc({ commitment: "confirmed" });
+
+  before(async () => {
+    for (const k of [authority, outsider, voter, lateVoter]) await airdrop(k.publicKey);
+
+    const start = nowSec() + 3;
+    end = start + 6;
+    poll = await createPoll(authority, pollCfg(2101, start, { endTs: new BN(end) }));
+    await addOption(authority, poll, 0, "Alpha");
+    await addOption(authority, poll, 1, "Beta");
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+    await castVote(voter, poll, 0, 1);
+  });
+
+  it("rejects closing before end_ts", async () => {
+    await expectAnchorErrCode(close(authority), "VotingNotOver");
+  });
+
+  it("rejects closing by someone other than the authority", async () => {
+    await waitUntilChainTime(end + 1);
+    await expectAnchorErrCode(close(outsider), "Unauthorized");
+  });
+
+  it("rejects votes once end_ts has passed", async () => {
+    await expectAnchorErrCode(castVote(lateVoter, poll, 0, 1), "VotingClosed");
+  });
+
+  it("closes the poll and refunds its rent", async () => {
+    const rent = await provider.connection.getBalance(poll);
+    const closedRent = await provider.connection.getMinimumBalanceForRentExemption(8 + 32);
+    const before = await provider.connection.getBalance(authority.publicKey);
+
+    await close(authority);
+
+    expect(await provider.connection.getAccountInfo(poll)).to.be.null;
+    const after = await provider.connection.getBalance(authority.publicKey);
+    // the authority also pays the ClosedPoll marker and the transaction fee
+    expect(after).to.be.greaterThan(before + rent - closedRent - 10_000);
+
+    await expectAnchorErrCode(castVote(lateVoter, poll, 0, 1), "AccountNotInitialized");
+  });
+
+  it("still lets voters reclaim their Receipt and Voter rent", async () => {
+    const receipt = receiptPda(poll, 0, voter.publicKey);
+    const voterAcc = voterPda(poll, voter.publicKey);
+
+    await program.methods
+      .closeReceipt(0)
+      .accountsPartial({ voterAuthority: voter.publicKey, poll, receipt })
+      .signers([voter])
+      .rpc({ commitment: "confirmed" });
+    await program.methods
+      .closeVoter()
+      .accountsPartial({ voterAuthority: voter.publicKey, poll, voter: voterAcc })
+      .signers([voter])
+      .rpc({ commitment: "confirmed" });
+
+    expect(await provider.connection.getAccountInfo(receipt)).to.be.null;
+    expect(await provider.connection.getAccountInfo(voterAcc)).to.be.null;
+  });
+
+  it("rejects re-creating a closed poll id", async () => {
+    await expectAnchorErrCode(createPoll(authority, pollCfg(2101, nowSec() + 60)), "PollIdClosed");
+  });
+});
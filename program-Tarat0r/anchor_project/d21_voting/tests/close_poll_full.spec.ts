@@ -0,0 +1,70 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
+import {
+  program, provider, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode,
+  pollCfg, createPoll, addOption, optionPda, labelSeed, labelGuardPda,
+} from "./helpers";
+
+describe("close_poll_full", () => {
+  const authority = Keypair.generate();
+  const labels = ["Alpha", "Beta"];
+  let poll: PublicKey;
+  let otherPoll: PublicKey;
+  let cleanup: PublicKey[];
+  let end: number;
+
+  const teardown = (accounts: PublicKey[]) =>
+    program.methods
+      .closePollFull()
+      .accountsPartial({ authority: authority.publicKey, poll })
+      .remainingAccounts(accounts.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })))
+      .signers([authority])
+      .rpc({ commitment: "confirmed" });
+
+  before(async () => {
+    await airdrop(authority.publicKey);
+
+    const start = nowSec() + 3;
+    end = start + 4;
+    poll = await createPoll(authority, pollCfg(1701, start, { endTs: new BN(end) }));
+    otherPoll = await createPoll(authority, pollCfg(1702, start));
+    for (const [i, label] of labels.entries()) await addOption(authority, poll, i, label);
+    await addOption(authority, otherPoll, 0, "Alpha");
+
+    cleanup = [
+      ...labels.map((_, i) => optionPda(poll, i)),
+      ...labels.map((label) => labelGuardPda(poll, labelSeed(label))),
+    ];
+  });
+
+  it("rejects teardown before the voting period is over", async () => {
+    await expectAnchorErrCode(teardown(cleanup), "VotingNotOver");
+  });
+
+  it("aborts the whole teardown on an account of another poll", async () => {
+    await waitUntilChainTime(end + 1);
+    await expectAnchorErrCode(teardown([...cleanup, optionPda(otherPoll, 0)]), "PollMismatch");
+
+    for (const pk of cleanup) {
+      expect(await provider.connection.getAccountInfo(pk), "account closed by a failed teardown").to.not.be.null;
+    }
+    expect((await program.account.poll.fetch(poll)).ended).to.eq(false);
+  });
+
+  it("ends the poll and refunds every option and label guard", async () => {
+    const rent = (await Promise.all(cleanup.map((pk) => provider.connection.getBalance(pk))))
+      .reduce((a, b) => a + b, 0);
+    const before = await provider.connection.getBalance(authority.publicKey);
+
+    await teardown(cleanup);
+
+    for (const pk of cleanup) {
+      expect(await provider.connection.getAccountInfo(pk)).to.be.null;
+    }
+    expect((await program.account.poll.fetch(poll)).ended).to.eq(true);
+    const after = await provider.connection.getBalance(authority.publicKey);
+    // the authority also pays the transaction fee
+    expect(after).to.be.greaterThan(before + rent - 10_000);
+  });
+});
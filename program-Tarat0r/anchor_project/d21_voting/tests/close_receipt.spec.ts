@@ -0,0 +1,56 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
+import {
+  program, provider, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode,
+  pollCfg, createPoll, beginPoll, addOption, castVote, optionPda, receiptPda,
+} from "./helpers";
+
+describe("close_receipt", () => {
+  const authority = Keypair.generate();
+  const voter = Keypair.generate();
+  let poll: PublicKey;
+  let end: number;
+
+  const closeReceipt = (index: number) =>
+    program.methods
+      .closeReceipt(index)
+      .accountsPartial({
+        voterAuthority: voter.publicKey,
+        poll,
+        receipt: receiptPda(poll, index, voter.publicKey),
+      })
+      .signers([voter])
+      .rpc({ commitment: "confirmed" });
+
+  before(async () => {
+    for (const k of [authority, voter]) await airdrop(k.publicKey);
+
+    const start = nowSec() + 3;
+    end = start + 6;
+    poll = await createPoll(authority, pollCfg(3901, start, { endTs: new BN(end) }));
+    await addOption(authority, poll, 0, "Alpha");
+    await addOption(authority, poll, 1, "Beta");
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+    await castVote(voter, poll, 0, 1);
+  });
+
+  it("rejects closing while voting is open", async () => {
+    await expectAnchorErrCode(closeReceipt(0), "VotingNotOver");
+  });
+
+  it("refunds the receipt after the poll ends and keeps the tally", async () => {
+    await waitUntilChainTime(end + 1);
+    const receipt = receiptPda(poll, 0, voter.publicKey);
+    const rent = await provider.connection.getBalance(receipt);
+    const before = await provider.connection.getBalance(voter.publicKey);
+
+    await closeReceipt(0);
+
+    expect(await provider.connection.getAccountInfo(receipt)).to.be.null;
+    const after = await provider.connection.getBalance(voter.publicKey);
+    expect(after).to.be.greaterThan(before + rent - 10_000);
+    expect((await program.account.optionNode.fetch(optionPda(poll, 0))).plusVotes).to.eq(1);
+  });
+});
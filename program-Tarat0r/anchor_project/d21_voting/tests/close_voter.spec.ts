@@ -0,0 +1,68 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
+import {
+  program, provider, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode,
+  pollCfg, createPoll, beginPoll, addOption, castVote, voterPda,
+} from "./helpers";
+
+describe("close_voter", () => {
+  const authority = Keypair.generate();
+  const voter = Keypair.generate();
+  let poll: PublicKey;
+  let end: number;
+
+  const closeVoter = () =>
+    program.methods
+      .closeVoter()
+      .accountsPartial({
+        voterAuthority: voter.publicKey,
+        poll,
+        voter: voterPda(poll, voter.publicKey),
+      })
+      .signers([voter])
+      .rpc({ commitment: "confirmed" });
+
+  before(async () => {
+    for (const k of [authority, voter]) await airdrop(k.publicKey);
+
+    const start = nowSec() + 3;
+    end = start + 6;
+    poll = await createPoll(authority, pollCfg(4001, start, { endTs: new BN(end) }));
+    await addOption(authority, poll, 0, "Alpha");
+    await addOption(authority, poll, 1, "Beta");
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+    await castVote(voter, poll, 0, 1);
+  });
+
+  it("rejects closing while voting is open", async () => {
+    await expectAnchorErrCode(closeVoter(), "VotingNotOver");
+  });
+
+  it("refunds the voter account after the poll ends", async () => {
+    await waitUntilChainTime(end + 1);
+    const voterAcc = voterPda(poll, voter.publicKey);
+    const rent = await provider.connection.getBalance(voterAcc);
+    const before = await provider.connection.getBalance(voter.publicKey);
+
+    await closeVoter();
+
+    expect(await provider.connection.getAccountInfo(voterAcc)).to.be.null;
+    const after = await provider.connection.getBalance(voter.publicKey);
+    expect(after).to.be.greaterThan(before + rent - 10_000);
+  });
+
+  it("does not let a closed voter vote again with fresh credits", async () => {
+    await expectAnchorErrCode(castVote(voter, poll, 1, 1), "VotingClosed");
+  });
+
+  it("does not let the poll be reopened after end_ts", async () => {
+    const extend = program.methods
+      .extendVotingWindow(new BN(nowSec() + 600))
+      .accountsPartial({ authority: authority.publicKey, poll })
+      .signers([authority])
+      .rpc({ commitment: "confirmed" });
+    await expectAnchorErrCode(extend, "VotingClosed");
+  });
+});
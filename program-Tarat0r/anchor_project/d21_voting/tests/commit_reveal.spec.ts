@@ -0,0 +1,103 @@
+import { expect } from "chai";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import BN from "bn.js";
+import { createHash, randomBytes } from "crypto";
+import {
+  program, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode,
+  pollCfg, createPoll, beginPoll, addOption, castVote, optionPda, voterPda, receiptPda,
+} from "./helpers";
+
+function commitmentPda(poll: PublicKey, voter: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("commitment"), poll.toBuffer(), voter.toBuffer()],
+    program.programId
+  )[0];
+}
+
+function commitmentHash(index: number, sentiment: number, nonce: Buffer): number[] {
+  const head = Buffer.alloc(3);
+  head.writeUInt16LE(index, 0);
+  head.writeInt8(sentiment, 2);
+  return [...createHash("sha256").update(Buffer.concat([head, nonce])).digest()];
+}
+
+describe("commit-reveal", () => {
+  const authority = Keypair.generate();
+  const honest = Keypair.generate();
+  const cheater = Keypair.generate();
+  let poll: PublicKey;
+  let commitEnd: number;
+  const nonceA = randomBytes(32);
+  const nonceB = randomBytes(32);
+
+  const commit = (voter: Keypair, hash: number[]) =>
+    program.methods
+      .commitVote(hash)
+      .accountsPartial({
+        voterAuthority: voter.publicKey,
+        poll,
+        commitment: commitmentPda(poll, voter.publicKey),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc({ commitment: "confirmed" });
+
+  const reveal = (voter: Keypair, index: number, sentiment: number, nonce: Buffer) =>
+    program.methods
+      .revealVote(index, sentiment, [...nonce], [])
+      .accountsPartial({
+        voterAuthority: voter.publicKey,
+        poll,
+        optionNode: optionPda(poll, index),
+        commitment: commitmentPda(poll, voter.publicKey),
+        voter: voterPda(poll, voter.publicKey),
+        receipt: receiptPda(poll, index, voter.publicKey),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc({ commitment: "confirmed" });
+
+  before(async () => {
+    for (const k of [authority, honest, cheater]) await airdrop(k.publicKey);
+
+    const start = nowSec() + 3;
+    commitEnd = start + 6;
+    poll = await createPoll(authority, pollCfg(4601, start, {
+      commitEndTs: new BN(commitEnd),
+      revealEndTs: new BN(start + 600),
+    }));
+    await addOption(authority, poll, 0, "Alpha");
+    await addOption(authority, poll, 1, "Beta");
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+  });
+
+  it("rejects plain votes on a commit-reveal poll", async () => {
+    await expectAnchorErrCode(castVote(honest, poll, 0, 1), "UnsupportedVotingMode");
+  });
+
+  it("stores commitments during the commit phase", async () => {
+    await commit(honest, commitmentHash(1, 1, nonceA));
+    await commit(cheater, commitmentHash(0, 1, nonceB));
+    const c = await program.account.commitment.fetch(commitmentPda(poll, honest.publicKey));
+    expect(c.commitment).to.deep.eq(commitmentHash(1, 1, nonceA));
+  });
+
+  it("rejects reveals before the reveal phase", async () => {
+    await expectAnchorErrCode(reveal(honest, 1, 1, nonceA), "RevealNotStarted");
+  });
+
+  it("applies a correct reveal", async () => {
+    await waitUntilChainTime(commitEnd + 1);
+    await reveal(honest, 1, 1, nonceA);
+    expect((await program.account.optionNode.fetch(optionPda(poll, 1))).plusVotes).to.eq(1);
+    const info = await program.provider.connection.getAccountInfo(commitmentPda(poll, honest.publicKey));
+    expect(info).to.eq(null);
+  });
+
+  it("rejects a tampered reveal", async () => {
+    await expectAnchorErrCode(reveal(cheater, 1, 1, nonceB), "CommitmentMismatch");
+    await expectAnchorErrCode(reveal(cheater, 0, -1, nonceB), "CommitmentMismatch");
+    expect((await program.account.optionNode.fetch(optionPda(poll, 0))).plusVotes).to.eq(0);
+  });
+});
@@ -0,0 +1,59 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode, eventsOf,
+  pollCfg, createPoll, beginPoll, addOption, castVote, optionPda,
+} from "./helpers";
+
+describe("compute_winner", () => {
+  const authority = Keypair.generate();
+  const voters = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+  let poll: PublicKey;
+
+  const computeWinner = (order: number[]) =>
+    program.methods
+      .computeWinner()
+      .accountsPartial({ poll })
+      .remainingAccounts(order.map((i) => ({
+        pubkey: optionPda(poll, i), isSigner: false, isWritable: false,
+      })))
+      .rpc({ commitment: "confirmed" });
+
+  before(async () => {
+    await airdrop(authority.publicKey);
+    for (const v of voters) await airdrop(v.publicKey);
+
+    const start = nowSec() + 3;
+    poll = await createPoll(authority, pollCfg(901, start));
+    await addOption(authority, poll, 0, "Alpha");
+    await addOption(authority, poll, 1, "Beta");
+    await addOption(authority, poll, 2, "Gamma");
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+
+    // Alpha net 1, Beta and Gamma tie on net 2 and plus 2 -> lower index wins
+    await castVote(voters[0], poll, 0, 1);
+    await castVote(voters[0], poll, 1, 1);
+    await castVote(voters[1], poll, 1, 1);
+    await castVote(voters[1], poll, 2, 1);
+    await castVote(voters[2], poll, 2, 1);
+  });
+
+  it("picks the same winner whatever the input order", async () => {
+    for (const order of [[0, 1, 2], [2, 1, 0], [1, 2, 0], [2, 0, 1]]) {
+      const ev = (await eventsOf(await computeWinner(order))).find((e) => e.name === "winnerComputed");
+      expect(ev, `WinnerComputed not emitted for ${order}`).to.exist;
+      expect(ev!.data.optionIndex).to.eq(1);
+      expect(ev!.data.net.toNumber()).to.eq(2);
+      expect(ev!.data.plusVotes).to.eq(2);
+    }
+  });
+
+  it("rejects the same option passed twice", async () => {
+    await expectAnchorErrCode(computeWinner([0, 1, 1]), "DuplicateOptionAccount");
+  });
+
+  it("rejects a partial option list", async () => {
+    await expectAnchorErrCode(computeWinner([2, 0]), "OptionCountMismatch");
+  });
+});
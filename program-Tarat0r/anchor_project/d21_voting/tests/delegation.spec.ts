@@ -0,0 +1,90 @@
+import { expect } from "chai";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode,
+  pollCfg, createPoll, beginPoll, addOption, optionPda, voterPda, receiptPda,
+} from "./helpers";
+
+function delegationPda(poll: PublicKey, delegator: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("delegation"), poll.toBuffer(), delegator.toBuffer()],
+    program.programId
+  )[0];
+}
+
+describe("vote delegation", () => {
+  const authority = Keypair.generate();
+  const delegator = Keypair.generate();
+  const delegate = Keypair.generate();
+  let poll: PublicKey;
+
+  const delegateTo = (to: PublicKey) =>
+    program.methods
+      .delegateVotes(to)
+      .accountsPartial({
+        delegator: delegator.publicKey,
+        poll,
+        delegation: delegationPda(poll, delegator.publicKey),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([delegator])
+      .rpc({ commitment: "confirmed" });
+
+  const delegatedVote = (index: number) =>
+    program.methods
+      .castVote(index, 1, null, [])
+      .accountsPartial({
+        payer: delegate.publicKey,
+        voterAuthority: delegate.publicKey,
+        poll,
+        delegation: delegationPda(poll, delegator.publicKey),
+        optionNode: optionPda(poll, index),
+        voter: voterPda(poll, delegator.publicKey),
+        receipt: receiptPda(poll, index, delegator.publicKey),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([delegate])
+      .rpc({ commitment: "confirmed" });
+
+  before(async () => {
+    for (const k of [authority, delegator, delegate]) await airdrop(k.publicKey);
+
+    const start = nowSec() + 3;
+    poll = await createPoll(authority, pollCfg(4801, start));
+    await addOption(authority, poll, 0, "Alpha");
+    await addOption(authority, poll, 1, "Beta");
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+  });
+
+  it("rejects delegating to oneself", async () => {
+    await expectAnchorErrCode(delegateTo(delegator.publicKey), "SelfDelegation");
+  });
+
+  it("lets the delegate vote with the delegator's credits", async () => {
+    await delegateTo(delegate.publicKey);
+    await delegatedVote(0);
+
+    const v = await program.account.voter.fetch(voterPda(poll, delegator.publicKey));
+    expect(v.voter.toBase58()).to.eq(delegator.publicKey.toBase58());
+    expect(v.usedPlus).to.eq(1);
+    const r = await program.account.receipt.fetch(receiptPda(poll, 0, delegator.publicKey));
+    expect(r.voter.toBase58()).to.eq(delegator.publicKey.toBase58());
+    const own = await program.provider.connection.getAccountInfo(voterPda(poll, delegate.publicKey));
+    expect(own).to.eq(null);
+  });
+
+  it("blocks delegated votes after revocation", async () => {
+    await program.methods
+      .revokeDelegation()
+      .accountsPartial({
+        delegator: delegator.publicKey,
+        poll,
+        delegation: delegationPda(poll, delegator.publicKey),
+      })
+      .signers([delegator])
+      .rpc({ commitment: "confirmed" });
+
+    await expectAnchorErrCode(delegatedVote(1), "AccountNotInitialized");
+  });
+});
@@ -0,0 +1,51 @@
+import { expect } from "chai";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  program, provider, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode,
+  pollCfg, createPoll,
+} from "./helpers";
+
+const MAX_TITLE = 64;
+const MAX_TITLE_EXTENDED = 256;
+
+describe("expand_poll_text", () => {
+  const authority = Keypair.generate();
+  let poll: PublicKey;
+  let startedPoll: PublicKey;
+  let start: number;
+
+  const expand = (target: PublicKey, title: string | null, desc: string | null) =>
+    program.methods
+      .expandPollText(title, desc)
+      .accountsPartial({ authority: authority.publicKey, poll: target, systemProgram: SystemProgram.programId })
+      .signers([authority])
+      .rpc({ commitment: "confirmed" });
+
+  before(async () => {
+    await airdrop(authority.publicKey);
+    start = nowSec() + 3;
+    poll = await createPoll(authority, pollCfg(5201, nowSec() + 3600));
+    startedPoll = await createPoll(authority, pollCfg(5202, start));
+  });
+
+  it("grows the account and stores a title past MAX_TITLE", async () => {
+    const sizeBefore = (await provider.connection.getAccountInfo(poll))!.data.length;
+    const title = "t".repeat(MAX_TITLE + 100);
+    await expand(poll, title, null);
+
+    const sizeAfter = (await provider.connection.getAccountInfo(poll))!.data.length;
+    expect(sizeAfter).to.be.greaterThan(sizeBefore);
+    const acc = await program.account.poll.fetch(poll);
+    expect(acc.title).to.eq(title);
+    expect(acc.description).to.eq("test poll");
+  });
+
+  it("rejects a title past MAX_TITLE_EXTENDED", async () => {
+    await expectAnchorErrCode(expand(poll, "t".repeat(MAX_TITLE_EXTENDED + 1), null), "TitleTooLong");
+  });
+
+  it("rejects growing once voting has started", async () => {
+    await waitUntilChainTime(start);
+    await expectAnchorErrCode(expand(startedPoll, "t".repeat(MAX_TITLE + 1), null), "VotingStarted");
+  });
+});
@@ -0,0 +1,60 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
+import {
+  program, airdrop, nowSec, expectAnchorErrCode, eventsOf, pollCfg, createPoll,
+} from "./helpers";
+
+describe("extend_voting_window", () => {
+  const authority = Keypair.generate();
+  const rando = Keypair.generate();
+  let poll: PublicKey;
+  let endedPoll: PublicKey;
+
+  const extend = (signer: Keypair, target: PublicKey, newEnd: number) =>
+    program.methods
+      .extendVotingWindow(new BN(newEnd))
+      .accountsPartial({ authority: signer.publicKey, poll: target })
+      .signers([signer])
+      .rpc({ commitment: "confirmed" });
+
+  before(async () => {
+    for (const k of [authority, rando]) await airdrop(k.publicKey);
+    poll = await createPoll(authority, pollCfg(3001, nowSec() + 60));
+    endedPoll = await createPoll(authority, pollCfg(3002, nowSec() + 60));
+    await program.methods
+      .endPoll()
+      .accountsPartial({ signer: authority.publicKey, poll: endedPoll, admins: null })
+      .signers([authority])
+      .rpc({ commitment: "confirmed" });
+  });
+
+  it("rejects new_end_ts equal to the current end_ts", async () => {
+    const end = (await program.account.poll.fetch(poll)).endTs.toNumber();
+    await expectAnchorErrCode(extend(authority, poll, end), "InvalidTimeWindow");
+  });
+
+  it("rejects shortening the window", async () => {
+    const end = (await program.account.poll.fetch(poll)).endTs.toNumber();
+    await expectAnchorErrCode(extend(authority, poll, end - 1), "InvalidTimeWindow");
+  });
+
+  it("extends by one second past end_ts", async () => {
+    const end = (await program.account.poll.fetch(poll)).endTs.toNumber();
+    const ev = (await eventsOf(await extend(authority, poll, end + 1))).find((e) => e.name === "votingWindowExtended");
+    expect(ev, "VotingWindowExtended not emitted").to.exist;
+    expect(ev!.data.oldEndTs.toNumber()).to.eq(end);
+    expect(ev!.data.newEndTs.toNumber()).to.eq(end + 1);
+    expect((await program.account.poll.fetch(poll)).endTs.toNumber()).to.eq(end + 1);
+  });
+
+  it("rejects extension by someone other than the authority", async () => {
+    const end = (await program.account.poll.fetch(poll)).endTs.toNumber();
+    await expectAnchorErrCode(extend(rando, poll, end + 60), "Unauthorized");
+  });
+
+  it("rejects extending an ended poll", async () => {
+    const end = (await program.account.poll.fetch(endedPoll)).endTs.toNumber();
+    await expectAnchorErrCode(extend(authority, endedPoll, end + 60), "VotingClosed");
+  });
+});
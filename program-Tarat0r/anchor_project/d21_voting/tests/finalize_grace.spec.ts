@@ -0,0 +1,42 @@
+import { Keypair } from "@solana/web3.js";
+import BN from "bn.js";
+import {
+  airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode, pollCfg, createPoll, beginPoll, addOption, castVote,
+} from "./helpers";
+
+describe("finalize_grace", () => {
+  const authority = Keypair.generate();
+  const voter = Keypair.generate();
+  const lateVoter = Keypair.generate();
+
+  before(async () => {
+    await airdrop(authority.publicKey);
+    await airdrop(voter.publicKey);
+    await airdrop(lateVoter.publicKey);
+  });
+
+  it("rejects a negative finalize_grace", async () => {
+    await expectAnchorErrCode(
+      createPoll(authority, pollCfg(701, nowSec() + 60, { finalizeGrace: new BN(-1) })),
+      "InvalidFinalizeGrace"
+    );
+  });
+
+  it("rejects votes after end_ts even while the grace window is open", async () => {
+    const start = nowSec() + 3;
+    const end = start + 4;
+    const poll = await createPoll(authority, pollCfg(702, start, {
+      endTs: new BN(end),
+      finalizeGrace: new BN(600),
+      minOptions: 1,
+    }));
+    await addOption(authority, poll, 0, "Alpha");
+
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+    await castVote(voter, poll, 0, 1);
+
+    await waitUntilChainTime(end + 1);
+    await expectAnchorErrCode(castVote(lateVoter, poll, 0, 1), "VotingClosed");
+  });
+});
@@ -0,0 +1,108 @@
+import { expect } from "chai";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import BN from "bn.js";
+import {
+  program, provider, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode, eventsOf,
+  pollCfg, createPoll, beginPoll, addOption, castVote, optionPda, snapshotPda,
+} from "./helpers";
+
+describe("finalize_poll", () => {
+  const authority = Keypair.generate();
+  const voters = [Keypair.generate(), Keypair.generate()];
+  let clear: PublicKey;
+  let tied: PublicKey;
+  let empty: PublicKey;
+  let end: number;
+
+  const finalize = (poll: PublicKey, indexes: number[]) =>
+    program.methods
+      .finalizePoll()
+      .accountsPartial({
+        payer: provider.wallet.publicKey,
+        poll,
+        snapshot: snapshotPda(poll),
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(indexes.map((i) => ({
+        pubkey: optionPda(poll, i), isSigner: false, isWritable: false,
+      })))
+      .rpc({ commitment: "confirmed" });
+
+  before(async () => {
+    await airdrop(authority.publicKey);
+    for (const v of voters) await airdrop(v.publicKey);
+
+    const start = nowSec() + 3;
+    end = start + 8;
+    const window = { endTs: new BN(end) };
+    clear = await createPoll(authority, pollCfg(2201, start, window));
+    tied = await createPoll(authority, pollCfg(2202, start, window));
+    empty = await createPoll(authority, pollCfg(2203, start, window));
+    for (const poll of [clear, tied]) {
+      await addOption(authority, poll, 0, "Alpha");
+      await addOption(authority, poll, 1, "Beta");
+    }
+    await waitUntilChainTime(start);
+    for (const p of [clear, tied, empty]) await beginPoll(authority, p);
+
+    // clear: Beta net 2, Alpha net 0
+    await castVote(voters[0], clear, 1, 1);
+    await castVote(voters[1], clear, 1, 1);
+    // tied: both net 1 with one plus vote each
+    await castVote(voters[0], tied, 0, 1);
+    await castVote(voters[1], tied, 1, 1);
+  });
+
+  it("rejects finalizing before end_ts", async () => {
+    await expectAnchorErrCode(finalize(clear, [0, 1]), "VotingNotOver");
+  });
+
+  it("rejects a partial option list", async () => {
+    await waitUntilChainTime(end + 1);
+    await expectAnchorErrCode(finalize(clear, [0]), "OptionCountMismatch");
+  });
+
+  it("stores a clear winner and emits PollFinalized", async () => {
+    const ev = (await eventsOf(await finalize(clear, [1, 0]))).find((e) => e.name === "pollFinalized");
+    expect(ev, "PollFinalized not emitted").to.exist;
+    expect(ev!.data.winnerIndex).to.eq(1);
+    expect(ev!.data.winnerScore.toNumber()).to.eq(2);
+
+    const acc = await program.account.poll.fetch(clear);
+    expect(acc.winnerIndex).to.eq(1);
+    expect(acc.winnerScore.toNumber()).to.eq(2);
+  });
+
+  it("resolves a tie to the lowest index", async () => {
+    await finalize(tied, [1, 0]);
+    const acc = await program.account.poll.fetch(tied);
+    expect(acc.winnerIndex).to.eq(0);
+    expect(acc.winnerScore.toNumber()).to.eq(1);
+  });
+
+  it("rejects a poll without options", async () => {
+    await expectAnchorErrCode(finalize(empty, []), "NoOptions");
+  });
+
+  it("writes a results snapshot matching the option totals", async () => {
+    const snap = await program.account.resultsSnapshot.fetch(snapshotPda(clear));
+    const options = await Promise.all([0, 1].map((i) => program.account.optionNode.fetch(optionPda(clear, i))));
+    expect(snap.poll.toBase58()).to.eq(clear.toBase58());
+    expect(snap.totalPlus.toNumber()).to.eq(options.reduce((n, o) => n + o.plusVotes, 0));
+    expect(snap.totalMinus.toNumber()).to.eq(options.reduce((n, o) => n + o.minusVotes, 0));
+    expect(snap.winnerIndex).to.eq(1);
+    expect(snap.winnerScore.toNumber()).to.eq(2);
+    expect(snap.optionCount).to.eq(2);
+  });
+
+  it("cannot be finalized twice", async () => {
+    let failed = false;
+    try {
+      await finalize(clear, [0, 1]);
+    } catch (e: any) {
+      failed = true;
+      expect(String(e)).to.match(/(already in use|AccountInUse|account exists)/i);
+    }
+    expect(failed).to.eq(true);
+  });
+});
@@ -0,0 +1,57 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
+import {
+  airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode,
+  pollCfg, createPoll, beginPoll, addOption, castVote,
+} from "./helpers";
+
+describe("grace_secs", () => {
+  const authority = Keypair.generate();
+  const voters = [Keypair.generate(), Keypair.generate(), Keypair.generate(), Keypair.generate()];
+  const grace = 4;
+  let poll: PublicKey;
+  let start: number;
+  let end: number;
+
+  before(async () => {
+    await airdrop(authority.publicKey);
+    for (const v of voters) await airdrop(v.publicKey);
+
+    start = nowSec() + 4;
+    end = start + 6;
+    poll = await createPoll(authority, pollCfg(5601, start, {
+      endTs: new BN(end),
+      graceSecs: new BN(grace),
+      minOptions: 1,
+    }));
+    await addOption(authority, poll, 0, "Alpha");
+  });
+
+  it("rejects a negative grace_secs", async () => {
+    await expectAnchorErrCode(
+      createPoll(authority, pollCfg(5602, nowSec() + 60, { graceSecs: new BN(-1) })),
+      "InvalidGraceSecs"
+    );
+  });
+
+  it("keeps start_ts strict", async () => {
+    await expectAnchorErrCode(castVote(voters[0], poll, 0, 1), "VotingNotStarted");
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+  });
+
+  it("accepts a vote at end_ts", async () => {
+    await waitUntilChainTime(end);
+    await castVote(voters[1], poll, 0, 1);
+  });
+
+  it("accepts a vote at end_ts + grace", async () => {
+    await waitUntilChainTime(end + grace);
+    await castVote(voters[2], poll, 0, 1);
+  });
+
+  it("rejects a vote at end_ts + grace + 1", async () => {
+    await waitUntilChainTime(end + grace + 1);
+    await expectAnchorErrCode(castVote(voters[3], poll, 0, 1), "VotingClosed");
+  });
+});
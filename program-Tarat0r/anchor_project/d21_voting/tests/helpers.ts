@@ -0,0 +1,210 @@
+import { expect } from "chai";
+import * as anchor from "@coral-xyz/anchor";
+import type { Program } from "@coral-xyz/anchor";
+import { SystemProgram, PublicKey, Keypair } from "@solana/web3.js";
+import BN from "bn.js";
+import { createHash } from "crypto";
+import type { D21Voting } from "../target/types/d21_voting";
+
+// Shared helpers for the instruction specs. Mirrors the PDA seeds and
+// label canonicalization used by the program.
+
+// ---------- setup helpers ----------
+export const provider = anchor.AnchorProvider.env();
+anchor.setProvider(provider);
+export const program = anchor.workspace.D21Voting as Program<D21Voting>;
+
+export function u64LeBytes(n: BN): Buffer {
+  const b = Buffer.alloc(8);
+  b.writeBigUInt64LE(BigInt(n.toString()));
+  return b;
+}
+export function u16LeBytes(n: number): Buffer {
+  const b = Buffer.alloc(2);
+  b.writeUInt16LE(n);
+  return b;
+}
+export async function airdrop(pk: PublicKey, lamports = 2e9) {
+  const sig = await provider.connection.requestAirdrop(pk, lamports);
+  await provider.connection.confirmTransaction(sig, "confirmed");
+}
+export function nowSec() {
+  return Math.floor(Date.now() / 1000);
+}
+export async function waitUntilChainTime(targetTs: number, timeoutMs = 15000, pollMs = 250) {
+  const start = Date.now();
+  while (Date.now() - start < timeoutMs) {
+    const slot = await provider.connection.getSlot("processed");
+    const bt = await provider.connection.getBlockTime(slot); // seconds | null
+    if (bt !== null && bt >= targetTs) return;
+    await new Promise((r) => setTimeout(r, pollMs));
+  }
+  throw new Error(`timeout waiting for chain time >= ${targetTs}`);
+}
+function anchorErrCode(e: any): string | undefined {
+  return e?.error?.errorCode?.code;
+}
+export async function expectAnchorErrCode(p: Promise<any>, code: string) {
+  try { await p; expect.fail("expected failure"); }
+  catch (e) { const got = anchorErrCode(e); if (!got) throw e; expect(got).to.equal(code); }
+}
+export async function eventsOf(sig: string) {
+  const tx = await provider.connection.getTransaction(sig, {
+    commitment: "confirmed",
+    maxSupportedTransactionVersion: 0,
+  });
+  const parser = new anchor.EventParser(program.programId, new anchor.BorshCoder(program.idl));
+  return [...parser.parseLogs(tx!.meta!.logMessages!)];
+}
+
+// Default config for a poll opening at `start`; override any field per test.
+export function pollCfg(pollId: number, start: number, overrides: Record<string, any> = {}) {
+  return {
+    pollId: new BN(pollId),
+    title: `Poll ${pollId}`,
+    description: "test poll",
+    plusCredits: 3,
+    minusCredits: 1,
+    startTs: new BN(start),
+    endTs: new BN(start + 3600),
+    finalizeGrace: new BN(0),
+    minLabelLen: 1,
+    negRatio: 2,
+    quorum: 0,
+    minOptions: 2,
+    mode: { d21: {} },
+    commitEndTs: new BN(0),
+    revealEndTs: new BN(0),
+    allowlistRoot: null,
+    graceSecs: new BN(0),
+    voteCooldownSecs: new BN(0),
+    maxVotesPerVoter: 0,
+    tags: [],
+    ...overrides,
+  };
+}
+
+// ---------- PDA helpers ----------
+export function pollPda(authority: PublicKey, pollId: BN): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("poll"), authority.toBuffer(), u64LeBytes(pollId)],
+    program.programId
+  )[0];
+}
+export function optionPda(poll: PublicKey, index: number): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("option"), poll.toBuffer(), u16LeBytes(index)],
+    program.programId
+  )[0];
+}
+export function labelSeed(label: string): Buffer {
+  const canonical = label.normalize("NFC").trim().split(/\s+/).join(" ").toLowerCase();
+  return createHash("sha256").update(Buffer.from(canonical, "utf8")).digest(); // 32 bytes
+}
+export function labelGuardPda(poll: PublicKey, seed: Buffer): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("option_label"), poll.toBuffer(), seed],
+    program.programId
+  )[0];
+}
+export function tagIndexPda(tag: string): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("tag"), Buffer.from(tag, "utf8")],
+    program.programId
+  )[0];
+}
+export function voterPda(poll: PublicKey, voter: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("voter"), poll.toBuffer(), voter.toBuffer()],
+    program.programId
+  )[0];
+}
+export function receiptPda(poll: PublicKey, index: number, voter: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("receipt"), poll.toBuffer(), u16LeBytes(index), voter.toBuffer()],
+    program.programId
+  )[0];
+}
+export function adminsPda(poll: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("admins"), poll.toBuffer()],
+    program.programId
+  )[0];
+}
+
+export function allowedVoterPda(poll: PublicKey, voter: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("allowed"), poll.toBuffer(), voter.toBuffer()],
+    program.programId
+  )[0];
+}
+
+export function snapshotPda(poll: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("snapshot"), poll.toBuffer()],
+    program.programId
+  )[0];
+}
+
+// ---------- instruction helpers ----------
+export async function createPoll(authority: Keypair, cfg: any): Promise<PublicKey> {
+  const poll = pollPda(authority.publicKey, cfg.pollId);
+  await program.methods
+    .initializePoll(cfg)
+    .accountsPartial({
+      payer: authority.publicKey,
+      authority: authority.publicKey,
+      poll,
+      systemProgram: SystemProgram.programId,
+    })
+    .remainingAccounts((cfg.tags ?? []).map((tag: string) => ({
+      pubkey: tagIndexPda(tag), isSigner: false, isWritable: true,
+    })))
+    .signers([authority])
+    .rpc();
+  return poll;
+}
+export async function beginPoll(authority: Keypair, poll: PublicKey) {
+  await program.methods
+    .beginPoll()
+    .accountsPartial({ authority: authority.publicKey, poll })
+    .signers([authority])
+    .rpc();
+}
+export async function addOption(
+  authority: Keypair, poll: PublicKey, index: number, label: string, admins: PublicKey | null = null,
+  description = "", imageUri = ""
+) {
+  const seed = labelSeed(label);
+  await program.methods
+    .addOption(index, label, [...seed], description, imageUri)
+    .accountsPartial({
+      authority: authority.publicKey,
+      poll,
+      admins,
+      optionNode: optionPda(poll, index),
+      labelGuard: labelGuardPda(poll, seed),
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([authority])
+    .rpc();
+}
+export function castVote(
+  voter: Keypair, poll: PublicKey, index: number, sentiment: number, note: string | null = null,
+  sponsor: Keypair = voter, proof: number[][] = [], allowedVoter: PublicKey | null = null
+) {
+  return program.methods
+    .castVote(index, sentiment, note, proof)
+    .accountsPartial({
+      payer: sponsor.publicKey,
+      voterAuthority: voter.publicKey,
+      poll,
+      optionNode: optionPda(poll, index),
+      voter: voterPda(poll, voter.publicKey),
+      receipt: receiptPda(poll, index, voter.publicKey),
+      allowedVoter,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers(sponsor === voter ? [voter] : [voter, sponsor])
+    .rpc({ commitment: "confirmed" });
+}
@@ -58,6 +58,19 @@ describe("initialize_poll", () => {
       minusCredits: 0,
       startTs: new BN(now + 60),
       endTs: new BN(now + 7 * 24 * 3600),
+      finalizeGrace: new BN(0),
+      minLabelLen: 1,
+      negRatio: 2,
+      quorum: 0,
+      minOptions: 2,
+      mode: { d21: {} },
+      commitEndTs: new BN(0),
+      revealEndTs: new BN(0),
+      allowlistRoot: null,
+      graceSecs: new BN(0),
+      voteCooldownSecs: new BN(0),
+      maxVotesPerVoter: 0,
+      tags: [],
     };
 
     pollPda = PublicKey.findProgramAddressSync(
@@ -101,6 +114,19 @@ describe("initialize_poll", () => {
       minusCredits: 0,
       startTs: new BN(now + 60),
       endTs: new BN(now + 600),
+      finalizeGrace: new BN(0),
+      minLabelLen: 1,
+      negRatio: 2,
+      quorum: 0,
+      minOptions: 2,
+      mode: { d21: {} },
+      commitEndTs: new BN(0),
+      revealEndTs: new BN(0),
+      allowlistRoot: null,
+      graceSecs: new BN(0),
+      voteCooldownSecs: new BN(0),
+      maxVotesPerVoter: 0,
+      tags: [],
     };
 
     const [pda] = PublicKey.findProgramAddressSync(
@@ -133,6 +159,19 @@ describe("initialize_poll", () => {
       minusCredits: 0,
       startTs: new BN(now + 60),
       endTs: new BN(now + 600),
+      finalizeGrace: new BN(0),
+      minLabelLen: 1,
+      negRatio: 2,
+      quorum: 0,
+      minOptions: 2,
+      mode: { d21: {} },
+      commitEndTs: new BN(0),
+      revealEndTs: new BN(0),
+      allowlistRoot: null,
+      graceSecs: new BN(0),
+      voteCooldownSecs: new BN(0),
+      maxVotesPerVoter: 0,
+      tags: [],
     };
 
     const [pda] = PublicKey.findProgramAddressSync(
@@ -165,6 +204,19 @@ describe("initialize_poll", () => {
       minusCredits: 1,
       startTs: new BN(now + 60),
       endTs: new BN(now + 600),
+      finalizeGrace: new BN(0),
+      minLabelLen: 1,
+      negRatio: 2,
+      quorum: 0,
+      minOptions: 2,
+      mode: { d21: {} },
+      commitEndTs: new BN(0),
+      revealEndTs: new BN(0),
+      allowlistRoot: null,
+      graceSecs: new BN(0),
+      voteCooldownSecs: new BN(0),
+      maxVotesPerVoter: 0,
+      tags: [],
     };
 
     const [pda] = PublicKey.findProgramAddressSync(
@@ -198,6 +250,19 @@ describe("initialize_poll", () => {
         minusCredits: 0,
         startTs: new BN(now + 500),
         endTs: new BN(now + 400), // end <= start
+        finalizeGrace: new BN(0),
+        minLabelLen: 1,
+        negRatio: 2,
+        quorum: 0,
+        minOptions: 2,
+        mode: { d21: {} },
+        commitEndTs: new BN(0),
+        revealEndTs: new BN(0),
+        allowlistRoot: null,
+        graceSecs: new BN(0),
+        voteCooldownSecs: new BN(0),
+        maxVotesPerVoter: 0,
+        tags: [],
       },
       {
         pollId: new BN(6),
@@ -207,6 +272,19 @@ describe("initialize_poll", () => {
         minusCredits: 0,
         startTs: new BN(now - 10), // start in past
         endTs: new BN(now + 400),
+        finalizeGrace: new BN(0),
+        minLabelLen: 1,
+        negRatio: 2,
+        quorum: 0,
+        minOptions: 2,
+        mode: { d21: {} },
+        commitEndTs: new BN(0),
+        revealEndTs: new BN(0),
+        allowlistRoot: null,
+        graceSecs: new BN(0),
+        voteCooldownSecs: new BN(0),
+        maxVotesPerVoter: 0,
+        tags: [],
       },
     ];
 
@@ -242,6 +320,19 @@ describe("initialize_poll", () => {
       minusCredits: 0,
       startTs: new BN(now + 60),
       endTs: new BN(now + 600),
+      finalizeGrace: new BN(0),
+      minLabelLen: 1,
+      negRatio: 2,
+      quorum: 0,
+      minOptions: 2,
+      mode: { d21: {} },
+      commitEndTs: new BN(0),
+      revealEndTs: new BN(0),
+      allowlistRoot: null,
+      graceSecs: new BN(0),
+      voteCooldownSecs: new BN(0),
+      maxVotesPerVoter: 0,
+      tags: [],
     };
 
     const [pda] = PublicKey.findProgramAddressSync(
@@ -0,0 +1,36 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, expectAnchorErrCode, pollCfg, createPoll, addOption, optionPda,
+} from "./helpers";
+
+describe("label length in chars", () => {
+  const authority = Keypair.generate();
+  let poll: PublicKey;
+
+  before(async () => {
+    await airdrop(authority.publicKey);
+    poll = await createPoll(authority, pollCfg(4101, nowSec() + 3600, { minLabelLen: 3 }));
+  });
+
+  it("accepts a 20-emoji label", async () => {
+    const label = "🎉".repeat(20);
+    await addOption(authority, poll, 0, label);
+    expect((await program.account.optionNode.fetch(optionPda(poll, 0))).label).to.eq(label);
+  });
+
+  it("accepts 64 two-byte chars", async () => {
+    const label = "é".repeat(64);
+    await addOption(authority, poll, 1, label);
+    expect((await program.account.optionNode.fetch(optionPda(poll, 1))).label).to.eq(label);
+  });
+
+  it("rejects 65 multi-byte chars", async () => {
+    await expectAnchorErrCode(addOption(authority, poll, 2, "é".repeat(65)), "LabelTooLong");
+  });
+
+  it("counts chars for the minimum length too", async () => {
+    await expectAnchorErrCode(addOption(authority, poll, 2, "éé"), "LabelTooShort");
+    await addOption(authority, poll, 2, "ééé");
+  });
+});
@@ -0,0 +1,75 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, waitUntilChainTime, pollCfg, createPoll, beginPoll, addOption, castVote,
+} from "./helpers";
+
+describe("streaming leader", () => {
+  const authority = Keypair.generate();
+  const [a, b, c] = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+  let poll: PublicKey;
+
+  const leader = async () => {
+    const acc = await program.account.poll.fetch(poll);
+    return [acc.leaderIndex, acc.leaderNet.toNumber()];
+  };
+
+  before(async () => {
+    for (const k of [authority, a, b, c]) await airdrop(k.publicKey);
+
+    const start = nowSec() + 3;
+    poll = await createPoll(authority, pollCfg(1301, start));
+    await addOption(authority, poll, 0, "Alpha");
+    await addOption(authority, poll, 1, "Beta");
+    await addOption(authority, poll, 2, "Gamma");
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+  });
+
+  it("tracks the leader across a vote sequence", async () => {
+    await castVote(a, poll, 1, 1);
+    expect(await leader()).to.deep.eq([1, 1]);
+
+    // tie on net and plus: the lower index keeps the lead
+    await castVote(b, poll, 2, 1);
+    expect(await leader()).to.deep.eq([1, 1]);
+
+    await castVote(b, poll, 0, 1);
+    expect(await leader()).to.deep.eq([0, 1]);
+
+    await castVote(c, poll, 2, 1);
+    expect(await leader()).to.deep.eq([2, 2]);
+
+    await castVote(a, poll, 0, 1);
+    expect(await leader()).to.deep.eq([0, 2]);
+  });
+
+  it("only lowers the cached score on minus votes against the leader", async () => {
+    const voters = Array.from({ length: 6 }, () => Keypair.generate());
+    for (const k of voters) await airdrop(k.publicKey);
+    const [p, q, t, r, s, u] = voters;
+
+    const start = nowSec() + 3;
+    poll = await createPoll(authority, pollCfg(1302, start));
+    await addOption(authority, poll, 0, "Alpha");
+    await addOption(authority, poll, 1, "Beta");
+    await addOption(authority, poll, 2, "Gamma");
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+
+    for (const v of [p, q, t]) await castVote(v, poll, 0, 1);
+    expect(await leader()).to.deep.eq([0, 3]);
+
+    for (const v of [r, s]) {
+      await castVote(v, poll, 1, 1);
+      await castVote(v, poll, 2, 1);
+      await castVote(v, poll, 0, -1);
+    }
+    // Beta (net 2, plus 2) now truly leads, but only Alpha was re-scored
+    expect(await leader()).to.deep.eq([0, 1]);
+
+    // the next vote on another option corrects the cache
+    await castVote(u, poll, 2, 1);
+    expect(await leader()).to.deep.eq([2, 3]);
+  });
+});
@@ -0,0 +1,73 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode,
+  pollCfg, createPoll, beginPoll, addOption, castVote, optionPda, voterPda, receiptPda,
+} from "./helpers";
+
+describe("max_votes_per_voter", () => {
+  const authority = Keypair.generate();
+  const voter = Keypair.generate();
+  let poll: PublicKey;
+
+  const retract = (index: number) =>
+    program.methods
+      .retractVote(index)
+      .accountsPartial({
+        voterAuthority: voter.publicKey,
+        poll,
+        optionNode: optionPda(poll, index),
+        voter: voterPda(poll, voter.publicKey),
+        receipt: receiptPda(poll, index, voter.publicKey),
+      })
+      .signers([voter])
+      .rpc({ commitment: "confirmed" });
+
+  const changeVote = (index: number, sentiment: number) =>
+    program.methods
+      .changeVote(index, sentiment)
+      .accountsPartial({
+        voterAuthority: voter.publicKey,
+        poll,
+        optionNode: optionPda(poll, index),
+        voter: voterPda(poll, voter.publicKey),
+        receipt: receiptPda(poll, index, voter.publicKey),
+      })
+      .signers([voter])
+      .rpc({ commitment: "confirmed" });
+
+  const optionsVoted = async () =>
+    (await program.account.voter.fetch(voterPda(poll, voter.publicKey))).optionsVoted;
+
+  before(async () => {
+    for (const k of [authority, voter]) await airdrop(k.publicKey);
+
+    const start = nowSec() + 3;
+    poll = await createPoll(authority, pollCfg(6001, start, { maxVotesPerVoter: 2 }));
+    for (const [i, label] of ["Alpha", "Beta", "Gamma"].entries()) await addOption(authority, poll, i, label);
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+  });
+
+  it("counts abstentions toward the cap", async () => {
+    await castVote(voter, poll, 0, 1);
+    await castVote(voter, poll, 1, 0);
+    expect(await optionsVoted()).to.eq(2);
+  });
+
+  it("rejects a vote past the cap", async () => {
+    await expectAnchorErrCode(castVote(voter, poll, 2, 1), "TooManyVotes");
+  });
+
+  it("still allows changing a vote at the cap", async () => {
+    await changeVote(1, 1);
+    expect(await optionsVoted()).to.eq(2);
+  });
+
+  it("frees a slot on retract", async () => {
+    await retract(1);
+    expect(await optionsVoted()).to.eq(1);
+    await castVote(voter, poll, 2, 1);
+    expect(await optionsVoted()).to.eq(2);
+  });
+});
@@ -0,0 +1,37 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  program, airdrop, nowSec, expectAnchorErrCode, pollCfg, createPoll, addOption, optionPda,
+} from "./helpers";
+
+describe("min_label_len", () => {
+  const authority = Keypair.generate();
+  let poll: PublicKey;
+
+  before(async () => {
+    await airdrop(authority.publicKey);
+    poll = await createPoll(authority, pollCfg(1801, nowSec() + 60, { minLabelLen: 3 }));
+  });
+
+  it("rejects a minimum of 0 or above the maximum label length", async () => {
+    await expectAnchorErrCode(
+      createPoll(authority, pollCfg(1802, nowSec() + 60, { minLabelLen: 0 })),
+      "InvalidMinLabelLen"
+    );
+    await expectAnchorErrCode(
+      createPoll(authority, pollCfg(1803, nowSec() + 60, { minLabelLen: 65 })),
+      "InvalidMinLabelLen"
+    );
+  });
+
+  it("accepts a label exactly at the minimum after trimming", async () => {
+    await addOption(authority, poll, 0, "  Abc  ");
+    const option = await program.account.optionNode.fetch(optionPda(poll, 0));
+    expect(option.label).to.eq("Abc");
+    expect((await program.account.poll.fetch(poll)).minLabelLen).to.eq(3);
+  });
+
+  it("rejects a label one character below the minimum", async () => {
+    await expectAnchorErrCode(addOption(authority, poll, 1, " Ab "), "LabelTooShort");
+  });
+});
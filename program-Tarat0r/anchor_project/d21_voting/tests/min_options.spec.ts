@@ -0,0 +1,38 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode,
+  pollCfg, createPoll, beginPoll, addOption, castVote,
+} from "./helpers";
+
+describe("min_options", () => {
+  const authority = Keypair.generate();
+  const voter = Keypair.generate();
+  let single: PublicKey;
+  let full: PublicKey;
+
+  before(async () => {
+    for (const k of [authority, voter]) await airdrop(k.publicKey);
+
+    const start = nowSec() + 3;
+    single = await createPoll(authority, pollCfg(3801, start, { minOptions: 2 }));
+    full = await createPoll(authority, pollCfg(3802, start, { minOptions: 2 }));
+    await addOption(authority, single, 0, "Alpha");
+    await addOption(authority, full, 0, "Alpha");
+    await addOption(authority, full, 1, "Beta");
+    await waitUntilChainTime(start);
+    for (const p of [single, full]) await beginPoll(authority, p);
+  });
+
+  it("rejects votes on a single-option poll", async () => {
+    await expectAnchorErrCode(castVote(voter, single, 0, 1), "NotEnoughOptions");
+  });
+
+  it("accepts votes once the minimum is reached", async () => {
+    await castVote(voter, full, 0, 1);
+
+    const acc = await program.account.poll.fetch(full);
+    expect(acc.minOptions).to.eq(2);
+    expect(acc.totalVoters).to.eq(1);
+  });
+});
@@ -0,0 +1,64 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode, eventsOf,
+  pollCfg, createPoll, beginPoll, addOption, castVote, receiptPda,
+} from "./helpers";
+
+describe("my_receipts", () => {
+  const authority = Keypair.generate();
+  const voter = Keypair.generate();
+  const other = Keypair.generate();
+  let poll: PublicKey;
+
+  before(async () => {
+    await airdrop(authority.publicKey);
+    await airdrop(voter.publicKey);
+    await airdrop(other.publicKey);
+
+    const start = nowSec() + 3;
+    poll = await createPoll(authority, pollCfg(401, start));
+    await addOption(authority, poll, 0, "Alpha");
+    await addOption(authority, poll, 1, "Beta");
+    await addOption(authority, poll, 2, "Gamma");
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+
+    await castVote(voter, poll, 0, 1);
+    await castVote(voter, poll, 1, 1);
+    await castVote(voter, poll, 2, -1);
+    await castVote(other, poll, 0, 1);
+  });
+
+  it("emits all of a voter's receipts in one event", async () => {
+    const sig = await program.methods
+      .myReceipts()
+      .accountsPartial({ voterAuthority: voter.publicKey, poll })
+      .remainingAccounts([0, 1, 2].map((i) => ({
+        pubkey: receiptPda(poll, i, voter.publicKey), isSigner: false, isWritable: false,
+      })))
+      .signers([voter])
+      .rpc({ commitment: "confirmed" });
+
+    const ev = (await eventsOf(sig)).find((e) => e.name === "voterReceipts");
+    expect(ev, "VoterReceipts emitted").to.not.be.undefined;
+    expect(ev!.data.voter.toBase58()).to.eq(voter.publicKey.toBase58());
+    const entries = (ev!.data.entries as any[]).map((e) => [Number(e.optionIndex), Number(e.sentiment)]);
+    expect(entries).to.deep.eq([[0, 1], [1, 1], [2, -1]]);
+  });
+
+  it("rejects a receipt that belongs to another voter", async () => {
+    await expectAnchorErrCode(
+      program.methods
+        .myReceipts()
+        .accountsPartial({ voterAuthority: voter.publicKey, poll })
+        .remainingAccounts([
+          { pubkey: receiptPda(poll, 0, voter.publicKey), isSigner: false, isWritable: false },
+          { pubkey: receiptPda(poll, 0, other.publicKey), isSigner: false, isWritable: false },
+        ])
+        .signers([voter])
+        .rpc(),
+      "Unauthorized"
+    );
+  });
+});
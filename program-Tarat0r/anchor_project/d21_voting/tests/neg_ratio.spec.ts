@@ -0,0 +1,44 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode,
+  pollCfg, createPoll, beginPoll, addOption, castVote, optionPda,
+} from "./helpers";
+
+describe("neg_ratio", () => {
+  const authority = Keypair.generate();
+  const voter = Keypair.generate();
+  let poll: PublicKey;
+
+  before(async () => {
+    for (const k of [authority, voter]) await airdrop(k.publicKey);
+
+    const start = nowSec() + 3;
+    poll = await createPoll(authority, pollCfg(2601, start, { plusCredits: 3, negRatio: 3 }));
+    for (let i = 0; i < 4; i++) await addOption(authority, poll, i, `Option ${i}`);
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+  });
+
+  it("rejects a neg_ratio of 0", async () => {
+    await expectAnchorErrCode(
+      createPoll(authority, pollCfg(2602, nowSec() + 60, { negRatio: 0 })),
+      "InvalidNegRatio"
+    );
+  });
+
+  it("stores the configured ratio", async () => {
+    expect((await program.account.poll.fetch(poll)).negRatio).to.eq(3);
+  });
+
+  it("requires three positives before one negative", async () => {
+    await castVote(voter, poll, 0, 1);
+    await castVote(voter, poll, 1, 1);
+    await expectAnchorErrCode(castVote(voter, poll, 3, -1), "InsufficientPositivesForNegative");
+
+    await castVote(voter, poll, 2, 1);
+    await castVote(voter, poll, 3, -1);
+    const o = await program.account.optionNode.fetch(optionPda(poll, 3));
+    expect(o.minusVotes).to.eq(1);
+  });
+});
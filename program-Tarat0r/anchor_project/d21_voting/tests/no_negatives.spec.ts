@@ -0,0 +1,40 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode,
+  pollCfg, createPoll, beginPoll, addOption, castVote, voterPda,
+} from "./helpers";
+
+describe("minus_credits = 0", () => {
+  const authority = Keypair.generate();
+  const voter = Keypair.generate();
+  let poll: PublicKey;
+
+  before(async () => {
+    for (const k of [authority, voter]) await airdrop(k.publicKey);
+
+    const start = nowSec() + 3;
+    poll = await createPoll(authority, pollCfg(2701, start, { minusCredits: 0 }));
+    for (let i = 0; i < 3; i++) await addOption(authority, poll, i, `Option ${i}`);
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+  });
+
+  it("accepts a poll without negative credits", async () => {
+    expect((await program.account.poll.fetch(poll)).minusCredits).to.eq(0);
+  });
+
+  it("rejects a negative vote before the ratio gate is met", async () => {
+    await expectAnchorErrCode(castVote(voter, poll, 0, -1), "OutOfNegativeCredits");
+  });
+
+  it("rejects a negative vote once the ratio gate is met", async () => {
+    await castVote(voter, poll, 0, 1);
+    await castVote(voter, poll, 1, 1);
+    await expectAnchorErrCode(castVote(voter, poll, 2, -1), "OutOfNegativeCredits");
+
+    const v = await program.account.voter.fetch(voterPda(poll, voter.publicKey));
+    expect(v.usedPlus).to.eq(2);
+    expect(v.usedMinus).to.eq(0);
+  });
+});
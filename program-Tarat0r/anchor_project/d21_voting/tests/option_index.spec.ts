@@ -0,0 +1,24 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, expectAnchorErrCode, pollCfg, createPoll, addOption,
+} from "./helpers";
+
+describe("option index order", () => {
+  const authority = Keypair.generate();
+  let poll: PublicKey;
+
+  before(async () => {
+    await airdrop(authority.publicKey);
+    poll = await createPoll(authority, pollCfg(3701, nowSec() + 3600));
+  });
+
+  it("accepts sequential indices and keeps an exact count", async () => {
+    for (let i = 0; i < 3; i++) await addOption(authority, poll, i, `Option ${i}`);
+    expect((await program.account.poll.fetch(poll)).optionsCount).to.eq(3);
+  });
+
+  it("rejects an index that skips ahead", async () => {
+    await expectAnchorErrCode(addOption(authority, poll, 4, "Skipped"), "NonContiguousOptionIndex");
+  });
+});
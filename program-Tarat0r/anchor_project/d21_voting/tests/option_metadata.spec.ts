@@ -0,0 +1,46 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, expectAnchorErrCode, pollCfg, createPoll, addOption, optionPda,
+} from "./helpers";
+
+const MAX_OPTION_DESC = 256;
+const MAX_URI = 200;
+
+describe("option description and image uri", () => {
+  const authority = Keypair.generate();
+  let poll: PublicKey;
+
+  before(async () => {
+    await airdrop(authority.publicKey);
+    poll = await createPoll(authority, pollCfg(4301, nowSec() + 3600));
+  });
+
+  it("stores description and image uri", async () => {
+    const uri = "https://example.com/alice.png";
+    await addOption(authority, poll, 0, "Alice", null, "Long-time organizer", uri);
+    const opt = await program.account.optionNode.fetch(optionPda(poll, 0));
+    expect(opt.description).to.eq("Long-time organizer");
+    expect(opt.imageUri).to.eq(uri);
+  });
+
+  it("allows an empty image uri", async () => {
+    await addOption(authority, poll, 1, "Bob", null, "No picture", "");
+    const opt = await program.account.optionNode.fetch(optionPda(poll, 1));
+    expect(opt.imageUri).to.eq("");
+  });
+
+  it("rejects a description over MAX_OPTION_DESC", async () => {
+    await expectAnchorErrCode(
+      addOption(authority, poll, 2, "Carol", null, "d".repeat(MAX_OPTION_DESC + 1)),
+      "OptionDescriptionTooLong"
+    );
+  });
+
+  it("rejects an image uri over MAX_URI", async () => {
+    await expectAnchorErrCode(
+      addOption(authority, poll, 2, "Carol", null, "", "u".repeat(MAX_URI + 1)),
+      "ImageUriTooLong"
+    );
+  });
+});
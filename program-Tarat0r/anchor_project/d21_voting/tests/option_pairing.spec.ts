@@ -0,0 +1,45 @@
+import { expect } from "chai";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode,
+  pollCfg, createPoll, beginPoll, addOption, optionPda, voterPda, receiptPda,
+} from "./helpers";
+
+describe("option / index pairing", () => {
+  const authority = Keypair.generate();
+  const voter = Keypair.generate();
+  let poll: PublicKey;
+
+  before(async () => {
+    for (const k of [authority, voter]) await airdrop(k.publicKey);
+
+    const start = nowSec() + 3;
+    poll = await createPoll(authority, pollCfg(5901, start));
+    await addOption(authority, poll, 0, "Alpha");
+    await addOption(authority, poll, 1, "Beta");
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+  });
+
+  it("rejects a vote whose option account does not match the index", async () => {
+    const vote = program.methods
+      .castVote(0, 1, null, [])
+      .accountsPartial({
+        payer: voter.publicKey,
+        voterAuthority: voter.publicKey,
+        poll,
+        optionNode: optionPda(poll, 1),
+        voter: voterPda(poll, voter.publicKey),
+        receipt: receiptPda(poll, 0, voter.publicKey),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc({ commitment: "confirmed" });
+    await expectAnchorErrCode(vote, "ConstraintSeeds");
+
+    for (const i of [0, 1]) {
+      expect((await program.account.optionNode.fetch(optionPda(poll, i))).plusVotes).to.eq(0);
+    }
+    expect(await program.provider.connection.getAccountInfo(receiptPda(poll, 0, voter.publicKey))).to.eq(null);
+  });
+});
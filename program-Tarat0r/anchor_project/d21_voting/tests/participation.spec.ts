@@ -0,0 +1,151 @@
+import { expect } from "chai";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode, eventsOf,
+  pollCfg, createPoll, beginPoll, addOption, castVote, allowedVoterPda,
+  optionPda, voterPda, receiptPda,
+} from "./helpers";
+
+describe("participation_rate", () => {
+  const authority = Keypair.generate();
+  const outsider = Keypair.generate();
+  const voters = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+  let poll: PublicKey;
+  let emptyPoll: PublicKey;
+  let merklePoll: PublicKey;
+
+  const allow = (signer: Keypair, target: PublicKey, voter: PublicKey) =>
+    program.methods
+      .addAllowedVoter(voter)
+      .accountsPartial({
+        signer: signer.publicKey,
+        poll: target,
+        admins: null,
+        allowedVoter: allowedVoterPda(target, voter),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc({ commitment: "confirmed" });
+  const participation = async (target: PublicKey) => {
+    const sig = await program.methods
+      .participationRate()
+      .accountsPartial({ poll: target })
+      .rpc({ commitment: "confirmed" });
+    const ev = (await eventsOf(sig)).find((e) => e.name === "participation");
+    expect(ev, "Participation not emitted").to.exist;
+    return ev!.data;
+  };
+  const precheck = async (voter: Keypair, allowedVoter: PublicKey | null) => {
+    const sig = await program.methods
+      .precheckVote(0, 1, [])
+      .accountsPartial({
+        voterAuthority: voter.publicKey,
+        poll,
+        allowedVoter,
+        optionNode: optionPda(poll, 0),
+        voter: voterPda(poll, voter.publicKey),
+        receipt: receiptPda(poll, 0, voter.publicKey),
+      })
+      .rpc({ commitment: "confirmed" });
+    const ev = (await eventsOf(sig)).find((e) => e.name === "votePrecheck");
+    expect(ev, "VotePrecheck not emitted").to.exist;
+    return ev!.data;
+  };
+  const voteAllowed = (voter: Keypair, index: number) =>
+    castVote(voter, poll, index, 1, null, voter, [], allowedVoterPda(poll, voter.publicKey));
+
+  before(async () => {
+    for (const k of [authority, outsider, ...voters]) await airdrop(k.publicKey);
+
+    const start = nowSec() + 3;
+    poll = await createPoll(authority, pollCfg(1501, start));
+    emptyPoll = await createPoll(authority, pollCfg(1502, start));
+    merklePoll = await createPoll(authority, pollCfg(1503, start, { allowlistRoot: Array(32).fill(7) }));
+    await addOption(authority, poll, 0, "Alpha");
+    await addOption(authority, poll, 1, "Beta");
+    for (const v of voters) await allow(authority, poll, v.publicKey);
+    await waitUntilChainTime(start);
+    for (const p of [poll, emptyPoll]) await beginPoll(authority, p);
+  });
+
+  it("tracks the allowlist size", async () => {
+    const acc = await program.account.poll.fetch(poll);
+    expect(acc.allowedCount).to.eq(3);
+    expect(acc.totalVoters).to.eq(0);
+  });
+
+  it("counts each voter once and reports the rate in bps", async () => {
+    await voteAllowed(voters[0], 0);
+    await voteAllowed(voters[0], 1);
+    await voteAllowed(voters[1], 0);
+
+    const data = await participation(poll);
+    expect(data.voted).to.eq(2);
+    expect(data.allowed).to.eq(3);
+    expect(data.rateBps.toNumber()).to.eq(6666);
+  });
+
+  it("reports 0 when the allowlist is empty", async () => {
+    const data = await participation(emptyPoll);
+    expect(data.allowed).to.eq(0);
+    expect(data.rateBps.toNumber()).to.eq(0);
+  });
+
+  it("rejects voters without an allowlist entry", async () => {
+    await expectAnchorErrCode(castVote(outsider, poll, 0, 1), "NotOnAllowlist");
+    // another voter's entry does not count
+    await expectAnchorErrCode(
+      castVote(outsider, poll, 0, 1, null, outsider, [], allowedVoterPda(poll, voters[2].publicKey)),
+      "NotOnAllowlist"
+    );
+
+    const batch = program.methods
+      .castVotesBatch([{ index: 1, sentiment: 1 }], [])
+      .accountsPartial({
+        payer: outsider.publicKey,
+        voterAuthority: outsider.publicKey,
+        poll,
+        voter: voterPda(poll, outsider.publicKey),
+        allowedVoter: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts([
+        { pubkey: optionPda(poll, 1), isSigner: false, isWritable: true },
+        { pubkey: receiptPda(poll, 1, outsider.publicKey), isSigner: false, isWritable: true },
+      ])
+      .signers([outsider])
+      .rpc({ commitment: "confirmed" });
+    await expectAnchorErrCode(batch, "NotOnAllowlist");
+
+    const data = await participation(poll);
+    expect(data.voted).to.eq(2);
+  });
+
+  it("precheck_vote reports voters without an allowlist entry", async () => {
+    expect((await precheck(outsider, null)).reason).to.eq(14);
+    expect((await precheck(outsider, allowedVoterPda(poll, voters[2].publicKey))).reason).to.eq(14);
+
+    const listed = await precheck(voters[2], allowedVoterPda(poll, voters[2].publicKey));
+    expect(listed.allowed).to.eq(true);
+    expect(listed.reason).to.eq(0);
+  });
+
+  it("keeps the rate within 10000 bps once everyone voted", async () => {
+    await voteAllowed(voters[2], 0);
+    const data = await participation(poll);
+    expect(data.voted).to.eq(3);
+    expect(data.rateBps.toNumber()).to.eq(10_000);
+  });
+
+  it("rejects allowlisting by a non-admin", async () => {
+    await expectAnchorErrCode(allow(outsider, poll, outsider.publicKey), "InsufficientRole");
+  });
+
+  it("rejects allowlisting once the poll is active", async () => {
+    await expectAnchorErrCode(allow(authority, emptyPoll, outsider.publicKey), "PollAlreadyActive");
+  });
+
+  it("rejects allowlist entries on a merkle-gated poll", async () => {
+    await expectAnchorErrCode(allow(authority, merklePoll, outsider.publicKey), "AllowlistRootSet");
+  });
+});
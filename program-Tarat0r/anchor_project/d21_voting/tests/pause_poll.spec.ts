@@ -0,0 +1,60 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode,
+  pollCfg, createPoll, beginPoll, addOption, castVote, optionPda,
+} from "./helpers";
+
+describe("pause_poll / resume_poll", () => {
+  const authority = Keypair.generate();
+  const outsider = Keypair.generate();
+  const voter = Keypair.generate();
+  let poll: PublicKey;
+
+  const pause = (signer: Keypair) =>
+    program.methods
+      .pausePoll()
+      .accountsPartial({ authority: signer.publicKey, poll })
+      .signers([signer])
+      .rpc({ commitment: "confirmed" });
+
+  const resume = (signer: Keypair) =>
+    program.methods
+      .resumePoll()
+      .accountsPartial({ authority: signer.publicKey, poll })
+      .signers([signer])
+      .rpc({ commitment: "confirmed" });
+
+  before(async () => {
+    for (const k of [authority, outsider, voter]) await airdrop(k.publicKey);
+
+    const start = nowSec() + 3;
+    poll = await createPoll(authority, pollCfg(5701, start));
+    await addOption(authority, poll, 0, "Alpha");
+    await addOption(authority, poll, 1, "Beta");
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+  });
+
+  it("only lets the authority pause", async () => {
+    await expectAnchorErrCode(pause(outsider), "Unauthorized");
+  });
+
+  it("rejects votes while paused", async () => {
+    await pause(authority);
+    expect((await program.account.poll.fetch(poll)).paused).to.eq(true);
+    await expectAnchorErrCode(castVote(voter, poll, 0, 1), "PollPaused");
+    await expectAnchorErrCode(pause(authority), "PollPaused");
+  });
+
+  it("accepts votes again after resume", async () => {
+    await expectAnchorErrCode(resume(outsider), "Unauthorized");
+    await resume(authority);
+    await castVote(voter, poll, 0, 1);
+    expect((await program.account.optionNode.fetch(optionPda(poll, 0))).plusVotes).to.eq(1);
+  });
+
+  it("rejects resuming a poll that is not paused", async () => {
+    await expectAnchorErrCode(resume(authority), "PollNotPaused");
+  });
+});
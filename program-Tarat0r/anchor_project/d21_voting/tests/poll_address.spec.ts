@@ -0,0 +1,56 @@
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode,
+  pollCfg, createPoll, addOption, optionPda, voterPda, receiptPda, labelSeed, labelGuardPda,
+} from "./helpers";
+
+describe("poll address checks", () => {
+  const authority = Keypair.generate();
+  const voter = Keypair.generate();
+  let poll: PublicKey;
+  let otherPoll: PublicKey;
+
+  before(async () => {
+    for (const k of [authority, voter]) await airdrop(k.publicKey);
+
+    const start = nowSec() + 4;
+    poll = await createPoll(authority, pollCfg(3601, start));
+    otherPoll = await createPoll(authority, pollCfg(3602, start));
+    await addOption(authority, poll, 0, "Alpha");
+    await waitUntilChainTime(start);
+  });
+
+  it("rejects a vote that pairs one poll with another poll's accounts", async () => {
+    const vote = program.methods
+      .castVote(0, 1, null, [])
+      .accountsPartial({
+        payer: voter.publicKey,
+        voterAuthority: voter.publicKey,
+        poll: otherPoll,
+        optionNode: optionPda(poll, 0),
+        voter: voterPda(poll, voter.publicKey),
+        receipt: receiptPda(poll, 0, voter.publicKey),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc({ commitment: "confirmed" });
+    await expectAnchorErrCode(vote, "ConstraintSeeds");
+  });
+
+  it("rejects an option created under another poll's address", async () => {
+    const seed = labelSeed("Beta");
+    const add = program.methods
+      .addOption(1, "Beta", [...seed], "", "")
+      .accountsPartial({
+        authority: authority.publicKey,
+        poll: otherPoll,
+        admins: null,
+        optionNode: optionPda(poll, 1),
+        labelGuard: labelGuardPda(otherPoll, seed),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc({ commitment: "confirmed" });
+    await expectAnchorErrCode(add, "ConstraintSeeds");
+  });
+});
@@ -0,0 +1,40 @@
+import { expect } from "chai";
+import { Keypair } from "@solana/web3.js";
+import { program, airdrop, nowSec, pollCfg, pollPda, createPoll } from "./helpers";
+
+describe("PollInitialized event", () => {
+  const authority = Keypair.generate();
+
+  before(async () => {
+    await airdrop(authority.publicKey);
+  });
+
+  it("reports the poll config", async () => {
+    const cfg = pollCfg(5101, nowSec() + 60, { plusCredits: 4, minusCredits: 2, tags: ["budget"] });
+    const poll = pollPda(authority.publicKey, cfg.pollId);
+
+    let listener: number | undefined;
+    const received = new Promise<any>((resolve) => {
+      listener = program.addEventListener("pollInitialized", (ev) => {
+        if (ev.poll.equals(poll)) resolve(ev);
+      });
+    });
+    try {
+      await createPoll(authority, cfg);
+      const ev = await received;
+      expect(ev.authority.toBase58()).to.eq(authority.publicKey.toBase58());
+      expect(ev.pollId.toString()).to.eq(cfg.pollId.toString());
+      expect(ev.startTs.toString()).to.eq(cfg.startTs.toString());
+      expect(ev.endTs.toString()).to.eq(cfg.endTs.toString());
+      expect(ev.plusCredits).to.eq(4);
+      expect(ev.minusCredits).to.eq(2);
+      expect(ev.tags).to.deep.eq(["budget"]);
+      const acc = await program.account.poll.fetch(poll);
+      expect(ev.createdTs.toString()).to.eq(acc.createdTs.toString());
+      expect(acc.createdTs.toNumber()).to.be.gt(0);
+      expect(acc.createdTs.toNumber()).to.be.lte(cfg.startTs.toNumber());
+    } finally {
+      if (listener !== undefined) await program.removeEventListener(listener);
+    }
+  });
+});
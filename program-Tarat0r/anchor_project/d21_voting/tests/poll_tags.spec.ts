@@ -0,0 +1,108 @@
+import { expect } from "chai";
+import { Keypair, PublicKey, SystemProgram, Transaction } from "@solana/web3.js";
+import {
+  program, provider, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode, pollCfg, createPoll, pollPda, tagIndexPda,
+} from "./helpers";
+
+describe("poll_tags", () => {
+  const authority = Keypair.generate();
+  // unique per run so the global tag counters start from zero
+  const tag = `dao-${Date.now() % 1_000_000}`;
+  const other = `ops-${Date.now() % 1_000_000}`;
+  const funded = `gift-${Date.now() % 1_000_000}`;
+
+  // index accounts: dropped tags first, then added ones
+  const setTags = (poll: PublicKey, tags: string[], indexTags: string[]) =>
+    program.methods
+      .setTags(tags)
+      .accountsPartial({ authority: authority.publicKey, poll, systemProgram: SystemProgram.programId })
+      .remainingAccounts(indexTags.map((t) => ({ pubkey: tagIndexPda(t), isSigner: false, isWritable: true })))
+      .signers([authority])
+      .rpc({ commitment: "confirmed" });
+  const pollCount = async (t: string) =>
+    (await program.account.tagIndex.fetch(tagIndexPda(t))).pollCount.toNumber();
+
+  before(async () => {
+    await airdrop(authority.publicKey);
+  });
+
+  it("stores tags and increments the tag index per poll", async () => {
+    const poll = await createPoll(authority, pollCfg(501, nowSec() + 60, { tags: [tag, "budget"] }));
+
+    const acct = await program.account.poll.fetch(poll);
+    expect(acct.tags).to.deep.eq([tag, "budget"]);
+    let index = await program.account.tagIndex.fetch(tagIndexPda(tag));
+    expect(index.tag).to.eq(tag);
+    expect(index.pollCount.toNumber()).to.eq(1);
+
+    await createPoll(authority, pollCfg(502, nowSec() + 60, { tags: [tag] }));
+    index = await program.account.tagIndex.fetch(tagIndexPda(tag));
+    expect(index.pollCount.toNumber()).to.eq(2);
+  });
+
+  it("creates a tag index whose address was funded beforehand", async () => {
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        SystemProgram.transfer({ fromPubkey: authority.publicKey, toPubkey: tagIndexPda(funded), lamports: 1_000 })
+      ),
+      [authority]
+    );
+
+    await createPoll(authority, pollCfg(506, nowSec() + 60, { tags: [funded] }));
+    expect(await pollCount(funded)).to.eq(1);
+    const info = await provider.connection.getAccountInfo(tagIndexPda(funded));
+    expect(info!.owner.toBase58()).to.eq(program.programId.toBase58());
+  });
+
+  it("accepts exactly MAX_TAGS tags and rejects one more", async () => {
+    await createPoll(authority, pollCfg(503, nowSec() + 60, { tags: ["a", "b", "c"] }));
+    await expectAnchorErrCode(
+      createPoll(authority, pollCfg(504, nowSec() + 60, { tags: ["a", "b", "c", "d"] })),
+      "TooManyTags"
+    );
+  });
+
+  it("rejects a tag longer than MAX_TAG_LEN", async () => {
+    // a 33-byte tag can't be a PDA seed, so send it without its index account;
+    // the length check runs before the index accounts are inspected
+    const cfg = pollCfg(505, nowSec() + 60, { tags: ["x".repeat(33)] });
+    await expectAnchorErrCode(
+      program.methods
+        .initializePoll(cfg)
+        .accountsPartial({
+          payer: authority.publicKey,
+          authority: authority.publicKey,
+          poll: pollPda(authority.publicKey, cfg.pollId),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc(),
+      "TagTooLong"
+    );
+  });
+
+  it("set_tags swaps tags and keeps the tag indexes in step", async () => {
+    const poll = await createPoll(authority, pollCfg(6101, nowSec() + 60, { tags: [tag] }));
+    const before = await pollCount(tag);
+
+    await setTags(poll, [other, "budget"], [tag, other]);
+    expect((await program.account.poll.fetch(poll)).tags).to.deep.eq([other, "budget"]);
+    expect(await pollCount(tag)).to.eq(before - 1);
+    expect(await pollCount(other)).to.eq(1);
+
+    await expectAnchorErrCode(setTags(poll, [other], []), "TagIndexMismatch");
+  });
+
+  it("set_tags accepts exactly MAX_TAGS tags and rejects one more", async () => {
+    const poll = await createPoll(authority, pollCfg(6102, nowSec() + 60));
+    await setTags(poll, ["a", "b", "c"], ["a", "b", "c"]);
+    await expectAnchorErrCode(setTags(poll, ["a", "b", "c", "d"], ["d"]), "TooManyTags");
+  });
+
+  it("set_tags is rejected once voting has started", async () => {
+    const start = nowSec() + 2;
+    const poll = await createPoll(authority, pollCfg(6103, start));
+    await waitUntilChainTime(start);
+    await expectAnchorErrCode(setTags(poll, ["a"], ["a"]), "VotingStarted");
+  });
+});
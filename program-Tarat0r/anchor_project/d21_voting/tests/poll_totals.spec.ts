@@ -0,0 +1,61 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, waitUntilChainTime,
+  pollCfg, createPoll, beginPoll, addOption, castVote, optionPda, voterPda, receiptPda,
+} from "./helpers";
+
+describe("poll totals", () => {
+  const authority = Keypair.generate();
+  const voters = [Keypair.generate(), Keypair.generate()];
+  let poll: PublicKey;
+
+  const accounts = (who: Keypair, index: number) => ({
+    voterAuthority: who.publicKey,
+    poll,
+    optionNode: optionPda(poll, index),
+    voter: voterPda(poll, who.publicKey),
+    receipt: receiptPda(poll, index, who.publicKey),
+  });
+
+  const expectTotalsMatchOptions = async () => {
+    const acc = await program.account.poll.fetch(poll);
+    const options = await Promise.all([0, 1, 2].map((i) => program.account.optionNode.fetch(optionPda(poll, i))));
+    expect(acc.totalPlus.toNumber()).to.eq(options.reduce((n, o) => n + o.plusVotes, 0));
+    expect(acc.totalMinus.toNumber()).to.eq(options.reduce((n, o) => n + o.minusVotes, 0));
+  };
+
+  before(async () => {
+    await airdrop(authority.publicKey);
+    for (const v of voters) await airdrop(v.publicKey);
+
+    const start = nowSec() + 3;
+    poll = await createPoll(authority, pollCfg(6301, start));
+    for (const [i, label] of ["Alpha", "Beta", "Gamma"].entries()) await addOption(authority, poll, i, label);
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+  });
+
+  it("tracks plus and minus votes across options", async () => {
+    await castVote(voters[0], poll, 0, 1);
+    await castVote(voters[0], poll, 1, 1);
+    await castVote(voters[0], poll, 2, -1);
+    await castVote(voters[1], poll, 2, 1);
+    await castVote(voters[1], poll, 1, 0);
+    await expectTotalsMatchOptions();
+    const acc = await program.account.poll.fetch(poll);
+    expect([acc.totalPlus.toNumber(), acc.totalMinus.toNumber()]).to.deep.eq([3, 1]);
+  });
+
+  it("follows changed and retracted votes", async () => {
+    await program.methods.changeVote(1, 1).accountsPartial(accounts(voters[1], 1))
+      .signers([voters[1]]).rpc({ commitment: "confirmed" });
+    await expectTotalsMatchOptions();
+
+    await program.methods.retractVote(2).accountsPartial(accounts(voters[0], 2))
+      .signers([voters[0]]).rpc({ commitment: "confirmed" });
+    await expectTotalsMatchOptions();
+    const acc = await program.account.poll.fetch(poll);
+    expect([acc.totalPlus.toNumber(), acc.totalMinus.toNumber()]).to.deep.eq([4, 0]);
+  });
+});
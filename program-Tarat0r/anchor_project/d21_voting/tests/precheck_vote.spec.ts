@@ -0,0 +1,80 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
+import {
+  program, provider, airdrop, nowSec, waitUntilChainTime, eventsOf,
+  pollCfg, createPoll, beginPoll, addOption, castVote, optionPda, voterPda, receiptPda,
+} from "./helpers";
+
+describe("precheck_vote", () => {
+  const authority = Keypair.generate();
+  const voter = Keypair.generate();
+  let poll: PublicKey;
+  let laterPoll: PublicKey;
+  let commitPoll: PublicKey;
+
+  const precheck = async (target: PublicKey, index: number, sentiment: number) => {
+    const sig = await program.methods
+      .precheckVote(index, sentiment, [])
+      .accountsPartial({
+        voterAuthority: voter.publicKey,
+        poll: target,
+        optionNode: optionPda(target, index),
+        voter: voterPda(target, voter.publicKey),
+        receipt: receiptPda(target, index, voter.publicKey),
+      })
+      .rpc({ commitment: "confirmed" });
+    const ev = (await eventsOf(sig)).find((e) => e.name === "votePrecheck");
+    expect(ev, "VotePrecheck not emitted").to.exist;
+    return ev!.data;
+  };
+
+  before(async () => {
+    for (const k of [authority, voter]) await airdrop(k.publicKey);
+
+    const start = nowSec() + 3;
+    poll = await createPoll(authority, pollCfg(2001, start));
+    laterPoll = await createPoll(authority, pollCfg(2002, start + 600));
+    commitPoll = await createPoll(authority, pollCfg(2003, start, {
+      commitEndTs: new BN(start + 600),
+      revealEndTs: new BN(start + 1200),
+    }));
+    for (let i = 0; i < 3; i++) await addOption(authority, poll, i, `Option ${i}`);
+    await addOption(authority, laterPoll, 0, "Alpha");
+    for (let i = 0; i < 2; i++) await addOption(authority, commitPoll, i, `Option ${i}`);
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+    await beginPoll(authority, commitPoll);
+  });
+
+  it("allows a first vote without creating any accounts", async () => {
+    const data = await precheck(poll, 0, 1);
+    expect(data.allowed).to.eq(true);
+    expect(data.reason).to.eq(0);
+
+    const info = await provider.connection.getAccountInfo(voterPda(poll, voter.publicKey));
+    expect(info).to.be.null;
+  });
+
+  it("reports the window, sentiment and ratio gate", async () => {
+    expect((await precheck(laterPoll, 0, 1)).reason).to.eq(1);
+    expect((await precheck(poll, 0, 2)).reason).to.eq(3);
+    expect((await precheck(poll, 0, -1)).reason).to.eq(7);
+  });
+
+  it("rejects direct votes on a commit-reveal poll", async () => {
+    const data = await precheck(commitPoll, 0, 1);
+    expect(data.allowed).to.eq(false);
+    expect(data.reason).to.eq(13);
+  });
+
+  it("tracks the real vote path", async () => {
+    await castVote(voter, poll, 0, 1);
+    const again = await precheck(poll, 0, 1);
+    expect(again.allowed).to.eq(false);
+    expect(again.reason).to.eq(4);
+
+    await castVote(voter, poll, 1, 1);
+    expect((await precheck(poll, 2, -1)).allowed).to.eq(true);
+  });
+});
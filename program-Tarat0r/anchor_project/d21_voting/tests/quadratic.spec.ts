@@ -0,0 +1,89 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode, eventsOf,
+  pollCfg, createPoll, beginPoll, addOption, castVote, optionPda, voterPda, receiptPda,
+} from "./helpers";
+
+describe("quadratic voting", () => {
+  const authority = Keypair.generate();
+  const voter = Keypair.generate();
+  let poll: PublicKey;
+
+  const retract = (index: number) =>
+    program.methods
+      .retractVote(index)
+      .accountsPartial({
+        voterAuthority: voter.publicKey,
+        poll,
+        optionNode: optionPda(poll, index),
+        voter: voterPda(poll, voter.publicKey),
+        receipt: receiptPda(poll, index, voter.publicKey),
+      })
+      .signers([voter])
+      .rpc({ commitment: "confirmed" });
+
+  const precheck = async (index: number) => {
+    const sig = await program.methods
+      .precheckVote(index, 1, [])
+      .accountsPartial({
+        voterAuthority: voter.publicKey,
+        poll,
+        optionNode: optionPda(poll, index),
+        voter: voterPda(poll, voter.publicKey),
+        receipt: receiptPda(poll, index, voter.publicKey),
+      })
+      .rpc({ commitment: "confirmed" });
+    const ev = (await eventsOf(sig)).find((e) => e.name === "votePrecheck");
+    expect(ev, "VotePrecheck not emitted").to.exist;
+    return ev!.data;
+  };
+
+  before(async () => {
+    for (const k of [authority, voter]) await airdrop(k.publicKey);
+
+    const start = nowSec() + 3;
+    poll = await createPoll(authority, pollCfg(4501, start, { plusCredits: 30, mode: { quadratic: {} } }));
+    await addOption(authority, poll, 0, "Alpha");
+    await addOption(authority, poll, 1, "Beta");
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+  });
+
+  it("charges 1, 4, 9, 16 credits for successive votes on one option", async () => {
+    let remaining = 30;
+    for (const weight of [1, 2, 3, 4]) {
+      await castVote(voter, poll, 0, 1);
+      remaining -= weight * weight;
+      const v = await program.account.voter.fetch(voterPda(poll, voter.publicKey));
+      expect(v.creditsRemaining).to.eq(remaining);
+      const r = await program.account.receipt.fetch(receiptPda(poll, 0, voter.publicKey));
+      expect(r.weight).to.eq(weight);
+    }
+    expect((await program.account.optionNode.fetch(optionPda(poll, 0))).plusVotes).to.eq(4);
+  });
+
+  it("rejects a vote once the budget is exhausted", async () => {
+    await expectAnchorErrCode(castVote(voter, poll, 0, 1), "OutOfPositiveCredits");
+    await expectAnchorErrCode(castVote(voter, poll, 1, 1), "OutOfPositiveCredits");
+  });
+
+  it("precheck_vote reports a vote the budget can't cover", async () => {
+    for (const index of [0, 1]) {
+      const data = await precheck(index);
+      expect(data.allowed).to.eq(false);
+      expect(data.reason).to.eq(5);
+    }
+  });
+
+  it("only accepts positive votes", async () => {
+    await expectAnchorErrCode(castVote(voter, poll, 1, -1), "InvalidSentiment");
+  });
+
+  it("refunds the full cost on retract", async () => {
+    await retract(0);
+    const v = await program.account.voter.fetch(voterPda(poll, voter.publicKey));
+    expect(v.creditsRemaining).to.eq(30);
+    expect((await program.account.optionNode.fetch(optionPda(poll, 0))).plusVotes).to.eq(0);
+  });
+});
@@ -0,0 +1,71 @@
+import { expect } from "chai";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import BN from "bn.js";
+import {
+  program, provider, airdrop, nowSec, waitUntilChainTime, eventsOf,
+  pollCfg, createPoll, beginPoll, addOption, castVote, optionPda, snapshotPda,
+} from "./helpers";
+
+describe("quorum", () => {
+  const authority = Keypair.generate();
+  const voters = [Keypair.generate(), Keypair.generate()];
+  let missed: PublicKey;
+  let met: PublicKey;
+  let end: number;
+
+  const finalize = (poll: PublicKey) =>
+    program.methods
+      .finalizePoll()
+      .accountsPartial({
+        payer: provider.wallet.publicKey,
+        poll,
+        snapshot: snapshotPda(poll),
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts([0, 1].map((i) => ({
+        pubkey: optionPda(poll, i), isSigner: false, isWritable: false,
+      })))
+      .rpc({ commitment: "confirmed" });
+
+  before(async () => {
+    await airdrop(authority.publicKey);
+    for (const v of voters) await airdrop(v.publicKey);
+
+    const start = nowSec() + 3;
+    end = start + 8;
+    missed = await createPoll(authority, pollCfg(3201, start, { endTs: new BN(end), quorum: 3 }));
+    met = await createPoll(authority, pollCfg(3202, start, { endTs: new BN(end), quorum: 2 }));
+    for (const poll of [missed, met]) {
+      await addOption(authority, poll, 0, "Alpha");
+      await addOption(authority, poll, 1, "Beta");
+    }
+    await waitUntilChainTime(start);
+    for (const p of [missed, met]) await beginPoll(authority, p);
+
+    for (const poll of [missed, met]) {
+      await castVote(voters[0], poll, 0, 1);
+      await castVote(voters[0], poll, 1, 1);
+      await castVote(voters[1], poll, 0, 1);
+    }
+  });
+
+  it("counts distinct voters only", async () => {
+    expect((await program.account.poll.fetch(missed)).totalVoters).to.eq(2);
+  });
+
+  it("reports a missed quorum instead of failing", async () => {
+    await waitUntilChainTime(end + 1);
+    const ev = (await eventsOf(await finalize(missed))).find((e) => e.name === "pollFinalized");
+    expect(ev, "PollFinalized not emitted").to.exist;
+    expect(ev!.data.quorumMet).to.eq(false);
+
+    const acc = await program.account.poll.fetch(missed);
+    expect(acc.quorumMet).to.eq(false);
+    expect(acc.winnerIndex).to.eq(0);
+  });
+
+  it("reports a met quorum", async () => {
+    await finalize(met);
+    expect((await program.account.poll.fetch(met)).quorumMet).to.eq(true);
+  });
+});
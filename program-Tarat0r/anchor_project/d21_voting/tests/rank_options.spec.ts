@@ -0,0 +1,56 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode, eventsOf,
+  pollCfg, createPoll, beginPoll, addOption, castVote, optionPda,
+} from "./helpers";
+
+describe("rank_options", () => {
+  const authority = Keypair.generate();
+  const voters = [Keypair.generate(), Keypair.generate()];
+  let poll: PublicKey;
+
+  const rankOptions = (order: number[]) =>
+    program.methods
+      .rankOptions()
+      .accountsPartial({ poll })
+      .remainingAccounts(order.map((i) => ({
+        pubkey: optionPda(poll, i), isSigner: false, isWritable: false,
+      })))
+      .rpc({ commitment: "confirmed" });
+
+  before(async () => {
+    await airdrop(authority.publicKey);
+    for (const v of voters) await airdrop(v.publicKey);
+
+    const start = nowSec() + 3;
+    poll = await createPoll(authority, pollCfg(5501, start));
+    for (const [i, label] of ["Alpha", "Beta", "Gamma", "Delta"].entries()) {
+      await addOption(authority, poll, i, label);
+    }
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+
+    // Alpha net 1, Beta net 2, Gamma net 2, Delta net -1
+    for (const i of [0, 1, 2]) await castVote(voters[0], poll, i, 1);
+    await castVote(voters[0], poll, 3, -1);
+    await castVote(voters[1], poll, 1, 1);
+    await castVote(voters[1], poll, 2, 1);
+  });
+
+  it("orders by net score with ties to the lowest index", async () => {
+    for (const order of [[0, 1, 2, 3], [3, 2, 1, 0], [2, 0, 3, 1]]) {
+      const ev = (await eventsOf(await rankOptions(order))).find((e) => e.name === "rankingComputed");
+      expect(ev, `RankingComputed not emitted for ${order}`).to.exist;
+      expect(ev!.data.orderedIndices).to.deep.eq([1, 2, 0, 3]);
+    }
+  });
+
+  it("rejects the same option passed twice", async () => {
+    await expectAnchorErrCode(rankOptions([0, 1, 2, 2]), "DuplicateOptionAccount");
+  });
+
+  it("rejects a partial option list", async () => {
+    await expectAnchorErrCode(rankOptions([0, 1, 2]), "OptionCountMismatch");
+  });
+});
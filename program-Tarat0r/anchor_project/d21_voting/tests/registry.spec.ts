@@ -0,0 +1,59 @@
+import { expect } from "chai";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import BN from "bn.js";
+import { program, provider, airdrop, nowSec, pollCfg, pollPda, createPoll } from "./helpers";
+
+const registryPda = PublicKey.findProgramAddressSync([Buffer.from("registry")], program.programId)[0];
+
+describe("poll registry", () => {
+  const authority = Keypair.generate();
+
+  const nextId = async () => (await program.account.registry.fetch(registryPda)).nextPollId as BN;
+
+  async function createAuto(): Promise<PublicKey> {
+    const poll = pollPda(authority.publicKey, await nextId());
+    await program.methods
+      .initializePollAuto(pollCfg(0, nowSec() + 60))
+      .accountsPartial({
+        payer: authority.publicKey,
+        authority: authority.publicKey,
+        registry: registryPda,
+        poll,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+    return poll;
+  }
+
+  before(async () => {
+    await airdrop(authority.publicKey);
+    if (!(await provider.connection.getAccountInfo(registryPda))) {
+      await program.methods
+        .initializeRegistry()
+        .accountsPartial({ payer: authority.publicKey, registry: registryPda, systemProgram: SystemProgram.programId })
+        .signers([authority])
+        .rpc();
+    }
+  });
+
+  it("assigns distinct, incrementing ids", async () => {
+    const first = await nextId();
+    const a = await createAuto();
+    const b = await createAuto();
+
+    expect((await program.account.poll.fetch(a)).pollId.toString()).to.eq(first.toString());
+    expect((await program.account.poll.fetch(b)).pollId.toString()).to.eq(first.addn(1).toString());
+    expect((await nextId()).toString()).to.eq(first.addn(2).toString());
+  });
+
+  it("keeps counting across polls and leaves manual ids alone", async () => {
+    const before = await nextId();
+    await createPoll(authority, pollCfg(5001, nowSec() + 60));
+    expect((await nextId()).toString()).to.eq(before.toString());
+
+    const c = await createAuto();
+    expect((await program.account.poll.fetch(c)).pollId.toString()).to.eq(before.toString());
+    expect((await nextId()).toString()).to.eq(before.addn(1).toString());
+  });
+});
@@ -0,0 +1,70 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  program, provider, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode, eventsOf,
+  pollCfg, createPoll, addOption, optionPda, labelSeed, labelGuardPda,
+} from "./helpers";
+
+describe("remove_option", () => {
+  const authority = Keypair.generate();
+  const rando = Keypair.generate();
+  let poll: PublicKey;
+  let startedPoll: PublicKey;
+  let start: number;
+
+  const removeOption = (signer: Keypair, target: PublicKey, index: number, label: string) =>
+    program.methods
+      .removeOption(index)
+      .accountsPartial({
+        authority: signer.publicKey,
+        poll: target,
+        optionNode: optionPda(target, index),
+        labelGuard: labelGuardPda(target, labelSeed(label)),
+      })
+      .signers([signer])
+      .rpc({ commitment: "confirmed" });
+
+  before(async () => {
+    for (const k of [authority, rando]) await airdrop(k.publicKey);
+
+    start = nowSec() + 4;
+    poll = await createPoll(authority, pollCfg(2801, nowSec() + 3600));
+    startedPoll = await createPoll(authority, pollCfg(2802, start));
+    await addOption(authority, poll, 0, "Alpha");
+    await addOption(authority, poll, 1, "Btea");
+    await addOption(authority, startedPoll, 0, "Alpha");
+  });
+
+  it("rejects removal by someone other than the authority", async () => {
+    await expectAnchorErrCode(removeOption(rando, poll, 1, "Btea"), "Unauthorized");
+  });
+
+  it("rejects removing an option below the highest index", async () => {
+    await expectAnchorErrCode(removeOption(authority, poll, 0, "Alpha"), "NonContiguousOptionIndex");
+  });
+
+  it("removes the highest option and refunds both accounts", async () => {
+    const option = optionPda(poll, 1);
+    const guard = labelGuardPda(poll, labelSeed("Btea"));
+
+    const ev = (await eventsOf(await removeOption(authority, poll, 1, "Btea"))).find((e) => e.name === "optionRemoved");
+    expect(ev, "OptionRemoved not emitted").to.exist;
+    expect(ev!.data.index).to.eq(1);
+
+    expect(await provider.connection.getAccountInfo(option)).to.be.null;
+    expect(await provider.connection.getAccountInfo(guard)).to.be.null;
+    expect((await program.account.poll.fetch(poll)).optionsCount).to.eq(1);
+  });
+
+  it("lets the freed label and index be added again", async () => {
+    await addOption(authority, poll, 1, "Btea");
+    const o = await program.account.optionNode.fetch(optionPda(poll, 1));
+    expect(o.label).to.eq("Btea");
+    expect((await program.account.poll.fetch(poll)).optionsCount).to.eq(2);
+  });
+
+  it("rejects removal once voting has started", async () => {
+    await waitUntilChainTime(start);
+    await expectAnchorErrCode(removeOption(authority, startedPoll, 0, "Alpha"), "VotingStarted");
+  });
+});
@@ -0,0 +1,81 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
+import {
+  program, provider, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode,
+  pollCfg, createPoll, beginPoll, addOption, castVote, optionPda, voterPda, receiptPda,
+} from "./helpers";
+
+describe("retract_vote", () => {
+  const authority = Keypair.generate();
+  const voter = Keypair.generate();
+  let poll: PublicKey;
+  let shortPoll: PublicKey;
+  let shortEnd: number;
+
+  const retract = (target: PublicKey, index: number) =>
+    program.methods
+      .retractVote(index)
+      .accountsPartial({
+        voterAuthority: voter.publicKey,
+        poll: target,
+        optionNode: optionPda(target, index),
+        voter: voterPda(target, voter.publicKey),
+        receipt: receiptPda(target, index, voter.publicKey),
+      })
+      .signers([voter])
+      .rpc({ commitment: "confirmed" });
+
+  before(async () => {
+    for (const k of [authority, voter]) await airdrop(k.publicKey);
+
+    const start = nowSec() + 3;
+    shortEnd = start + 6;
+    poll = await createPoll(authority, pollCfg(2401, start));
+    shortPoll = await createPoll(authority, pollCfg(2402, start, { endTs: new BN(shortEnd), minOptions: 1 }));
+    for (let i = 0; i < 4; i++) await addOption(authority, poll, i, `Option ${i}`);
+    await addOption(authority, shortPoll, 0, "Alpha");
+    await waitUntilChainTime(start);
+    for (const p of [poll, shortPoll]) await beginPoll(authority, p);
+
+    // P=3, M=1
+    for (let i = 0; i < 3; i++) await castVote(voter, poll, i, 1);
+    await castVote(voter, poll, 3, -1);
+    await castVote(voter, shortPoll, 0, 1);
+  });
+
+  it("retracts a plus vote down to exactly P = 2*M and refunds the receipt", async () => {
+    const receipt = receiptPda(poll, 0, voter.publicKey);
+    const rent = await provider.connection.getBalance(receipt);
+    const before = await provider.connection.getBalance(voter.publicKey);
+
+    await retract(poll, 0);
+
+    expect(await provider.connection.getAccountInfo(receipt)).to.be.null;
+    const after = await provider.connection.getBalance(voter.publicKey);
+    expect(after).to.be.greaterThan(before + rent - 10_000);
+    const v = await program.account.voter.fetch(voterPda(poll, voter.publicKey));
+    expect(v.usedPlus).to.eq(2);
+    expect(v.usedMinus).to.eq(1);
+    expect((await program.account.optionNode.fetch(optionPda(poll, 0))).plusVotes).to.eq(0);
+  });
+
+  it("rejects a plus retraction that would leave P < 2*M", async () => {
+    await expectAnchorErrCode(retract(poll, 1), "InsufficientPositivesForNegative");
+  });
+
+  it("allows the plus retraction once the minus is retracted", async () => {
+    await retract(poll, 3);
+    await retract(poll, 1);
+
+    const v = await program.account.voter.fetch(voterPda(poll, voter.publicKey));
+    expect(v.usedPlus).to.eq(1);
+    expect(v.usedMinus).to.eq(0);
+    expect((await program.account.optionNode.fetch(optionPda(poll, 3))).minusVotes).to.eq(0);
+  });
+
+  it("rejects retraction after end_ts", async () => {
+    await waitUntilChainTime(shortEnd + 1);
+    await expectAnchorErrCode(retract(shortPoll, 0), "VotingClosed");
+  });
+});
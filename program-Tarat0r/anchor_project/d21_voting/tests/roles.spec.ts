@@ -0,0 +1,91 @@
+import { expect } from "chai";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, expectAnchorErrCode, pollCfg, createPoll, addOption, adminsPda,
+} from "./helpers";
+
+describe("roles", () => {
+  const authority = Keypair.generate();
+  const manager = Keypair.generate();
+  const closer = Keypair.generate();
+  const delegateAdmin = Keypair.generate();
+  const outsider = Keypair.generate();
+  let poll: PublicKey;
+  let admins: PublicKey;
+
+  const setAdmin = (signer: Keypair, key: PublicKey, role: any) =>
+    program.methods
+      .setAdmin(key, role)
+      .accountsPartial({ signer: signer.publicKey, poll, admins, systemProgram: SystemProgram.programId })
+      .signers([signer])
+      .rpc();
+  const removeAdmin = (signer: Keypair, key: PublicKey) =>
+    program.methods
+      .removeAdmin(key)
+      .accountsPartial({ signer: signer.publicKey, poll, admins })
+      .signers([signer])
+      .rpc();
+  const endPoll = (signer: Keypair) =>
+    program.methods
+      .endPoll()
+      .accountsPartial({ signer: signer.publicKey, poll, admins })
+      .signers([signer])
+      .rpc();
+
+  before(async () => {
+    for (const k of [authority, manager, closer, delegateAdmin, outsider]) await airdrop(k.publicKey);
+    poll = await createPoll(authority, pollCfg(1001, nowSec() + 60));
+    admins = adminsPda(poll);
+
+    await setAdmin(authority, manager.publicKey, { optionManager: {} });
+    await setAdmin(authority, closer.publicKey, { closer: {} });
+  });
+
+  it("records roles on the Admins account", async () => {
+    const acc = await program.account.admins.fetch(admins);
+    expect(acc.poll.toBase58()).to.eq(poll.toBase58());
+    expect(acc.entries.map((e: any) => [e.key.toBase58(), Object.keys(e.role)[0]])).to.deep.eq([
+      [manager.publicKey.toBase58(), "optionManager"],
+      [closer.publicKey.toBase58(), "closer"],
+    ]);
+  });
+
+  it("OptionManager can add options but not end the poll", async () => {
+    await addOption(manager, poll, 0, "Alpha", admins);
+    const poll_ = await program.account.poll.fetch(poll);
+    expect(poll_.optionsCount).to.eq(1);
+
+    await expectAnchorErrCode(endPoll(manager), "InsufficientRole");
+  });
+
+  it("Closer cannot add options", async () => {
+    await expectAnchorErrCode(addOption(closer, poll, 1, "Beta", admins), "InsufficientRole");
+  });
+
+  it("only the poll authority can manage admins", async () => {
+    await expectAnchorErrCode(setAdmin(outsider, outsider.publicKey, { fullAdmin: {} }), "Unauthorized");
+    await expectAnchorErrCode(setAdmin(manager, outsider.publicKey, { optionManager: {} }), "Unauthorized");
+
+    await setAdmin(authority, delegateAdmin.publicKey, { fullAdmin: {} });
+    await expectAnchorErrCode(setAdmin(delegateAdmin, outsider.publicKey, { optionManager: {} }), "Unauthorized");
+    await setAdmin(authority, outsider.publicKey, { optionManager: {} });
+    await addOption(outsider, poll, 1, "Beta", admins);
+
+    await expectAnchorErrCode(removeAdmin(delegateAdmin, outsider.publicKey), "Unauthorized");
+    await removeAdmin(authority, outsider.publicKey);
+    await expectAnchorErrCode(addOption(outsider, poll, 2, "Gamma", admins), "InsufficientRole");
+    await expectAnchorErrCode(removeAdmin(authority, outsider.publicKey), "AdminNotFound");
+  });
+
+  it("FullAdmin holds every role", async () => {
+    await addOption(delegateAdmin, poll, 2, "Gamma", admins);
+  });
+
+  it("Closer can end the poll", async () => {
+    await endPoll(closer);
+    const poll_ = await program.account.poll.fetch(poll);
+    expect(poll_.ended).to.eq(true);
+
+    await expectAnchorErrCode(addOption(authority, poll, 3, "Delta"), "VotingClosed");
+  });
+});
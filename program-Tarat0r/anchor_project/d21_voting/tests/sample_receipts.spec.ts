@@ -0,0 +1,74 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode, eventsOf,
+  pollCfg, createPoll, beginPoll, addOption, castVote, receiptPda,
+} from "./helpers";
+
+describe("sample_receipts", () => {
+  const authority = Keypair.generate();
+  const alice = Keypair.generate();
+  const bob = Keypair.generate();
+  let poll: PublicKey;
+  let otherPoll: PublicKey;
+  let receipts: PublicKey[];
+
+  const sample = async (stride: number, offset: number, accounts: PublicKey[]) => {
+    const sig = await program.methods
+      .sampleReceipts(stride, offset)
+      .accountsPartial({ poll })
+      .remainingAccounts(accounts.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })))
+      .rpc({ commitment: "confirmed" });
+    const ev = (await eventsOf(sig)).find((e) => e.name === "sampleResult");
+    expect(ev, "SampleResult not emitted").to.exist;
+    return ev!.data;
+  };
+
+  before(async () => {
+    for (const k of [authority, alice, bob]) await airdrop(k.publicKey);
+
+    const start = nowSec() + 3;
+    poll = await createPoll(authority, pollCfg(1601, start));
+    otherPoll = await createPoll(authority, pollCfg(1602, start));
+    for (const p of [poll, otherPoll]) {
+      await addOption(authority, p, 0, "Alpha");
+      await addOption(authority, p, 1, "Beta");
+    }
+    await waitUntilChainTime(start);
+    for (const p of [poll, otherPoll]) await beginPoll(authority, p);
+
+    await castVote(alice, poll, 0, 1);
+    await castVote(alice, poll, 1, 1);
+    await castVote(bob, poll, 0, 1);
+    await castVote(bob, poll, 1, 1);
+    await castVote(bob, otherPoll, 0, 1);
+
+    receipts = [
+      receiptPda(poll, 0, alice.publicKey),
+      receiptPda(poll, 1, alice.publicKey),
+      receiptPda(poll, 0, bob.publicKey),
+      receiptPda(poll, 1, bob.publicKey),
+    ];
+  });
+
+  it("checks every stride-th receipt from the offset", async () => {
+    const data = await sample(2, 1, receipts);
+    expect(data.checked).to.eq(2);
+    expect(data.allValid).to.eq(true);
+
+    const all = await sample(1, 0, receipts);
+    expect(all.checked).to.eq(4);
+    expect(all.allValid).to.eq(true);
+  });
+
+  it("flags a sampled receipt of another poll", async () => {
+    const mixed = [...receipts, receiptPda(otherPoll, 0, bob.publicKey)];
+    expect((await sample(2, 0, mixed)).allValid).to.eq(false);
+    // the foreign receipt sits at index 4 and is skipped by this sample
+    expect((await sample(2, 1, mixed)).allValid).to.eq(true);
+  });
+
+  it("rejects a zero stride", async () => {
+    await expectAnchorErrCode(sample(0, 0, receipts), "InvalidStride");
+  });
+});
@@ -0,0 +1,49 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, expectAnchorErrCode, eventsOf, pollCfg, createPoll, addOption,
+} from "./helpers";
+
+describe("seal_options", () => {
+  const authority = Keypair.generate();
+  const rando = Keypair.generate();
+  let poll: PublicKey;
+
+  const seal = (signer: Keypair) =>
+    program.methods
+      .sealOptions()
+      .accountsPartial({ signer: signer.publicKey, poll, admins: null })
+      .signers([signer])
+      .rpc({ commitment: "confirmed" });
+
+  before(async () => {
+    await airdrop(authority.publicKey);
+    await airdrop(rando.publicKey);
+    // start far in the future so only the seal can block add_option
+    poll = await createPoll(authority, pollCfg(1201, nowSec() + 3600));
+    await addOption(authority, poll, 0, "Alpha");
+    await addOption(authority, poll, 1, "Beta");
+  });
+
+  it("rejects sealing without the OptionManager role", async () => {
+    await expectAnchorErrCode(seal(rando), "InsufficientRole");
+  });
+
+  it("seals the ballot before start", async () => {
+    const ev = (await eventsOf(await seal(authority))).find((e) => e.name === "ballotSealed");
+    expect(ev, "BallotSealed not emitted").to.exist;
+    expect(ev!.data.optionsCount).to.eq(2);
+
+    const acc = await program.account.poll.fetch(poll);
+    expect(acc.optionsSealed).to.eq(true);
+    expect(acc.startTs.toNumber()).to.be.greaterThan(nowSec());
+  });
+
+  it("rejects add_option after sealing even though voting has not started", async () => {
+    await expectAnchorErrCode(addOption(authority, poll, 2, "Gamma"), "OptionsSealed");
+  });
+
+  it("rejects sealing twice", async () => {
+    await expectAnchorErrCode(seal(authority), "OptionsSealed");
+  });
+});
@@ -0,0 +1,41 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  program, provider, airdrop, nowSec, waitUntilChainTime, pollCfg, createPoll, beginPoll, addOption, castVote,
+  voterPda, receiptPda,
+} from "./helpers";
+
+describe("sponsored votes", () => {
+  const authority = Keypair.generate();
+  const sponsor = Keypair.generate();
+  const voter = Keypair.generate(); // never funded
+  let poll: PublicKey;
+
+  before(async () => {
+    await airdrop(authority.publicKey);
+    await airdrop(sponsor.publicKey);
+
+    const start = nowSec() + 3;
+    poll = await createPoll(authority, pollCfg(1401, start, { minOptions: 1 }));
+    await addOption(authority, poll, 0, "Alpha");
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+  });
+
+  it("lets a sponsor pay rent while the vote belongs to the voter", async () => {
+    const sponsorBefore = await provider.connection.getBalance(sponsor.publicKey);
+
+    await castVote(voter, poll, 0, 1, null, sponsor);
+
+    const voterAcc = await program.account.voter.fetch(voterPda(poll, voter.publicKey));
+    expect(voterAcc.voter.toBase58()).to.eq(voter.publicKey.toBase58());
+    expect(voterAcc.usedPlus).to.eq(1);
+
+    const receipt = await program.account.receipt.fetch(receiptPda(poll, 0, voter.publicKey));
+    expect(receipt.voter.toBase58()).to.eq(voter.publicKey.toBase58());
+    expect(receipt.sentiment).to.eq(1);
+
+    expect(await provider.connection.getBalance(voter.publicKey)).to.eq(0);
+    expect(await provider.connection.getBalance(sponsor.publicKey)).to.be.lessThan(sponsorBefore);
+  });
+});
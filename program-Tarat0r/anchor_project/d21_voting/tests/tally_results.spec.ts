@@ -0,0 +1,59 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode, eventsOf,
+  pollCfg, createPoll, beginPoll, addOption, castVote, optionPda,
+} from "./helpers";
+
+describe("tally_results", () => {
+  const authority = Keypair.generate();
+  const voters = [Keypair.generate(), Keypair.generate()];
+  let poll: PublicKey;
+
+  const tally = (order: number[]) =>
+    program.methods
+      .tallyResults()
+      .accountsPartial({ poll })
+      .remainingAccounts(order.map((i) => ({
+        pubkey: optionPda(poll, i), isSigner: false, isWritable: false,
+      })))
+      .rpc({ commitment: "confirmed" });
+
+  before(async () => {
+    await airdrop(authority.publicKey);
+    for (const v of voters) await airdrop(v.publicKey);
+
+    const start = nowSec() + 3;
+    poll = await createPoll(authority, pollCfg(2501, start));
+    for (let i = 0; i < 3; i++) await addOption(authority, poll, i, `Option ${i}`);
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+
+    for (const v of voters) {
+      await castVote(v, poll, 0, 1);
+      await castVote(v, poll, 1, 1);
+    }
+    await castVote(voters[0], poll, 2, -1);
+  });
+
+  it("emits totals equal to the sum across option nodes", async () => {
+    const events = await eventsOf(await tally([2, 0, 1]));
+    const totals = events.find((e) => e.name === "resultsTallied");
+    expect(totals, "ResultsTallied not emitted").to.exist;
+
+    const options = await Promise.all([0, 1, 2].map((i) => program.account.optionNode.fetch(optionPda(poll, i))));
+    const plus = options.reduce((a, o) => a + o.plusVotes, 0);
+    const minus = options.reduce((a, o) => a + o.minusVotes, 0);
+    expect(totals!.data.totalPlus.toNumber()).to.eq(plus).and.eq(4);
+    expect(totals!.data.totalMinus.toNumber()).to.eq(minus).and.eq(1);
+    expect(totals!.data.optionCount).to.eq(3);
+
+    const breakdown = events.filter((e) => e.name === "optionTallied").map((e) => e.data);
+    expect(breakdown.map((b) => b.index)).to.deep.eq([0, 1, 2]);
+    expect(breakdown.map((b) => b.net.toNumber())).to.deep.eq([2, 2, -1]);
+  });
+
+  it("rejects a partial option list", async () => {
+    await expectAnchorErrCode(tally([0, 1]), "OptionCountMismatch");
+  });
+});
@@ -0,0 +1,32 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, waitUntilChainTime, pollCfg, createPoll, beginPoll, addOption, castVote,
+} from "./helpers";
+
+describe("total_voters", () => {
+  const authority = Keypair.generate();
+  const voters = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+  let poll: PublicKey;
+
+  before(async () => {
+    await airdrop(authority.publicKey);
+    for (const v of voters) await airdrop(v.publicKey);
+
+    const start = nowSec() + 3;
+    poll = await createPoll(authority, pollCfg(3301, start));
+    for (let i = 0; i < 4; i++) await addOption(authority, poll, i, `Option ${i}`);
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+  });
+
+  it("counts ten votes from three voters as three voters", async () => {
+    for (const v of voters) {
+      for (let i = 0; i < 3; i++) await castVote(v, poll, i, 1);
+    }
+    await castVote(voters[0], poll, 3, -1);
+
+    const acc = await program.account.poll.fetch(poll);
+    expect(acc.totalVoters).to.eq(3);
+  });
+});
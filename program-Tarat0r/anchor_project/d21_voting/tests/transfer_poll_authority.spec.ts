@@ -0,0 +1,50 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
+import {
+  program, airdrop, nowSec, expectAnchorErrCode, eventsOf,
+  pollCfg, createPoll, addOption, pollPda,
+} from "./helpers";
+
+describe("transfer_poll_authority", () => {
+  const creator = Keypair.generate();
+  const successor = Keypair.generate();
+  let poll: PublicKey;
+
+  const transfer = (signer: Keypair, to: PublicKey) =>
+    program.methods
+      .transferPollAuthority(to)
+      .accountsPartial({ authority: signer.publicKey, poll })
+      .signers([signer])
+      .rpc({ commitment: "confirmed" });
+
+  before(async () => {
+    for (const k of [creator, successor]) await airdrop(k.publicKey);
+    poll = await createPoll(creator, pollCfg(3401, nowSec() + 3600));
+  });
+
+  it("rejects a transfer by someone other than the authority", async () => {
+    await expectAnchorErrCode(transfer(successor, successor.publicKey), "Unauthorized");
+  });
+
+  it("transfers the poll and keeps its address", async () => {
+    const ev = (await eventsOf(await transfer(creator, successor.publicKey))).find((e) => e.name === "authorityTransferred");
+    expect(ev, "AuthorityTransferred not emitted").to.exist;
+    expect(ev!.data.old.toBase58()).to.eq(creator.publicKey.toBase58());
+    expect(ev!.data.new.toBase58()).to.eq(successor.publicKey.toBase58());
+
+    const acc = await program.account.poll.fetch(poll);
+    expect(acc.authority.toBase58()).to.eq(successor.publicKey.toBase58());
+    expect(acc.creator.toBase58()).to.eq(creator.publicKey.toBase58());
+    expect(pollPda(creator.publicKey, new BN(3401)).toBase58()).to.eq(poll.toBase58());
+  });
+
+  it("lets the new authority add options", async () => {
+    await addOption(successor, poll, 0, "Alpha");
+    expect((await program.account.poll.fetch(poll)).optionsCount).to.eq(1);
+  });
+
+  it("rejects the old authority", async () => {
+    await expectAnchorErrCode(addOption(creator, poll, 1, "Beta"), "InsufficientRole");
+  });
+});
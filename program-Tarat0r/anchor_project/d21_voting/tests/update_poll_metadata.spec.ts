@@ -0,0 +1,59 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode, eventsOf,
+  pollCfg, createPoll,
+} from "./helpers";
+
+describe("update_poll_metadata", () => {
+  const authority = Keypair.generate();
+  const rando = Keypair.generate();
+  let poll: PublicKey;
+  let startedPoll: PublicKey;
+  let start: number;
+
+  const update = (signer: Keypair, target: PublicKey, title: string | null, desc: string | null) =>
+    program.methods
+      .updatePollMetadata(title, desc)
+      .accountsPartial({ authority: signer.publicKey, poll: target })
+      .signers([signer])
+      .rpc({ commitment: "confirmed" });
+
+  before(async () => {
+    for (const k of [authority, rando]) await airdrop(k.publicKey);
+    start = nowSec() + 3;
+    poll = await createPoll(authority, pollCfg(2901, nowSec() + 3600));
+    startedPoll = await createPoll(authority, pollCfg(2902, start));
+  });
+
+  it("updates only the title and leaves the description intact", async () => {
+    const ev = (await eventsOf(await update(authority, poll, "Renamed", null))).find((e) => e.name === "pollMetadataUpdated");
+    expect(ev, "PollMetadataUpdated not emitted").to.exist;
+    expect(ev!.data.titleChanged).to.eq(true);
+    expect(ev!.data.descriptionChanged).to.eq(false);
+
+    const acc = await program.account.poll.fetch(poll);
+    expect(acc.title).to.eq("Renamed");
+    expect(acc.description).to.eq("test poll");
+  });
+
+  it("updates only the description and leaves the title intact", async () => {
+    await update(authority, poll, null, "new description");
+    const acc = await program.account.poll.fetch(poll);
+    expect(acc.title).to.eq("Renamed");
+    expect(acc.description).to.eq("new description");
+  });
+
+  it("rejects an over-long title", async () => {
+    await expectAnchorErrCode(update(authority, poll, "x".repeat(65), null), "TitleTooLong");
+  });
+
+  it("rejects edits by someone other than the authority", async () => {
+    await expectAnchorErrCode(update(rando, poll, "Hijacked", null), "Unauthorized");
+  });
+
+  it("rejects edits once voting has started", async () => {
+    await waitUntilChainTime(start);
+    await expectAnchorErrCode(update(authority, startedPoll, "Too late", null), "VotingStarted");
+  });
+});
@@ -0,0 +1,46 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, waitUntilChainTime, eventsOf,
+  pollCfg, createPoll, beginPoll, addOption, castVote, optionPda,
+} from "./helpers";
+
+describe("VoteCast option totals", () => {
+  const authority = Keypair.generate();
+  const voters = [Keypair.generate(), Keypair.generate()];
+  let poll: PublicKey;
+
+  before(async () => {
+    await airdrop(authority.publicKey);
+    for (const v of voters) await airdrop(v.publicKey);
+
+    const start = nowSec() + 3;
+    poll = await createPoll(authority, pollCfg(6201, start));
+    await addOption(authority, poll, 0, "Alpha");
+    await addOption(authority, poll, 1, "Beta");
+    await addOption(authority, poll, 2, "Gamma");
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+  });
+
+  it("reports the option counters after each vote", async () => {
+    // voters[0] needs two plus votes before the minus on option 2
+    const votes: [Keypair, number, number][] = [
+      [voters[0], 0, 1],
+      [voters[1], 0, 1],
+      [voters[0], 1, 1],
+      [voters[0], 2, -1],
+      [voters[1], 2, 1],
+    ];
+    for (const [voter, index, sentiment] of votes) {
+      const sig = await castVote(voter, poll, index, sentiment);
+      const ev = (await eventsOf(sig)).find((e) => e.name === "voteCast");
+      expect(ev, "VoteCast not emitted").to.exist;
+      const option = await program.account.optionNode.fetch(optionPda(poll, index));
+      expect(ev!.data.optionPlusVotes).to.eq(option.plusVotes);
+      expect(ev!.data.optionMinusVotes).to.eq(option.minusVotes);
+    }
+    const gamma = await program.account.optionNode.fetch(optionPda(poll, 2));
+    expect([gamma.plusVotes, gamma.minusVotes]).to.deep.eq([1, 1]);
+  });
+});
@@ -0,0 +1,52 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
+import {
+  program, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode,
+  pollCfg, createPoll, beginPoll, addOption, castVote, voterPda,
+} from "./helpers";
+
+describe("vote cooldown", () => {
+  const authority = Keypair.generate();
+  const voter = Keypair.generate();
+  const cooldown = 4;
+  let poll: PublicKey;
+  let open: PublicKey;
+
+  before(async () => {
+    for (const k of [authority, voter]) await airdrop(k.publicKey);
+
+    const start = nowSec() + 3;
+    poll = await createPoll(authority, pollCfg(5801, start, { voteCooldownSecs: new BN(cooldown) }));
+    open = await createPoll(authority, pollCfg(5802, start));
+    for (const p of [poll, open]) {
+      for (const [i, label] of ["Alpha", "Beta", "Gamma"].entries()) await addOption(authority, p, i, label);
+    }
+    await waitUntilChainTime(start);
+    for (const p of [poll, open]) await beginPoll(authority, p);
+  });
+
+  it("rejects a negative cooldown", async () => {
+    await expectAnchorErrCode(
+      createPoll(authority, pollCfg(5803, nowSec() + 60, { voteCooldownSecs: new BN(-1) })),
+      "InvalidVoteCooldown"
+    );
+  });
+
+  it("rejects a second vote inside the cooldown", async () => {
+    await castVote(voter, poll, 0, 1);
+    await expectAnchorErrCode(castVote(voter, poll, 1, 1), "VoteCooldownActive");
+  });
+
+  it("accepts the next vote once the cooldown has passed", async () => {
+    const last = (await program.account.voter.fetch(voterPda(poll, voter.publicKey))).lastVoteTs.toNumber();
+    await waitUntilChainTime(last + cooldown);
+    await castVote(voter, poll, 1, 1);
+    const next = (await program.account.voter.fetch(voterPda(poll, voter.publicKey))).lastVoteTs.toNumber();
+    expect(next).to.be.gte(last + cooldown);
+  });
+
+  it("does not throttle when the cooldown is 0", async () => {
+    for (const i of [0, 1, 2]) await castVote(voter, open, i, 1);
+  });
+});
@@ -0,0 +1,50 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode, eventsOf,
+  pollCfg, createPoll, beginPoll, addOption, castVote, receiptPda,
+} from "./helpers";
+
+describe("vote notes", () => {
+  const authority = Keypair.generate();
+  const voter = Keypair.generate();
+  let poll: PublicKey;
+
+  before(async () => {
+    await airdrop(authority.publicKey);
+    await airdrop(voter.publicKey);
+
+    const start = nowSec() + 3;
+    poll = await createPoll(authority, pollCfg(801, start));
+    await addOption(authority, poll, 0, "Alpha");
+    await addOption(authority, poll, 1, "Beta");
+    await addOption(authority, poll, 2, "Gamma");
+    await waitUntilChainTime(start);
+    await beginPoll(authority, poll);
+  });
+
+  it("stores the note on the receipt and in VoteCast", async () => {
+    const sig = await castVote(voter, poll, 0, 1, "best roadmap");
+
+    const receipt = await program.account.receipt.fetch(receiptPda(poll, 0, voter.publicKey));
+    expect(receipt.note).to.eq("best roadmap");
+
+    const ev = (await eventsOf(sig)).find((e) => e.name === "voteCast");
+    expect(ev, "VoteCast not emitted").to.exist;
+    expect(ev!.data.note).to.eq("best roadmap");
+  });
+
+  it("accepts a vote without a note", async () => {
+    const sig = await castVote(voter, poll, 1, 1);
+
+    const receipt = await program.account.receipt.fetch(receiptPda(poll, 1, voter.publicKey));
+    expect(receipt.note).to.be.null;
+
+    const ev = (await eventsOf(sig)).find((e) => e.name === "voteCast");
+    expect(ev!.data.note).to.be.null;
+  });
+
+  it("rejects a note over 128 bytes", async () => {
+    await expectAnchorErrCode(castVote(voter, poll, 2, 1, "x".repeat(129)), "NoteTooLong");
+  });
+});
@@ -0,0 +1,66 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  program, airdrop, nowSec, waitUntilChainTime, expectAnchorErrCode,
+  pollCfg, createPoll, beginPoll, addOption, castVote, optionPda, voterPda,
+} from "./helpers";
+
+describe("voting modes", () => {
+  const authority = Keypair.generate();
+  const voter = Keypair.generate();
+  let d21: PublicKey;
+  let approval: PublicKey;
+  let plurality: PublicKey;
+
+  before(async () => {
+    for (const k of [authority, voter]) await airdrop(k.publicKey);
+
+    const start = nowSec() + 4;
+    d21 = await createPoll(authority, pollCfg(4401, start, { plusCredits: 1 }));
+    approval = await createPoll(authority, pollCfg(4402, start, { plusCredits: 1, mode: { approval: {} } }));
+    plurality = await createPoll(authority, pollCfg(4403, start, { plusCredits: 3, mode: { plurality: {} } }));
+    for (const poll of [d21, approval, plurality]) {
+      for (const [i, label] of ["Alpha", "Beta", "Gamma"].entries()) {
+        await addOption(authority, poll, i, label);
+      }
+    }
+    await waitUntilChainTime(start);
+    for (const p of [d21, approval, plurality]) await beginPoll(authority, p);
+  });
+
+  it("stores the configured mode", async () => {
+    expect((await program.account.poll.fetch(d21)).mode).to.deep.eq({ d21: {} });
+    expect((await program.account.poll.fetch(approval)).mode).to.deep.eq({ approval: {} });
+    expect((await program.account.poll.fetch(plurality)).mode).to.deep.eq({ plurality: {} });
+  });
+
+  it("D21 keeps the credit limit", async () => {
+    await castVote(voter, d21, 0, 1);
+    await expectAnchorErrCode(castVote(voter, d21, 1, 1), "OutOfPositiveCredits");
+  });
+
+  it("Approval ignores credits and approves every option", async () => {
+    for (const i of [0, 1, 2]) await castVote(voter, approval, i, 1);
+    for (const i of [0, 1, 2]) {
+      expect((await program.account.optionNode.fetch(optionPda(approval, i))).plusVotes).to.eq(1);
+    }
+    expect((await program.account.voter.fetch(voterPda(approval, voter.publicKey))).usedPlus).to.eq(3);
+  });
+
+  it("Approval still allows one receipt per option", async () => {
+    await expectAnchorErrCode(castVote(voter, approval, 0, 1), "AlreadyVotedThisOption");
+  });
+
+  it("Approval only accepts positive votes", async () => {
+    const other = Keypair.generate();
+    await airdrop(other.publicKey);
+    await expectAnchorErrCode(castVote(other, approval, 0, -1), "InvalidSentiment");
+    await expectAnchorErrCode(castVote(other, approval, 0, 0), "InvalidSentiment");
+  });
+
+  it("Plurality allows one positive vote across the poll", async () => {
+    await castVote(voter, plurality, 1, 1);
+    await expectAnchorErrCode(castVote(voter, plurality, 2, 1), "OutOfPositiveCredits");
+    await expectAnchorErrCode(castVote(voter, plurality, 0, -1), "InvalidSentiment");
+  });
+});
@@ -133,7 +133,7 @@ export default function Home() {
 
   // ---- label-guard helpers (match on-chain hashing) ----
   async function labelSeed(label: string): Promise<Uint8Array> {
-    const canonical = label.trim().toLowerCase();
+    const canonical = label.normalize("NFC").trim().split(/\s+/).join(" ").toLowerCase();
 
     // Prefer WebCrypto in the browser; convert to ArrayBuffer explicitly
     if (typeof window !== "undefined" && globalThis.crypto?.subtle) {
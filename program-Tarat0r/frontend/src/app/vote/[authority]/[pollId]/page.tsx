@@ -240,9 +240,10 @@ export default function VotePage() {
       const receipt = receiptPda(pollPda, wallet.publicKey, opt.index);
 
       const sig = await program.methods
-        .castVote(opt.index, sentiment as any)
+        .castVote(opt.index, sentiment as any, null)
         .accounts({
           voterAuthority: wallet.publicKey,
+          payer: wallet.publicKey,
           poll: pollPda,
           optionNode: opt.pda,
           voter: voterPda,
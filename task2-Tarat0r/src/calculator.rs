@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 ///-------------------------------------------------------------------------------
 ///
 /// This is your calculator implementation task
@@ -17,7 +19,7 @@
 ///
 ///-------------------------------------------------------------------------------
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum OperationType {
     Addition,
     Subtraction,
@@ -48,6 +50,13 @@ impl OperationType {
     }
 }
 
+#[derive(Debug, PartialEq)]
+pub enum CalcError {
+    // position is the byte offset into the expression
+    ParseError { position: usize, message: String },
+    Overflow,
+}
+
 #[derive(Clone)]
 pub struct Operation {
     pub first_num: i64,
@@ -68,6 +77,13 @@ impl Operation {
 
 pub struct Calculator {
     pub history: Vec<Operation>,
+    // When set, only the most recent max_history operations are kept;
+    // recording beyond the limit evicts the oldest one, so history indices
+    // (e.g. in show_history and repeat) always refer to the current window
+    pub max_history: Option<usize>,
+    // Weight of each operation type used by total_cost; types missing from
+    // the map cost nothing
+    pub op_costs: BTreeMap<OperationType, u64>,
 }
 
 impl Calculator {
@@ -75,6 +91,17 @@ impl Calculator {
     pub fn new() -> Self {
         Self {
             history: Vec::new(),
+            max_history: None,
+            op_costs: default_op_costs(),
+        }
+    }
+
+    // Create a new Calculator that keeps at most max_history operations
+    pub fn with_max_history(max_history: usize) -> Self {
+        Self {
+            history: Vec::new(),
+            max_history: Some(max_history),
+            op_costs: default_op_costs(),
         }
     }
 
@@ -83,7 +110,7 @@ impl Calculator {
     pub fn addition(&mut self, x: i64, y: i64) -> Option<i64> {
         let result = OperationType::Addition.perform(x, y);
         if result.is_some() {
-            self.history.push(Operation {
+            self.record(Operation {
                 first_num: x,
                 second_num: y,
                 operation_type: OperationType::Addition,
@@ -97,7 +124,7 @@ impl Calculator {
     pub fn subtraction(&mut self, x: i64, y: i64) -> Option<i64> {
         let result = OperationType::Subtraction.perform(x, y);
         if result.is_some() {
-            self.history.push(Operation {
+            self.record(Operation {
                 first_num: x,
                 second_num: y,
                 operation_type: OperationType::Subtraction,
@@ -111,7 +138,7 @@ impl Calculator {
     pub fn multiplication(&mut self, x: i64, y: i64) -> Option<i64> {
         let result = OperationType::Multiplication.perform(x, y);
         if result.is_some() {
-            self.history.push(Operation {
+            self.record(Operation {
                 first_num: x,
                 second_num: y,
                 operation_type: OperationType::Multiplication,
@@ -154,17 +181,117 @@ impl Calculator {
         let result = operation.perform(x, y);
 
         if result.is_some() {
-            self.history.push(Operation {
+            self.record(Operation {
                 first_num: x,
                 second_num: y,
                 operation_type: operation,
             })
         }
 
-        self.history[operation_index].operation_type.perform(
-            self.history[operation_index].first_num,
-            self.history[operation_index].second_num,
-        )
+        result
+    }
+
+    // Apply each (operation, x, y) from the iterator in order, recording the
+    // successful ones in history like the individual methods do
+    //
+    // Returns one result per input; None marks an overflow
+    pub fn run_stream<I>(&mut self, ops: I) -> Vec<Option<i64>>
+    where
+        I: IntoIterator<Item = (OperationType, i64, i64)>,
+    {
+        ops.into_iter()
+            .map(|(operation_type, x, y)| self.apply(operation_type, x, y))
+            .collect()
+    }
+
+    // Cumulative sum of the results in history, one entry per position
+    //
+    // Once the running sum overflows every following entry is None
+    pub fn running_totals(&self) -> Vec<Option<i64>> {
+        let mut total = Some(0i64);
+        self.history
+            .iter()
+            .map(|op| {
+                total = total.and_then(|sum| {
+                    op.operation_type
+                        .perform(op.first_num, op.second_num)
+                        .and_then(|result| sum.checked_add(result))
+                });
+                total
+            })
+            .collect()
+    }
+
+    // Count the results in history per bucket of bucket_size values, keyed
+    // by result / bucket_size * bucket_size; the division truncates toward
+    // zero, so -3 with a bucket_size of 5 lands in the 0 bucket and -6 in -5
+    //
+    // Panics if bucket_size is not positive
+    pub fn result_histogram(&self, bucket_size: i64) -> BTreeMap<i64, usize> {
+        assert!(bucket_size > 0, "bucket_size must be positive");
+        let mut histogram = BTreeMap::new();
+        for op in &self.history {
+            if let Some(result) = op.operation_type.perform(op.first_num, op.second_num) {
+                let bucket = result / bucket_size * bucket_size;
+                *histogram.entry(bucket).or_insert(0) += 1;
+            }
+        }
+        histogram
+    }
+
+    // Change the weight of one operation type in the cost model
+    pub fn set_op_cost(&mut self, operation_type: OperationType, cost: u64) {
+        self.op_costs.insert(operation_type, cost);
+    }
+
+    // Sum of the weights of every operation in history, saturating at u64::MAX
+    pub fn total_cost(&self) -> u64 {
+        self.history
+            .iter()
+            .map(|op| self.op_costs.get(&op.operation_type).copied().unwrap_or(0))
+            .fold(0, u64::saturating_add)
+    }
+
+    fn apply(&mut self, operation_type: OperationType, x: i64, y: i64) -> Option<i64> {
+        let result = operation_type.perform(x, y);
+        if result.is_some() {
+            self.record(Operation::new(x, y, operation_type));
+        }
+        result
+    }
+
+    // Evaluate an expression like "(2 + 3) * -4" with the usual precedence
+    // (parentheses, then * before + and -), recording every primitive
+    // operation in history
+    pub fn evaluate_expr(&mut self, expr: &str) -> Result<i64, CalcError> {
+        let parsed = Parser::new(expr)?.parse()?;
+        self.evaluate(&parsed)
+    }
+
+    // Check that an expression parses, without evaluating or recording it
+    pub fn validate_expr(expr: &str) -> Result<(), CalcError> {
+        Parser::new(expr)?.parse().map(|_| ())
+    }
+
+    fn evaluate(&mut self, expr: &Expr) -> Result<i64, CalcError> {
+        match expr {
+            Expr::Number(n) => Ok(*n),
+            Expr::Binary(operation_type, lhs, rhs) => {
+                let x = self.evaluate(lhs)?;
+                let y = self.evaluate(rhs)?;
+                self.apply(*operation_type, x, y).ok_or(CalcError::Overflow)
+            }
+        }
+    }
+
+    fn record(&mut self, operation: Operation) {
+        self.history.push(operation);
+        if let Some(max_history) = self.max_history {
+            if self.history.len() > max_history {
+                let evicted = self.history.len() - max_history;
+                self.history.drain(..evicted);
+            }
+        }
     }
 
     // TODO: Clear all operations from history
@@ -172,3 +299,143 @@ impl Calculator {
         self.history = Vec::new()
     }
 }
+
+enum Expr {
+    Number(i64),
+    Binary(OperationType, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Clone, Copy)]
+enum Token {
+    Number(i64),
+    Operator(OperationType),
+    LParen,
+    RParen,
+    End,
+}
+
+// Recursive-descent parser over the tokens of an expression:
+//
+// expr   := term (('+' | '-') term)*
+// term   := factor ('*' factor)*
+// factor := '-'? number | '(' expr ')'
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+}
+
+impl Parser {
+    fn new(expr: &str) -> Result<Self, CalcError> {
+        let mut tokens = Vec::new();
+        let mut chars = expr.char_indices().peekable();
+
+        while let Some(&(start, c)) = chars.peek() {
+            if c.is_whitespace() {
+                chars.next();
+            } else if c.is_ascii_digit() {
+                let mut end = start;
+                while let Some(&(i, d)) = chars.peek() {
+                    if !d.is_ascii_digit() {
+                        break;
+                    }
+                    end = i + 1;
+                    chars.next();
+                }
+                let n = expr[start..end]
+                    .parse::<i64>()
+                    .map_err(|_| parse_error(start, "number out of range"))?;
+                tokens.push((start, Token::Number(n)));
+            } else if c == '(' || c == ')' {
+                let token = if c == '(' { Token::LParen } else { Token::RParen };
+                tokens.push((start, token));
+                chars.next();
+            } else {
+                let operation_type = match c {
+                    '+' => OperationType::Addition,
+                    '-' => OperationType::Subtraction,
+                    '*' => OperationType::Multiplication,
+                    _ => return Err(parse_error(start, &format!("unexpected character '{}'", c))),
+                };
+                tokens.push((start, Token::Operator(operation_type)));
+                chars.next();
+            }
+        }
+        tokens.push((expr.len(), Token::End));
+
+        Ok(Self { tokens, pos: 0 })
+    }
+
+    fn parse(mut self) -> Result<Expr, CalcError> {
+        let expr = self.expr()?;
+        match self.peek() {
+            (_, Token::End) => Ok(expr),
+            (position, Token::RParen) => Err(parse_error(position, "unmatched ')'")),
+            (position, _) => Err(parse_error(position, "expected an operator")),
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr, CalcError> {
+        let mut lhs = self.term()?;
+        while let (_, Token::Operator(op @ (OperationType::Addition | OperationType::Subtraction))) = self.peek() {
+            self.pos += 1;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(self.term()?));
+        }
+        Ok(lhs)
+    }
+
+    fn term(&mut self) -> Result<Expr, CalcError> {
+        let mut lhs = self.factor()?;
+        while let (_, Token::Operator(OperationType::Multiplication)) = self.peek() {
+            self.pos += 1;
+            lhs = Expr::Binary(OperationType::Multiplication, Box::new(lhs), Box::new(self.factor()?));
+        }
+        Ok(lhs)
+    }
+
+    fn factor(&mut self) -> Result<Expr, CalcError> {
+        let (position, token) = self.next();
+        match token {
+            Token::Number(n) => Ok(Expr::Number(n)),
+            Token::LParen => {
+                let inner = self.expr()?;
+                match self.next() {
+                    (_, Token::RParen) => Ok(inner),
+                    (position, _) => Err(parse_error(position, "expected ')'")),
+                }
+            }
+            Token::Operator(OperationType::Subtraction) => match self.next() {
+                (_, Token::Number(n)) => Ok(Expr::Number(-n)),
+                (position, _) => Err(parse_error(position, "expected a number")),
+            },
+            _ => Err(parse_error(position, "expected a number")),
+        }
+    }
+
+    fn peek(&self) -> (usize, Token) {
+        self.tokens[self.pos]
+    }
+
+    fn next(&mut self) -> (usize, Token) {
+        let token = self.peek();
+        if !matches!(token.1, Token::End) {
+            self.pos += 1;
+        }
+        token
+    }
+}
+
+// Addition and subtraction cost 1, multiplication 3
+fn default_op_costs() -> BTreeMap<OperationType, u64> {
+    BTreeMap::from([
+        (OperationType::Addition, 1),
+        (OperationType::Subtraction, 1),
+        (OperationType::Multiplication, 3),
+    ])
+}
+
+fn parse_error(position: usize, message: &str) -> CalcError {
+    CalcError::ParseError {
+        position,
+        message: message.to_string(),
+    }
+}
@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod calculator_tests {
     use crate::calculator::*;
+    use std::collections::BTreeMap;
     use rand::Rng;
 
     #[test]
@@ -260,6 +261,241 @@ mod calculator_tests {
         calculator.subtraction(20, 7);
         assert_eq!(calculator.show_history(), "0: 20 - 7 = 13\n");
     }
+
+    #[test]
+    fn run_stream_matches_individual_calls() {
+        let ops = (0..20i64).map(|i| {
+            let operation_type = match i % 3 {
+                0 => OperationType::Addition,
+                1 => OperationType::Subtraction,
+                _ => OperationType::Multiplication,
+            };
+            (operation_type, i * 7 - 40, i + 3)
+        });
+
+        let mut streamed = Calculator::new();
+        let results = streamed.run_stream(ops.clone());
+
+        let mut manual = Calculator::new();
+        let expected: Vec<Option<i64>> = ops
+            .map(|(operation_type, x, y)| match operation_type {
+                OperationType::Addition => manual.addition(x, y),
+                OperationType::Subtraction => manual.subtraction(x, y),
+                OperationType::Multiplication => manual.multiplication(x, y),
+            })
+            .collect();
+
+        assert_eq!(results, expected);
+        assert_eq!(streamed.show_history(), manual.show_history());
+    }
+
+    #[test]
+    fn run_stream_skips_overflow_in_history() {
+        let mut calculator = Calculator::new();
+        let results = calculator.run_stream(vec![
+            (OperationType::Addition, 2, 3),
+            (OperationType::Addition, i64::MAX, 1),
+            (OperationType::Multiplication, 4, 5),
+        ]);
+
+        assert_eq!(results, vec![Some(5), None, Some(20)]);
+        assert_eq!(calculator.show_history(), "0: 2 + 3 = 5\n1: 4 * 5 = 20\n");
+    }
+
+    #[test]
+    fn running_totals_match_prefix_sums() {
+        let mut calculator = Calculator::new();
+        calculator.addition(2, 3);
+        calculator.subtraction(10, 4);
+        calculator.addition(i64::MAX - 20, 5);
+        calculator.multiplication(i64::MAX / 2, 2);
+        calculator.subtraction(0, 9);
+
+        let expected = vec![Some(5), Some(11), Some(i64::MAX - 4), None, None];
+        assert_eq!(calculator.running_totals(), expected);
+        assert!(Calculator::new().running_totals().is_empty());
+    }
+
+    #[test]
+    fn validate_expr_accepts_well_formed() {
+        for expr in ["7", "-7", "2 + 3", "2+3*4", " 10 - -2 * 3 ", "1 * 2 * 3 - 4 + 5"] {
+            assert_eq!(Calculator::validate_expr(expr), Ok(()), "{}", expr);
+        }
+    }
+
+    #[test]
+    fn validate_expr_reports_position() {
+        let cases = [
+            ("", 0, "expected a number"),
+            ("2 +", 3, "expected a number"),
+            ("2 + * 3", 4, "expected a number"),
+            ("2 3", 2, "expected an operator"),
+            ("4 * 5 6", 6, "expected an operator"),
+            ("2 / 3", 2, "unexpected character '/'"),
+            ("99999999999999999999", 0, "number out of range"),
+        ];
+        for (expr, position, message) in cases {
+            assert_eq!(
+                Calculator::validate_expr(expr),
+                Err(CalcError::ParseError {
+                    position,
+                    message: message.to_string()
+                }),
+                "{}",
+                expr
+            );
+        }
+    }
+
+    #[test]
+    fn validate_expr_does_not_record() {
+        let mut calculator = Calculator::new();
+        assert_eq!(Calculator::validate_expr("2 + 3 * 4"), Ok(()));
+        assert!(calculator.history.is_empty());
+
+        assert_eq!(calculator.evaluate_expr("2 + 3 * 4"), Ok(14));
+        assert_eq!(calculator.show_history(), "0: 3 * 4 = 12\n1: 2 + 12 = 14\n");
+        assert_eq!(calculator.evaluate_expr("9223372036854775807 + 1"), Err(CalcError::Overflow));
+    }
+
+    #[test]
+    fn max_history_evicts_oldest() {
+        let mut calculator = Calculator::with_max_history(2);
+        calculator.addition(1, 2);
+        calculator.subtraction(10, 4);
+        calculator.multiplication(3, 5);
+
+        assert_eq!(calculator.history.len(), 2);
+        assert_eq!(calculator.show_history(), "0: 10 - 4 = 6\n1: 3 * 5 = 15\n");
+
+        // indices refer to the current window
+        assert_eq!(calculator.repeat(0), Some(6));
+        assert_eq!(calculator.show_history(), "0: 3 * 5 = 15\n1: 10 - 4 = 6\n");
+
+        // overflows are not recorded and evict nothing
+        assert_eq!(calculator.addition(i64::MAX, 1), None);
+        assert_eq!(calculator.show_history(), "0: 3 * 5 = 15\n1: 10 - 4 = 6\n");
+    }
+
+    #[test]
+    fn max_history_applies_to_streams() {
+        let mut calculator = Calculator::with_max_history(3);
+        calculator.run_stream((1..=10).map(|i| (OperationType::Addition, i, i)));
+
+        assert_eq!(calculator.show_history(), "0: 8 + 8 = 16\n1: 9 + 9 = 18\n2: 10 + 10 = 20\n");
+        assert_eq!(Calculator::new().max_history, None);
+    }
+
+    #[test]
+    fn result_histogram_buckets_results() {
+        let mut calculator = Calculator::new();
+        calculator.addition(2, 3);
+        calculator.addition(4, 4);
+        calculator.multiplication(3, 4);
+        calculator.subtraction(0, 3);
+        calculator.subtraction(0, 5);
+        calculator.subtraction(0, 6);
+
+        let histogram = calculator.result_histogram(5);
+        let expected = BTreeMap::from([(-5, 2), (0, 1), (5, 2), (10, 1)]);
+        assert_eq!(histogram, expected);
+
+        let singles = calculator.result_histogram(1);
+        assert_eq!(singles.len(), 6);
+        assert_eq!(singles.values().sum::<usize>(), 6);
+    }
+
+    #[test]
+    fn result_histogram_edge_cases() {
+        assert!(Calculator::new().result_histogram(10).is_empty());
+
+        let mut calculator = Calculator::new();
+        calculator.addition(1, 1);
+        calculator.subtraction(0, 1);
+        calculator.subtraction(i64::MIN + 1, 1);
+        let histogram = calculator.result_histogram(3);
+        assert_eq!(histogram, BTreeMap::from([(i64::MIN / 3 * 3, 1), (0, 2)]));
+    }
+
+    #[test]
+    #[should_panic(expected = "bucket_size must be positive")]
+    fn result_histogram_rejects_zero_bucket_size() {
+        Calculator::new().result_histogram(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "bucket_size must be positive")]
+    fn result_histogram_rejects_negative_bucket_size() {
+        Calculator::new().result_histogram(-5);
+    }
+
+    #[test]
+    fn evaluate_expr_with_parentheses() {
+        let mut calculator = Calculator::new();
+        assert_eq!(calculator.evaluate_expr("(2 + 3) * 4"), Ok(20));
+        assert_eq!(calculator.show_history(), "0: 2 + 3 = 5\n1: 5 * 4 = 20\n");
+
+        assert_eq!(calculator.evaluate_expr("2 + 3 * 4"), Ok(14));
+        assert_eq!(calculator.evaluate_expr("2 * (3 + 4) - (5 - 1)"), Ok(10));
+        assert_eq!(calculator.evaluate_expr("((1 + 2) * (3 - -4)) * 2"), Ok(42));
+        assert_eq!(calculator.evaluate_expr("(((7)))"), Ok(7));
+        assert_eq!(calculator.evaluate_expr("10 - (2 - 3)"), Ok(11));
+    }
+
+    #[test]
+    fn evaluate_expr_rejects_unbalanced_parentheses() {
+        let cases = [
+            ("(2 + 3", 6, "expected ')'"),
+            ("((1) * 2", 8, "expected ')'"),
+            ("2 + 3)", 5, "unmatched ')'"),
+            ("(1))", 3, "unmatched ')'"),
+            ("()", 1, "expected a number"),
+            ("2 (3)", 2, "expected an operator"),
+        ];
+        let mut calculator = Calculator::new();
+        for (expr, position, message) in cases {
+            assert_eq!(
+                calculator.evaluate_expr(expr),
+                Err(CalcError::ParseError {
+                    position,
+                    message: message.to_string()
+                }),
+                "{}",
+                expr
+            );
+        }
+        assert!(calculator.history.is_empty());
+    }
+
+    #[test]
+    fn total_cost_uses_default_weights() {
+        let mut calculator = Calculator::new();
+        assert_eq!(calculator.total_cost(), 0);
+
+        calculator.addition(1, 2);
+        calculator.subtraction(5, 3);
+        calculator.multiplication(2, 4);
+        calculator.multiplication(3, 3);
+        calculator.addition(i64::MAX, 1);
+        assert_eq!(calculator.total_cost(), 1 + 1 + 3 + 3);
+    }
+
+    #[test]
+    fn total_cost_uses_configured_weights() {
+        let mut calculator = Calculator::with_max_history(2);
+        calculator.set_op_cost(OperationType::Multiplication, 10);
+        calculator.op_costs.remove(&OperationType::Subtraction);
+
+        calculator.addition(1, 2);
+        calculator.subtraction(5, 3);
+        calculator.multiplication(2, 4);
+        // only the last two operations are kept
+        assert_eq!(calculator.total_cost(), 10);
+
+        calculator.set_op_cost(OperationType::Addition, u64::MAX);
+        calculator.addition(1, 1);
+        assert_eq!(calculator.total_cost(), u64::MAX);
+    }
 }
 
 #[cfg(test)]
@@ -8,4 +8,38 @@ pub enum VaultError {
     Overflow,
     #[msg("Insufficient balance")]
     InsufficientBalance,
+    #[msg("Pending withdrawal is not ready yet")]
+    WithdrawalNotReady,
+    #[msg("Delegate allowance exceeded")]
+    DelegateAllowanceExceeded,
+    #[msg("Signer is not a delegate of this vault")]
+    NotADelegate,
+    #[msg("Too many vaults in one batch")]
+    BatchTooLarge,
+    #[msg("Memo commitment and deposit note must be passed together")]
+    DepositNoteMismatch,
+    #[msg("Memo does not match the commitment")]
+    MemoMismatch,
+    #[msg("Refund exceeds the recorded withdrawals")]
+    RefundExceedsWithdrawn,
+    #[msg("Deposit would exceed the vault's max balance")]
+    MaxBalanceExceeded,
+    #[msg("Recipient is not on the vault's allowlist")]
+    RecipientNotAllowed,
+    #[msg("Vault is time locked")]
+    TimeLocked,
+    #[msg("Withdrawal exceeds the vault's daily limit")]
+    DailyLimitExceeded,
+    #[msg("Fee cannot exceed 10000 basis points")]
+    FeeTooHigh,
+    #[msg("Treasury account must be passed while a fee is set")]
+    TreasuryRequired,
+    #[msg("Vault still holds funds above its rent-exempt minimum")]
+    VaultNotEmpty,
+    #[msg("Vault has a pending withdrawal")]
+    PendingWithdrawalOpen,
+    #[msg("Vault already holds the maximum number of mints")]
+    TooManyMints,
+    #[msg("Vault token accounts do not match the vault's mints")]
+    TokenAccountMismatch,
 }
\ No newline at end of file
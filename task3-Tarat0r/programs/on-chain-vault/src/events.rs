@@ -4,6 +4,7 @@ use anchor_lang::prelude::*;
 pub struct InitializeVaultEvent {
     pub vault: Pubkey,
     pub vault_authority: Pubkey,
+    pub vault_id: u64,
     pub locked: bool,
 }
 
@@ -19,6 +20,24 @@ pub struct WithdrawEvent {
     pub amount: u64,
     pub vault_authority: Pubkey,
     pub vault: Pubkey,
+    pub fee: u64,
+    pub treasury: Pubkey,
+}
+
+#[event]
+pub struct SplDepositEvent {
+    pub amount: u64,
+    pub mint: Pubkey,
+    pub user: Pubkey,
+    pub vault: Pubkey,
+}
+
+#[event]
+pub struct SplWithdrawEvent {
+    pub amount: u64,
+    pub mint: Pubkey,
+    pub vault_authority: Pubkey,
+    pub vault: Pubkey,
 }
 
 #[event]
@@ -26,4 +45,162 @@ pub struct ToggleLockEvent {
     pub vault: Pubkey,
     pub vault_authority: Pubkey,
     pub locked: bool,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct LockEvent {
+    pub vault: Pubkey,
+    pub vault_authority: Pubkey,
+}
+
+#[event]
+pub struct UnlockEvent {
+    pub vault: Pubkey,
+    pub vault_authority: Pubkey,
+}
+
+#[event]
+pub struct UnlockTsSetEvent {
+    pub vault: Pubkey,
+    pub vault_authority: Pubkey,
+    pub unlock_ts: i64,
+}
+
+#[event]
+pub struct DailyLimitSetEvent {
+    pub vault: Pubkey,
+    pub vault_authority: Pubkey,
+    pub daily_limit: u64,
+}
+
+#[event]
+pub struct FeeSetEvent {
+    pub vault: Pubkey,
+    pub vault_authority: Pubkey,
+    pub fee_bps: u16,
+    pub treasury: Pubkey,
+}
+
+#[event]
+pub struct MaxBalanceSetEvent {
+    pub vault: Pubkey,
+    pub vault_authority: Pubkey,
+    pub max_balance: u64,
+}
+
+#[event]
+pub struct WithdrawalRequestedEvent {
+    pub amount: u64,
+    pub ready_ts: i64,
+    pub vault_authority: Pubkey,
+    pub vault: Pubkey,
+}
+
+#[event]
+pub struct WithdrawalCancelledEvent {
+    pub amount: u64,
+    pub vault_authority: Pubkey,
+    pub vault: Pubkey,
+}
+
+#[event]
+pub struct SetDelegateEvent {
+    pub vault: Pubkey,
+    pub delegate: Pubkey,
+    pub recipient: Pubkey,
+    pub max_amount: u64,
+}
+
+#[event]
+pub struct DelegatedWithdrawEvent {
+    pub amount: u64,
+    pub remaining: u64,
+    pub delegate: Pubkey,
+    pub recipient: Pubkey,
+    pub vault: Pubkey,
+    pub fee: u64,
+    pub treasury: Pubkey,
+}
+
+#[event]
+pub struct RecipientsGatedEvent {
+    pub vault: Pubkey,
+    pub recipients_gated: bool,
+}
+
+#[event]
+pub struct RecipientAllowedEvent {
+    pub vault: Pubkey,
+    pub recipient: Pubkey,
+    pub allowed: bool,
+}
+
+#[event]
+pub struct WithdrawToEvent {
+    pub amount: u64,
+    pub recipient: Pubkey,
+    pub vault_authority: Pubkey,
+    pub vault: Pubkey,
+    pub fee: u64,
+    pub treasury: Pubkey,
+}
+
+#[event]
+pub struct VaultClosed {
+    pub vault: Pubkey,
+    pub vault_authority: Pubkey,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct VaultMigratedEvent {
+    pub vault: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+    pub old_len: u64,
+    pub new_len: u64,
+}
+
+#[event]
+pub struct RefundEvent {
+    pub amount: u64,
+    pub fee: u64,
+    pub recipient: Pubkey,
+    pub withdrawn: u64,
+    pub vault_authority: Pubkey,
+    pub vault: Pubkey,
+}
+
+#[event]
+pub struct Reconciliation {
+    pub expected: i128,
+    pub actual: u64,
+    pub drift: i128,
+    pub vault: Pubkey,
+}
+
+#[event]
+pub struct BalanceReported {
+    pub vault: Pubkey,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct MemoRevealedEvent {
+    pub memo: String,
+    pub amount: u64,
+    pub depositor: Pubkey,
+    pub vault: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VaultBalance {
+    pub vault: Pubkey,
+    pub lamports: u64,
+    pub withdrawable: u64,
+}
+
+#[event]
+pub struct BatchBalances {
+    pub entries: Vec<VaultBalance>,
+}
@@ -0,0 +1,50 @@
+//-------------------------------------------------------------------------------
+///
+/// Add a recipient to the vault's withdraw_to allowlist
+/// 
+/// Requirements:
+/// - Only the vault authority can allow recipients
+/// - One AllowedRecipient PDA per vault and recipient
+/// - Emit a recipient allowed event
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use crate::state::{AllowedRecipient, Vault};
+use crate::events::RecipientAllowedEvent;
+
+#[derive(Accounts)]
+pub struct AllowRecipient<'info> {
+    #[account(mut)]
+    pub vault_authority: Signer<'info>,
+    #[account(
+        seeds = [b"vault", vault_authority.key().as_ref(), &vault.vault_id.to_le_bytes()[..vault.id_seed_len()]],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    /// CHECK: only used as a seed and stored as the recipient key
+    pub recipient: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = vault_authority,
+        space = 8 + AllowedRecipient::INIT_SPACE,
+        seeds = [b"allowed_recipient", vault.key().as_ref(), recipient.key().as_ref()],
+        bump
+    )]
+    pub allowed_recipient: Account<'info, AllowedRecipient>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn _allow_recipient(ctx: Context<AllowRecipient>) -> Result<()> {
+    let allowed_recipient = &mut ctx.accounts.allowed_recipient;
+
+    allowed_recipient.vault = ctx.accounts.vault.key();
+    allowed_recipient.recipient = ctx.accounts.recipient.key();
+
+    emit!(RecipientAllowedEvent {
+        vault: allowed_recipient.vault,
+        recipient: allowed_recipient.recipient,
+        allowed: true,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+//-------------------------------------------------------------------------------
+///
+/// Report the balances of several vaults in one call
+/// 
+/// - Vaults are passed as `remaining_accounts`, at most `MAX_BATCH_VAULTS`
+/// - Each account must be a `Vault` owned by this program
+/// - Withdrawable is the balance above rent exemption, zero while locked
+/// - Read only, the result is emitted as a single event
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use crate::state::{Vault, MAX_BATCH_VAULTS};
+use crate::errors::VaultError;
+use crate::events::{BatchBalances, VaultBalance};
+
+#[derive(Accounts)]
+pub struct QueryBalances {}
+
+pub fn _batch_balances<'info>(ctx: Context<'_, '_, 'info, 'info, QueryBalances>) -> Result<()> {
+    require!(ctx.remaining_accounts.len() <= MAX_BATCH_VAULTS, VaultError::BatchTooLarge);
+
+    let rent_exempt = Rent::get()?.minimum_balance(8 + Vault::INIT_SPACE);
+    let mut entries = Vec::with_capacity(ctx.remaining_accounts.len());
+
+    for info in ctx.remaining_accounts.iter() {
+        let vault = Account::<Vault>::try_from(info)?;
+        let lamports = info.lamports();
+        let withdrawable = if vault.locked {
+            0
+        } else {
+            lamports.saturating_sub(rent_exempt)
+        };
+
+        entries.push(VaultBalance {
+            vault: info.key(),
+            lamports,
+            withdrawable,
+        });
+    }
+
+    emit!(BatchBalances { entries });
+
+    Ok(())
+}
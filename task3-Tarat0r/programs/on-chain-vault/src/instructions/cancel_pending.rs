@@ -0,0 +1,40 @@
+//-------------------------------------------------------------------------------
+///
+/// Cancel a pending withdrawal before it is executed
+/// 
+/// - Only the vault authority can cancel
+/// - Closes the PendingWithdrawal PDA and returns its rent to the authority
+/// - No lamports leave the vault
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use crate::state::{PendingWithdrawal, Vault};
+use crate::events::WithdrawalCancelledEvent;
+
+#[derive(Accounts)]
+pub struct CancelPending<'info> {
+    #[account(mut)]
+    pub vault_authority: Signer<'info>,
+    #[account(
+        seeds = [b"vault", vault_authority.key().as_ref(), &vault.vault_id.to_le_bytes()[..vault.id_seed_len()]],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        seeds = [b"pending", vault.key().as_ref()],
+        bump,
+        close = vault_authority
+    )]
+    pub pending_withdrawal: Account<'info, PendingWithdrawal>,
+}
+
+pub fn _cancel_pending(ctx: Context<CancelPending>) -> Result<()> {
+    emit!(WithdrawalCancelledEvent {
+        amount: ctx.accounts.pending_withdrawal.amount,
+        vault_authority: ctx.accounts.vault_authority.key(),
+        vault: ctx.accounts.vault.key(),
+    });
+
+    Ok(())
+}
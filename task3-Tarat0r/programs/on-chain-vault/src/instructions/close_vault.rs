@@ -0,0 +1,96 @@
+//-------------------------------------------------------------------------------
+///
+/// Close the vault and return its rent to the vault authority
+/// 
+/// Requirements:
+/// - Only the vault authority can close the vault
+/// - The vault must be unlocked
+/// - The vault must hold no more than its rent-exempt minimum, all funds
+///   have to be withdrawn first
+/// - No PendingWithdrawal may be open, it has to be executed or cancelled first
+/// - The vault's associated token account of every mint in `vault.mints` is
+///   passed in remaining_accounts, in the same order; each must be empty and
+///   is closed with its rent going to the authority
+/// - Emit a vault closed event
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount};
+use crate::state::Vault;
+use crate::errors::VaultError;
+use crate::events::VaultClosed;
+
+#[derive(Accounts)]
+pub struct CloseVault<'info> {
+    #[account(mut)]
+    pub vault_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault", vault_authority.key().as_ref(), &vault.vault_id.to_le_bytes()[..vault.id_seed_len()]],
+        bump = vault.bump,
+        close = vault_authority,
+    )]
+    pub vault: Account<'info, Vault>,
+    /// CHECK: only checked to be empty, an open PendingWithdrawal blocks closing
+    #[account(
+        seeds = [b"pending", vault.key().as_ref()],
+        bump,
+        constraint = pending_withdrawal.data_is_empty() @ VaultError::PendingWithdrawalOpen,
+    )]
+    pub pending_withdrawal: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn _close_vault<'info>(ctx: Context<'_, '_, 'info, 'info, CloseVault<'info>>) -> Result<()> {
+    let vault = &ctx.accounts.vault;
+    let vault_info = vault.to_account_info();
+
+    require!(!vault.locked, VaultError::VaultLocked);
+    let lamports = vault_info.lamports();
+    require!(
+        lamports <= Rent::get()?.minimum_balance(vault_info.data_len()),
+        VaultError::VaultNotEmpty
+    );
+
+    require!(
+        ctx.remaining_accounts.len() == vault.mints.len(),
+        VaultError::TokenAccountMismatch
+    );
+    let vault_id = vault.vault_id.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"vault",
+        vault.vault_authority.as_ref(),
+        &vault_id[..vault.id_seed_len()],
+        &[vault.bump],
+    ]];
+    for (mint, info) in vault.mints.iter().zip(ctx.remaining_accounts.iter()) {
+        require_keys_eq!(
+            info.key(),
+            get_associated_token_address(&vault.key(), mint),
+            VaultError::TokenAccountMismatch
+        );
+        let token_account = Account::<TokenAccount>::try_from(info)?;
+        require!(token_account.amount == 0, VaultError::VaultNotEmpty);
+
+        token::close_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                CloseAccount {
+                    account: token_account.to_account_info(),
+                    destination: ctx.accounts.vault_authority.to_account_info(),
+                    authority: vault_info.clone(),
+                },
+                signer_seeds,
+            ),
+        )?;
+    }
+
+    emit!(VaultClosed {
+        vault: vault.key(),
+        vault_authority: ctx.accounts.vault_authority.key(),
+        lamports,
+    });
+
+    Ok(())
+}
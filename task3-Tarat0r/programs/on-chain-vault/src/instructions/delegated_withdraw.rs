@@ -0,0 +1,79 @@
+//-------------------------------------------------------------------------------
+///
+/// Withdraw from the vault as a delegate
+/// 
+/// - The signer must be the delegate recorded for this vault
+/// - The amount must fit into the remaining allowance, which is decremented
+/// - Funds go to the recipient fixed by the authority
+/// - The lock, balance and fee handling of withdraw still apply, the fee
+///   counts against the allowance
+/// - The amount is added to the vault authority's DepositLedger, the
+///   delegate pays for creating it
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use crate::state::{DepositLedger, Vault, WithdrawDelegate};
+use crate::errors::VaultError;
+use crate::events::DelegatedWithdrawEvent;
+
+#[derive(Accounts)]
+pub struct DelegatedWithdraw<'info> {
+    #[account(mut)]
+    pub delegate: Signer<'info>,
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        constraint = withdraw_delegate.vault == vault.key() @ VaultError::NotADelegate,
+        constraint = withdraw_delegate.delegate == delegate.key() @ VaultError::NotADelegate,
+    )]
+    pub withdraw_delegate: Account<'info, WithdrawDelegate>,
+    #[account(mut, address = withdraw_delegate.recipient)]
+    pub recipient: SystemAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = delegate,
+        space = 8 + DepositLedger::INIT_SPACE,
+        seeds = [b"deposit_ledger", vault.key().as_ref(), vault.vault_authority.as_ref()],
+        bump
+    )]
+    pub deposit_ledger: Account<'info, DepositLedger>,
+    // only needed while the vault charges a fee
+    #[account(mut, address = vault.treasury)]
+    pub treasury: Option<SystemAccount<'info>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn _delegated_withdraw(ctx: Context<DelegatedWithdraw>, amount: u64) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let withdraw_delegate = &mut ctx.accounts.withdraw_delegate;
+    let vault_info = vault.to_account_info();
+    let recipient_info = ctx.accounts.recipient.to_account_info();
+    let treasury_info = ctx.accounts.treasury.as_ref().map(|treasury| treasury.to_account_info());
+
+    require!(amount <= withdraw_delegate.remaining, VaultError::DelegateAllowanceExceeded);
+
+    withdraw_delegate.remaining -= amount;
+
+    let fee = vault.pay_out(&vault_info, &recipient_info, treasury_info.as_ref(), amount)?;
+
+    let ledger = &mut ctx.accounts.deposit_ledger;
+    ledger.vault = vault.key();
+    ledger.user = vault.vault_authority;
+    ledger.withdrawn = ledger
+    .withdrawn
+    .checked_add(amount)
+    .ok_or(VaultError::Overflow)?;
+
+    emit!(DelegatedWithdrawEvent {
+        amount,
+        remaining: withdraw_delegate.remaining,
+        delegate: ctx.accounts.delegate.key(),
+        recipient: recipient_info.key(),
+        vault: vault.key(),
+        fee,
+        treasury: vault.treasury,
+    });
+
+    Ok(())
+}
@@ -5,27 +5,51 @@
 /// Requirements:
 /// - Verify that the user has enough balance to deposit
 /// - Verify that the vault is not locked
+/// - Verify that the deposit keeps the vault balance within max_balance
 /// - Transfer lamports from user to vault using CPI (Cross-Program Invocation)
 /// - Emit a deposit event after successful transfer
+/// - Add the amount to the user's DepositLedger
+/// - Optionally record a memo commitment on a DepositNote PDA
 /// 
 ///-------------------------------------------------------------------------------
 use anchor_lang:: prelude::*;
 use anchor_lang::solana_program::program::invoke;
 use anchor_lang::solana_program::system_instruction::transfer;
-use crate::state::Vault;
+use crate::state::{DepositLedger, DepositNote, Vault};
 use crate::errors::VaultError;
 use crate::events::DepositEvent;
 
 #[derive(Accounts)]
+#[instruction(amount: u64, memo_commitment: Option<[u8; 32]>)]
 pub struct Deposit<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [b"vault", vault.vault_authority.as_ref(), &vault.vault_id.to_le_bytes()[..vault.id_seed_len()]],
+        bump = vault.bump,
+    )]
     pub vault: Account<'info, Vault>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + DepositLedger::INIT_SPACE,
+        seeds = [b"deposit_ledger", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub deposit_ledger: Account<'info, DepositLedger>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + DepositNote::INIT_SPACE,
+        seeds = [b"deposit_note", vault.key().as_ref(), &memo_commitment.unwrap_or_default()],
+        bump
+    )]
+    pub deposit_note: Option<Account<'info, DepositNote>>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn _deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+pub fn _deposit(ctx: Context<Deposit>, amount: u64, memo_commitment: Option<[u8; 32]>) -> Result<()> {
 
     let vault = &mut ctx.accounts.vault;
     let user = &mut ctx.accounts.user; 
@@ -37,9 +61,35 @@ pub fn _deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
     require!(from.lamports() >= amount, VaultError::InsufficientBalance);
 
     let to = vault.to_account_info();
+    let new_balance = to.lamports().checked_add(amount).ok_or(VaultError::Overflow)?;
+    require!(new_balance <= vault.max_balance, VaultError::MaxBalanceExceeded);
+
     let ix = transfer(&from.key(), &to.key(), amount);
     
     invoke(&ix, &[from.clone(), to.clone()])?;
+    vault.total_deposited = vault
+    .total_deposited
+    .checked_add(amount)
+    .ok_or(VaultError::Overflow)?;
+
+    let ledger = &mut ctx.accounts.deposit_ledger;
+    ledger.vault = vault.key();
+    ledger.user = user.key();
+    ledger.deposited = ledger
+    .deposited
+    .checked_add(amount)
+    .ok_or(VaultError::Overflow)?;
+
+    match (memo_commitment, ctx.accounts.deposit_note.as_mut()) {
+        (Some(memo_commitment), Some(note)) => {
+            note.vault = vault.key();
+            note.depositor = user.key();
+            note.amount = amount;
+            note.memo_commitment = memo_commitment;
+        }
+        (None, None) => {}
+        _ => return err!(VaultError::DepositNoteMismatch),
+    }
 
     emit!(DepositEvent{
         amount,
@@ -0,0 +1,85 @@
+//-------------------------------------------------------------------------------
+///
+/// Deposit SPL tokens into the vault
+/// 
+/// Requirements:
+/// - Verify that the vault is not locked
+/// - Verify that the user holds enough tokens to deposit
+/// - Transfer tokens from the user's token account to the vault's associated
+///   token account for the mint, creating it if needed
+/// - Record the mint on the vault, at most MAX_VAULT_MINTS distinct mints
+/// - Emit an SPL deposit event after successful transfer
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked};
+use crate::state::{Vault, MAX_VAULT_MINTS};
+use crate::errors::VaultError;
+use crate::events::SplDepositEvent;
+
+#[derive(Accounts)]
+pub struct DepositSpl<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault", vault.vault_authority.as_ref(), &vault.vault_id.to_le_bytes()[..vault.id_seed_len()]],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    pub mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = user,
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn _deposit_spl(ctx: Context<DepositSpl>, amount: u64) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+
+    require!(!vault.locked, VaultError::VaultLocked);
+    require!(amount > 0, VaultError::InsufficientBalance);
+    require!(ctx.accounts.user_token_account.amount >= amount, VaultError::InsufficientBalance);
+
+    let mint = ctx.accounts.mint.key();
+    if !vault.mints.contains(&mint) {
+        require!(vault.mints.len() < MAX_VAULT_MINTS, VaultError::TooManyMints);
+        vault.mints.push(mint);
+    }
+
+    token::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.vault_token_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    emit!(SplDepositEvent {
+        amount,
+        mint,
+        user: ctx.accounts.user.key(),
+        vault: vault.key(),
+    });
+
+    Ok(())
+}
\ No newline at end of file
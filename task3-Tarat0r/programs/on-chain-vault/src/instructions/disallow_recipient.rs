@@ -0,0 +1,41 @@
+//-------------------------------------------------------------------------------
+///
+/// Remove a recipient from the vault's withdraw_to allowlist
+/// 
+/// Requirements:
+/// - Only the vault authority can disallow recipients
+/// - The AllowedRecipient PDA is closed and its rent returned to the authority
+/// - Emit a recipient allowed event with allowed = false
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use crate::state::{AllowedRecipient, Vault};
+use crate::events::RecipientAllowedEvent;
+
+#[derive(Accounts)]
+pub struct DisallowRecipient<'info> {
+    #[account(mut)]
+    pub vault_authority: Signer<'info>,
+    #[account(
+        seeds = [b"vault", vault_authority.key().as_ref(), &vault.vault_id.to_le_bytes()[..vault.id_seed_len()]],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        close = vault_authority,
+        seeds = [b"allowed_recipient", vault.key().as_ref(), allowed_recipient.recipient.as_ref()],
+        bump
+    )]
+    pub allowed_recipient: Account<'info, AllowedRecipient>,
+}
+
+pub fn _disallow_recipient(ctx: Context<DisallowRecipient>) -> Result<()> {
+    emit!(RecipientAllowedEvent {
+        vault: ctx.accounts.vault.key(),
+        recipient: ctx.accounts.allowed_recipient.recipient,
+        allowed: false,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+//-------------------------------------------------------------------------------
+///
+/// Execute a previously requested withdrawal once its delay has passed
+/// 
+/// - Only the vault authority can execute
+/// - Rejects with WithdrawalNotReady while `now < ready_ts`
+/// - Applies the same lock, balance and fee handling as withdraw
+/// - Adds the amount to the vault authority's DepositLedger
+/// - Closes the PendingWithdrawal PDA and returns its rent to the authority
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use crate::state::{DepositLedger, PendingWithdrawal, Vault};
+use crate::errors::VaultError;
+use crate::events::WithdrawEvent;
+
+#[derive(Accounts)]
+pub struct ExecutePending<'info> {
+    #[account(mut)]
+    pub vault_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault", vault_authority.key().as_ref(), &vault.vault_id.to_le_bytes()[..vault.id_seed_len()]],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        seeds = [b"pending", vault.key().as_ref()],
+        bump,
+        close = vault_authority
+    )]
+    pub pending_withdrawal: Account<'info, PendingWithdrawal>,
+    #[account(
+        init_if_needed,
+        payer = vault_authority,
+        space = 8 + DepositLedger::INIT_SPACE,
+        seeds = [b"deposit_ledger", vault.key().as_ref(), vault_authority.key().as_ref()],
+        bump
+    )]
+    pub deposit_ledger: Account<'info, DepositLedger>,
+    // only needed while the vault charges a fee
+    #[account(mut, address = vault.treasury)]
+    pub treasury: Option<SystemAccount<'info>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn _execute_pending(ctx: Context<ExecutePending>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let pending = &ctx.accounts.pending_withdrawal;
+    let vault_info = vault.to_account_info();
+    let authority_info = ctx.accounts.vault_authority.to_account_info();
+    let treasury_info = ctx.accounts.treasury.as_ref().map(|treasury| treasury.to_account_info());
+    let amount = pending.amount;
+
+    require!(
+        Clock::get()?.unix_timestamp >= pending.ready_ts,
+        VaultError::WithdrawalNotReady
+    );
+
+    let fee = vault.pay_out(&vault_info, &authority_info, treasury_info.as_ref(), amount)?;
+
+    let ledger = &mut ctx.accounts.deposit_ledger;
+    ledger.vault = vault.key();
+    ledger.user = ctx.accounts.vault_authority.key();
+    ledger.withdrawn = ledger
+    .withdrawn
+    .checked_add(amount)
+    .ok_or(VaultError::Overflow)?;
+
+    emit!(WithdrawEvent {
+        amount,
+        vault_authority: ctx.accounts.vault_authority.key(),
+        vault: vault.key(),
+        fee,
+        treasury: vault.treasury,
+    });
+
+    Ok(())
+}
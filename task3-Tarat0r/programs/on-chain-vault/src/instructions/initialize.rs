@@ -15,10 +15,11 @@
 /// 
 ///-------------------------------------------------------------------------------
 use anchor_lang::prelude::*;
-use crate::state::Vault;
+use crate::state::{Vault, VAULT_VERSION};
 use crate::events::InitializeVaultEvent;
 
 #[derive(Accounts)]
+#[instruction(vault_id: u64)]
 pub struct InitializeVault<'info> {
     #[account(mut)]
     pub vault_authority: Signer<'info>,
@@ -27,22 +28,38 @@ pub struct InitializeVault<'info> {
         payer = vault_authority, 
         // space = discriminant + account size
         space = 8 + Vault::INIT_SPACE,
-        seeds = [b"vault", vault_authority.key().as_ref()],
+        seeds = [b"vault", vault_authority.key().as_ref(), &vault_id.to_le_bytes()],
         bump
     )]
     pub vault: Account<'info, Vault>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn _init_vault(ctx: Context<InitializeVault>, locked: bool) -> Result<()> {
+pub fn _initialize_vault(ctx: Context<InitializeVault>, vault_id: u64, locked: bool, max_balance: u64) -> Result<()> {
   let vault = &mut ctx.accounts.vault;
 
+  vault.vault_id = vault_id;
   vault.vault_authority = ctx.accounts.vault_authority.key();
   vault.locked = locked;
+  vault.total_deposited = 0;
+  vault.total_withdrawn = 0;
+  vault.max_balance = max_balance;
+  vault.recipients_gated = false;
+  vault.version = VAULT_VERSION;
+  vault.bump = ctx.bumps.vault;
+  vault.unlock_ts = 0;
+  vault.daily_limit = 0;
+  vault.window_start_ts = 0;
+  vault.withdrawn_in_window = 0;
+  vault.fee_bps = 0;
+  vault.treasury = Pubkey::default();
+  vault.legacy_seeds = false;
+  vault.mints = Vec::new();
   
   emit!(InitializeVaultEvent {
     vault: vault.key(),
     vault_authority: vault.vault_authority,
+    vault_id,
     locked,
   });
 
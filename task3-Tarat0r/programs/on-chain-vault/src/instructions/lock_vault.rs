@@ -0,0 +1,37 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Lock the vault
+/// 
+/// Requirements:
+/// - Set the vault to locked, deposits and withdrawals are rejected until it is unlocked
+/// - Only the vault authority should be able to lock the vault
+/// - Emit a lock event after successful state change
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use crate::state::Vault;
+use crate::events::LockEvent;
+
+#[derive(Accounts)]
+pub struct LockVault<'info> {
+    pub vault_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault", vault_authority.key().as_ref(), &vault.vault_id.to_le_bytes()[..vault.id_seed_len()]],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+}
+
+pub fn _lock_vault(ctx: Context<LockVault>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+
+    vault.locked = true;
+
+    emit!(LockEvent {
+        vault: vault.key(),
+        vault_authority: ctx.accounts.vault_authority.key(),
+    });
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,103 @@
+//-------------------------------------------------------------------------------
+///
+/// Migrate a vault account to the current Vault layout
+/// 
+/// Requirements:
+/// - The account must be owned by this program and carry the Vault discriminator
+/// - Grow the account to the current Vault space, the payer tops up the rent
+///   so the lamports already held stay withdrawable
+/// - Fields missing from the old layout start zeroed, except max_balance
+///   which starts uncapped and bump which is derived from the vault seeds
+/// - Vaults from before vault ids keep their `["vault", vault_authority]`
+///   address through legacy_seeds
+/// - mints starts empty, the next deposit_spl of a mint records it again
+/// - Set version to VAULT_VERSION, a vault already on it is left untouched
+/// - Emit a vault migrated event when the vault was migrated
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::{Vault, VAULT_VERSION};
+use crate::events::VaultMigratedEvent;
+
+/// End of `max_balance` in the account data (discriminator, vault_authority,
+/// locked, total_deposited, total_withdrawn, max_balance).
+const MAX_BALANCE_END: usize = 8 + 32 + 1 + 8 + 8 + 8;
+
+#[derive(Accounts)]
+pub struct MigrateVault<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: may still have an older layout than Vault, the discriminator is checked in the handler
+    #[account(mut, owner = crate::ID)]
+    pub vault: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn _migrate_vault(ctx: Context<MigrateVault>) -> Result<()> {
+    let vault_info = ctx.accounts.vault.to_account_info();
+    {
+        let data = vault_info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == *Vault::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+    }
+
+    let old_len = vault_info.data_len();
+    let new_len = 8 + Vault::INIT_SPACE;
+    if old_len < new_len {
+        let rent = Rent::get()?;
+        let missing = rent
+            .minimum_balance(new_len)
+            .saturating_sub(rent.minimum_balance(old_len).min(vault_info.lamports()));
+        if missing > 0 {
+            transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: vault_info.clone(),
+                    },
+                ),
+                missing,
+            )?;
+        }
+        // zero-extends the data
+        vault_info.resize(new_len)?;
+    }
+
+    let mut vault = Vault::try_deserialize(&mut &vault_info.try_borrow_data()?[..])?;
+    if vault.version >= VAULT_VERSION {
+        return Ok(());
+    }
+
+    // accounts from before versioning count as version 1
+    let from_version = vault.version.max(1);
+    if old_len < MAX_BALANCE_END {
+        vault.max_balance = u64::MAX;
+    }
+    if from_version < 3 {
+        vault.legacy_seeds = true;
+    }
+    if from_version < 4 {
+        let (address, bump) = Pubkey::find_program_address(
+            &[b"vault", vault.vault_authority.as_ref(), &vault.vault_id.to_le_bytes()[..vault.id_seed_len()]],
+            &crate::ID,
+        );
+        require_keys_eq!(address, vault_info.key(), ErrorCode::ConstraintSeeds);
+        vault.bump = bump;
+    }
+    vault.version = VAULT_VERSION;
+    vault.try_serialize(&mut &mut vault_info.try_borrow_mut_data()?[..])?;
+
+    emit!(VaultMigratedEvent {
+        vault: vault_info.key(),
+        from_version,
+        to_version: VAULT_VERSION,
+        old_len: old_len as u64,
+        new_len: new_len as u64,
+    });
+
+    Ok(())
+}
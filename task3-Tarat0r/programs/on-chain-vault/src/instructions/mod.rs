@@ -2,8 +2,56 @@ mod initialize;
 mod deposit;
 mod withdraw;
 mod toggle_lock;
+mod lock_vault;
+mod unlock_vault;
+mod request_withdrawal;
+mod execute_pending;
+mod cancel_pending;
+mod set_delegate;
+mod delegated_withdraw;
+mod batch_balances;
+mod reveal_memo;
+mod refund;
+mod reconcile;
+mod vault_balance;
+mod set_max_balance;
+mod set_unlock_ts;
+mod set_daily_limit;
+mod set_fee;
+mod set_recipients_gated;
+mod allow_recipient;
+mod disallow_recipient;
+mod withdraw_to;
+mod migrate_vault;
+mod deposit_spl;
+mod withdraw_spl;
+mod close_vault;
 
 pub use initialize::*;
 pub use deposit::*;
 pub use withdraw::*;
-pub use toggle_lock::*;
\ No newline at end of file
+pub use toggle_lock::*;
+pub use lock_vault::*;
+pub use unlock_vault::*;
+pub use request_withdrawal::*;
+pub use execute_pending::*;
+pub use cancel_pending::*;
+pub use set_delegate::*;
+pub use delegated_withdraw::*;
+pub use batch_balances::*;
+pub use reveal_memo::*;
+pub use refund::*;
+pub use reconcile::*;
+pub use vault_balance::*;
+pub use set_max_balance::*;
+pub use set_unlock_ts::*;
+pub use set_daily_limit::*;
+pub use set_fee::*;
+pub use set_recipients_gated::*;
+pub use allow_recipient::*;
+pub use disallow_recipient::*;
+pub use withdraw_to::*;
+pub use migrate_vault::*;
+pub use deposit_spl::*;
+pub use withdraw_spl::*;
+pub use close_vault::*;
\ No newline at end of file
@@ -0,0 +1,37 @@
+//-------------------------------------------------------------------------------
+///
+/// Compare the vault's balance with what its recorded totals imply
+/// 
+/// - Expected is rent exemption + total_deposited - total_withdrawn
+/// - A nonzero drift means lamports moved outside the program. Expected and
+///   drift are signed, withdrawing out-of-band funding can push expected below 0
+/// - Read only, the result is emitted as an event
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use crate::state::Vault;
+use crate::events::Reconciliation;
+
+#[derive(Accounts)]
+pub struct Reconcile<'info> {
+    pub vault: Account<'info, Vault>,
+}
+
+pub fn _reconcile(ctx: Context<Reconcile>) -> Result<()> {
+    let vault = &ctx.accounts.vault;
+    let vault_info = vault.to_account_info();
+
+    let rent_exempt = Rent::get()?.minimum_balance(vault_info.data_len());
+    let expected = rent_exempt as i128 + vault.total_deposited as i128 - vault.total_withdrawn as i128;
+    let actual = vault_info.lamports();
+    let drift = actual as i128 - expected;
+
+    emit!(Reconciliation {
+        expected,
+        actual,
+        drift,
+        vault: vault.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+//-------------------------------------------------------------------------------
+///
+/// Refund previously withdrawn lamports to a recipient
+/// 
+/// - Only the vault authority can refund, the vault's treasury must co-sign
+///   while a fee is set
+/// - The amount cannot exceed the authority's net withdrawals on its DepositLedger
+/// - The recipient gets the full amount: the treasury credits back the fee it
+///   took on that amount at the vault's current fee_bps, the authority pays the rest
+/// - Lowers the ledger's withdrawn, the vault's balance and totals are untouched
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::solana_program::system_instruction::transfer;
+use crate::state::{DepositLedger, Vault, MAX_FEE_BPS};
+use crate::errors::VaultError;
+use crate::events::RefundEvent;
+
+#[derive(Accounts)]
+pub struct Refund<'info> {
+    #[account(mut)]
+    pub vault_authority: Signer<'info>,
+    #[account(
+        seeds = [b"vault", vault_authority.key().as_ref(), &vault.vault_id.to_le_bytes()[..vault.id_seed_len()]],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        seeds = [b"deposit_ledger", vault.key().as_ref(), vault_authority.key().as_ref()],
+        bump
+    )]
+    pub deposit_ledger: Account<'info, DepositLedger>,
+    #[account(mut)]
+    pub recipient: SystemAccount<'info>,
+    // only needed while the vault charges a fee
+    #[account(mut, address = vault.treasury)]
+    pub treasury: Option<Signer<'info>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn _refund(ctx: Context<Refund>, amount: u64) -> Result<()> {
+    let vault = &ctx.accounts.vault;
+    let authority = &ctx.accounts.vault_authority;
+    let recipient = ctx.accounts.recipient.to_account_info();
+    let ledger = &mut ctx.accounts.deposit_ledger;
+
+    require!(amount <= ledger.withdrawn, VaultError::RefundExceedsWithdrawn);
+
+    let fee: u64 = (amount as u128)
+    .checked_mul(vault.fee_bps as u128)
+    .map(|fee| fee / MAX_FEE_BPS as u128)
+    .and_then(|fee| fee.try_into().ok())
+    .ok_or(VaultError::Overflow)?;
+    let from_authority = amount.checked_sub(fee).ok_or(VaultError::Overflow)?;
+
+    let from = authority.to_account_info();
+    require!(from.lamports() >= from_authority, VaultError::InsufficientBalance);
+    let ix = transfer(&from.key(), &recipient.key(), from_authority);
+    invoke(&ix, &[from.clone(), recipient.clone()])?;
+
+    if fee > 0 {
+        let treasury = ctx
+        .accounts
+        .treasury
+        .as_ref()
+        .ok_or(VaultError::TreasuryRequired)?
+        .to_account_info();
+        require!(treasury.lamports() >= fee, VaultError::InsufficientBalance);
+        let ix = transfer(&treasury.key(), &recipient.key(), fee);
+        invoke(&ix, &[treasury.clone(), recipient.clone()])?;
+    }
+
+    ledger.withdrawn -= amount;
+
+    emit!(RefundEvent {
+        amount,
+        fee,
+        recipient: recipient.key(),
+        withdrawn: ledger.withdrawn,
+        vault_authority: authority.key(),
+        vault: vault.key(),
+    });
+
+    Ok(())
+}
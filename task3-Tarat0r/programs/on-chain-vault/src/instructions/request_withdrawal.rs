@@ -0,0 +1,60 @@
+//-------------------------------------------------------------------------------
+///
+/// Request a time-delayed withdrawal from the vault
+/// 
+/// - Only the vault authority can request a withdrawal
+/// - Records the amount and `ready_ts = now + WITHDRAWAL_DELAY` in a PendingWithdrawal PDA
+/// - Only one pending withdrawal can exist per vault at a time
+/// - The transfer itself happens later in execute_pending
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use crate::state::{PendingWithdrawal, Vault, WITHDRAWAL_DELAY};
+use crate::errors::VaultError;
+use crate::events::WithdrawalRequestedEvent;
+
+#[derive(Accounts)]
+pub struct RequestWithdrawal<'info> {
+    #[account(mut)]
+    pub vault_authority: Signer<'info>,
+    #[account(
+        seeds = [b"vault", vault_authority.key().as_ref(), &vault.vault_id.to_le_bytes()[..vault.id_seed_len()]],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(
+        init,
+        payer = vault_authority,
+        space = 8 + PendingWithdrawal::INIT_SPACE,
+        seeds = [b"pending", vault.key().as_ref()],
+        bump
+    )]
+    pub pending_withdrawal: Account<'info, PendingWithdrawal>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn _request_withdrawal(ctx: Context<RequestWithdrawal>, amount: u64) -> Result<()> {
+    let vault = &ctx.accounts.vault;
+    let pending = &mut ctx.accounts.pending_withdrawal;
+
+    require!(!vault.locked, VaultError::VaultLocked);
+    require!(amount > 0, VaultError::InsufficientBalance);
+
+    let ready_ts = Clock::get()?
+        .unix_timestamp
+        .checked_add(WITHDRAWAL_DELAY)
+        .ok_or(VaultError::Overflow)?;
+
+    pending.vault = vault.key();
+    pending.amount = amount;
+    pending.ready_ts = ready_ts;
+
+    emit!(WithdrawalRequestedEvent {
+        amount,
+        ready_ts,
+        vault_authority: ctx.accounts.vault_authority.key(),
+        vault: vault.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+//-------------------------------------------------------------------------------
+///
+/// Reveal the memo behind a deposit's commitment
+/// 
+/// - Only the original depositor can reveal
+/// - hash(memo) must equal the stored memo_commitment
+/// - The memo is only emitted, the note account is left unchanged
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::state::DepositNote;
+use crate::errors::VaultError;
+use crate::events::MemoRevealedEvent;
+
+#[derive(Accounts)]
+pub struct RevealMemo<'info> {
+    pub depositor: Signer<'info>,
+    #[account(
+        has_one = depositor,
+        seeds = [b"deposit_note", deposit_note.vault.as_ref(), &deposit_note.memo_commitment],
+        bump,
+    )]
+    pub deposit_note: Account<'info, DepositNote>,
+}
+
+pub fn _reveal_memo(ctx: Context<RevealMemo>, memo: String) -> Result<()> {
+    let note = &ctx.accounts.deposit_note;
+
+    require!(
+        hash(memo.as_bytes()).to_bytes() == note.memo_commitment,
+        VaultError::MemoMismatch
+    );
+
+    emit!(MemoRevealedEvent {
+        memo,
+        amount: note.amount,
+        depositor: note.depositor,
+        vault: note.vault,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+//-------------------------------------------------------------------------------
+///
+/// Set the daily withdrawal limit of the vault
+/// 
+/// Requirements:
+/// - Only the vault authority can change the limit
+/// - 0 removes the limit
+/// - Lamports already withdrawn in the current window keep counting against it
+/// - Emit a daily limit event after the change
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use crate::state::Vault;
+use crate::events::DailyLimitSetEvent;
+
+#[derive(Accounts)]
+pub struct SetDailyLimit<'info> {
+    pub vault_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault", vault_authority.key().as_ref(), &vault.vault_id.to_le_bytes()[..vault.id_seed_len()]],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+}
+
+pub fn _set_daily_limit(ctx: Context<SetDailyLimit>, daily_limit: u64) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+
+    vault.daily_limit = daily_limit;
+
+    emit!(DailyLimitSetEvent {
+        vault: vault.key(),
+        vault_authority: ctx.accounts.vault_authority.key(),
+        daily_limit,
+    });
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,52 @@
+//-------------------------------------------------------------------------------
+///
+/// Grant a delegate limited withdrawal rights on the vault
+/// 
+/// - Only the vault authority can create a delegate
+/// - The delegate may withdraw up to `max_amount` in total
+/// - Withdrawn funds always go to the fixed `recipient`
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use crate::state::{Vault, WithdrawDelegate};
+use crate::events::SetDelegateEvent;
+
+#[derive(Accounts)]
+pub struct SetDelegate<'info> {
+    #[account(mut)]
+    pub vault_authority: Signer<'info>,
+    #[account(
+        seeds = [b"vault", vault_authority.key().as_ref(), &vault.vault_id.to_le_bytes()[..vault.id_seed_len()]],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    /// CHECK: only used as a seed and stored as the delegate key
+    pub delegate: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = vault_authority,
+        space = 8 + WithdrawDelegate::INIT_SPACE,
+        seeds = [b"wd_delegate", vault.key().as_ref(), delegate.key().as_ref()],
+        bump
+    )]
+    pub withdraw_delegate: Account<'info, WithdrawDelegate>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn _set_delegate(ctx: Context<SetDelegate>, max_amount: u64, recipient: Pubkey) -> Result<()> {
+    let withdraw_delegate = &mut ctx.accounts.withdraw_delegate;
+
+    withdraw_delegate.vault = ctx.accounts.vault.key();
+    withdraw_delegate.delegate = ctx.accounts.delegate.key();
+    withdraw_delegate.recipient = recipient;
+    withdraw_delegate.remaining = max_amount;
+
+    emit!(SetDelegateEvent {
+        vault: withdraw_delegate.vault,
+        delegate: withdraw_delegate.delegate,
+        recipient,
+        max_amount,
+    });
+
+    Ok(())
+}
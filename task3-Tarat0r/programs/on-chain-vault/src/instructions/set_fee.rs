@@ -0,0 +1,44 @@
+//-------------------------------------------------------------------------------
+///
+/// Set the withdrawal fee of the vault and the treasury it is paid to
+/// 
+/// Requirements:
+/// - Only the vault authority can change the fee
+/// - fee_bps is in basis points and cannot exceed MAX_FEE_BPS
+/// - 0 disables the fee, withdraw then needs no treasury account
+/// - Emit a fee event after the change
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use crate::state::{Vault, MAX_FEE_BPS};
+use crate::errors::VaultError;
+use crate::events::FeeSetEvent;
+
+#[derive(Accounts)]
+pub struct SetFee<'info> {
+    pub vault_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault", vault_authority.key().as_ref(), &vault.vault_id.to_le_bytes()[..vault.id_seed_len()]],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+}
+
+pub fn _set_fee(ctx: Context<SetFee>, fee_bps: u16, treasury: Pubkey) -> Result<()> {
+    require!(fee_bps <= MAX_FEE_BPS, VaultError::FeeTooHigh);
+
+    let vault = &mut ctx.accounts.vault;
+
+    vault.fee_bps = fee_bps;
+    vault.treasury = treasury;
+
+    emit!(FeeSetEvent {
+        vault: vault.key(),
+        vault_authority: ctx.accounts.vault_authority.key(),
+        fee_bps,
+        treasury,
+    });
+
+    Ok(())
+}
\ No newline at end of file
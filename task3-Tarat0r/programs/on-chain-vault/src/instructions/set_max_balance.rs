@@ -0,0 +1,39 @@
+//-------------------------------------------------------------------------------
+///
+/// Set the maximum lamport balance of the vault
+/// 
+/// Requirements:
+/// - Only the vault authority can change the cap
+/// - u64::MAX removes the cap
+/// - Lowering the cap below the current balance only blocks further deposits
+/// - Emit a max balance event after the change
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use crate::state::Vault;
+use crate::events::MaxBalanceSetEvent;
+
+#[derive(Accounts)]
+pub struct SetMaxBalance<'info> {
+    pub vault_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault", vault_authority.key().as_ref(), &vault.vault_id.to_le_bytes()[..vault.id_seed_len()]],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+}
+
+pub fn _set_max_balance(ctx: Context<SetMaxBalance>, max_balance: u64) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+
+    vault.max_balance = max_balance;
+
+    emit!(MaxBalanceSetEvent {
+        vault: vault.key(),
+        vault_authority: ctx.accounts.vault_authority.key(),
+        max_balance,
+    });
+
+    Ok(())
+}
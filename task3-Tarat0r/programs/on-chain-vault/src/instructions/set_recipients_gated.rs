@@ -0,0 +1,37 @@
+//-------------------------------------------------------------------------------
+///
+/// Turn the recipient allowlist of withdraw_to on or off
+/// 
+/// Requirements:
+/// - Only the vault authority can change it
+/// - While gated, withdraw_to only pays out to allowed recipients
+/// - Emit a recipients gated event after the change
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use crate::state::Vault;
+use crate::events::RecipientsGatedEvent;
+
+#[derive(Accounts)]
+pub struct SetRecipientsGated<'info> {
+    pub vault_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault", vault_authority.key().as_ref(), &vault.vault_id.to_le_bytes()[..vault.id_seed_len()]],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+}
+
+pub fn _set_recipients_gated(ctx: Context<SetRecipientsGated>, recipients_gated: bool) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+
+    vault.recipients_gated = recipients_gated;
+
+    emit!(RecipientsGatedEvent {
+        vault: vault.key(),
+        recipients_gated,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+//-------------------------------------------------------------------------------
+///
+/// Set the time before which the vault rejects withdrawals
+/// 
+/// Requirements:
+/// - Only the vault authority can change the unlock time
+/// - While the time lock is active it can only be pushed later
+/// - 0 removes the time lock once it has expired
+/// - Deposits are not affected by the time lock
+/// - Emit an unlock ts event after the change
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use crate::state::Vault;
+use crate::errors::VaultError;
+use crate::events::UnlockTsSetEvent;
+
+#[derive(Accounts)]
+pub struct SetUnlockTs<'info> {
+    pub vault_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault", vault_authority.key().as_ref(), &vault.vault_id.to_le_bytes()[..vault.id_seed_len()]],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+}
+
+pub fn _set_unlock_ts(ctx: Context<SetUnlockTs>, unlock_ts: i64) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(now >= vault.unlock_ts || unlock_ts >= vault.unlock_ts, VaultError::TimeLocked);
+
+    vault.unlock_ts = unlock_ts;
+
+    emit!(UnlockTsSetEvent {
+        vault: vault.key(),
+        vault_authority: ctx.accounts.vault_authority.key(),
+        unlock_ts,
+    });
+
+    Ok(())
+}
\ No newline at end of file
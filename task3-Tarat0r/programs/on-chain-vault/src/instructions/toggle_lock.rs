@@ -17,8 +17,8 @@ pub struct ToggleLock<'info> {
     pub vault_authority: Signer<'info>,
     #[account(
         mut,
-        seeds = [b"vault",  vault_authority.key().as_ref()],
-        bump,
+        seeds = [b"vault", vault_authority.key().as_ref(), &vault.vault_id.to_le_bytes()[..vault.id_seed_len()]],
+        bump = vault.bump,
     )]
     pub vault: Account<'info, Vault>,
 }
@@ -0,0 +1,37 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Unlock the vault
+/// 
+/// Requirements:
+/// - Set the vault to unlocked so deposits and withdrawals are accepted again
+/// - Only the vault authority should be able to unlock the vault
+/// - Emit a unlock event after successful state change
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use crate::state::Vault;
+use crate::events::UnlockEvent;
+
+#[derive(Accounts)]
+pub struct UnlockVault<'info> {
+    pub vault_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault", vault_authority.key().as_ref(), &vault.vault_id.to_le_bytes()[..vault.id_seed_len()]],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+}
+
+pub fn _unlock_vault(ctx: Context<UnlockVault>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+
+    vault.locked = false;
+
+    emit!(UnlockEvent {
+        vault: vault.key(),
+        vault_authority: ctx.accounts.vault_authority.key(),
+    });
+
+    Ok(())
+}
\ No newline at end of file
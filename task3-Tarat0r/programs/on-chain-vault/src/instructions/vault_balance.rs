@@ -0,0 +1,27 @@
+//-------------------------------------------------------------------------------
+///
+/// Report the vault's current lamport balance
+/// 
+/// - The balance includes the rent-exempt minimum
+/// - Read only, the result is emitted as an event
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use crate::state::Vault;
+use crate::events::BalanceReported;
+
+#[derive(Accounts)]
+pub struct QueryBalance<'info> {
+    pub vault: Account<'info, Vault>,
+}
+
+pub fn _vault_balance(ctx: Context<QueryBalance>) -> Result<()> {
+    let vault = &ctx.accounts.vault;
+
+    emit!(BalanceReported {
+        vault: vault.key(),
+        lamports: vault.to_account_info().lamports(),
+    });
+
+    Ok(())
+}
\ No newline at end of file
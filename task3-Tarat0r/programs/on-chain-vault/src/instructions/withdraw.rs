@@ -3,16 +3,19 @@
 /// TASK: Implement the withdraw functionality for the on-chain vault
 /// 
 /// Requirements:
-/// - Verify that the vault is not locked
-/// - Verify that the vault has enough balance to withdraw
-/// - Transfer lamports from vault to vault authority
+/// - Verify that the vault is not locked and its unlock time has passed
+/// - Verify that the vault has enough balance to withdraw and stays rent exempt
+/// - Verify that the withdrawal fits into the vault's daily limit
+/// - Transfer lamports from vault to vault authority, minus the fee which goes
+///   to the vault's treasury
+/// - Add the amount to the vault authority's DepositLedger
 /// - Emit a withdraw event after successful transfer
 /// 
 ///-------------------------------------------------------------------------------
 use anchor_lang::prelude::*;
 // use anchor_lang::solana_program::program::{invoke_signed};
 // use anchor_lang::solana_program::system_instruction::transfer;
-use crate::state::Vault;
+use crate::state::{DepositLedger, Vault};
 use crate::errors::VaultError;
 use crate::events::WithdrawEvent;
 
@@ -22,25 +25,38 @@ pub struct Withdraw<'info> {
     pub vault_authority: Signer<'info>,
     #[account(
         mut,
-        seeds = [b"vault",  vault_authority.key().as_ref()],
-        bump,
+        seeds = [b"vault", vault_authority.key().as_ref(), &vault.vault_id.to_le_bytes()[..vault.id_seed_len()]],
+        bump = vault.bump,
     )]
     pub vault: Account<'info, Vault>,
+    #[account(
+        init_if_needed,
+        payer = vault_authority,
+        space = 8 + DepositLedger::INIT_SPACE,
+        seeds = [b"deposit_ledger", vault.key().as_ref(), vault_authority.key().as_ref()],
+        bump
+    )]
+    pub deposit_ledger: Account<'info, DepositLedger>,
+    // only needed while the vault charges a fee
+    #[account(mut, address = vault.treasury)]
+    pub treasury: Option<SystemAccount<'info>>,
+    pub system_program: Program<'info, System>,
 }
 
 pub fn _withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
-    let vault = &ctx.accounts.vault;
+    let vault = &mut ctx.accounts.vault;
     let vault_info = vault.to_account_info();
     let authority_info = ctx.accounts.vault_authority.to_account_info();
-
-    require!(!vault.locked, VaultError::VaultLocked);
-    require!(vault_info.lamports() >= amount, VaultError::InsufficientBalance);
-
+    let treasury_info = ctx.accounts.treasury.as_ref().map(|treasury| treasury.to_account_info());
     let authority_key = ctx.accounts.vault_authority.key();
 
-    **vault_info.try_borrow_mut_lamports()? -= amount;
-    **authority_info.try_borrow_mut_lamports()? = authority_info
-    .lamports()
+    let fee = vault.pay_out(&vault_info, &authority_info, treasury_info.as_ref(), amount)?;
+
+    let ledger = &mut ctx.accounts.deposit_ledger;
+    ledger.vault = vault.key();
+    ledger.user = authority_key;
+    ledger.withdrawn = ledger
+    .withdrawn
     .checked_add(amount)
     .ok_or(VaultError::Overflow)?;
 
@@ -48,6 +64,8 @@ pub fn _withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
         amount,
         vault_authority: authority_key,
         vault: vault.key(),
+        fee,
+        treasury: vault.treasury,
     });
 
     Ok(())
@@ -0,0 +1,83 @@
+//-------------------------------------------------------------------------------
+///
+/// Withdraw SPL tokens from the vault
+/// 
+/// Requirements:
+/// - Only the vault authority can withdraw
+/// - The lock and time lock checks of withdraw still apply
+/// - Verify that the vault's token account holds enough tokens
+/// - Transfer tokens from the vault's associated token account to the
+///   authority's token account, signed by the vault PDA
+/// - Emit an SPL withdraw event after successful transfer
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked};
+use crate::state::Vault;
+use crate::errors::VaultError;
+use crate::events::SplWithdrawEvent;
+
+#[derive(Accounts)]
+pub struct WithdrawSpl<'info> {
+    pub vault_authority: Signer<'info>,
+    #[account(
+        seeds = [b"vault", vault_authority.key().as_ref(), &vault.vault_id.to_le_bytes()[..vault.id_seed_len()]],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    pub mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = vault_authority,
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn _withdraw_spl(ctx: Context<WithdrawSpl>, amount: u64) -> Result<()> {
+    let vault = &ctx.accounts.vault;
+
+    require!(!vault.locked, VaultError::VaultLocked);
+    require!(Clock::get()?.unix_timestamp >= vault.unlock_ts, VaultError::TimeLocked);
+    require!(ctx.accounts.vault_token_account.amount >= amount, VaultError::InsufficientBalance);
+
+    let vault_id = vault.vault_id.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"vault",
+        vault.vault_authority.as_ref(),
+        &vault_id[..vault.id_seed_len()],
+        &[vault.bump],
+    ]];
+    token::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.vault_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.authority_token_account.to_account_info(),
+                authority: vault.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    emit!(SplWithdrawEvent {
+        amount,
+        mint: ctx.accounts.mint.key(),
+        vault_authority: ctx.accounts.vault_authority.key(),
+        vault: vault.key(),
+    });
+
+    Ok(())
+}
\ No newline at end of file
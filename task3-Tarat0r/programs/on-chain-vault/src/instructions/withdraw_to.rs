@@ -0,0 +1,79 @@
+//-------------------------------------------------------------------------------
+///
+/// Withdraw from the vault to another account
+/// 
+/// Requirements:
+/// - Only the vault authority can withdraw
+/// - The lock, balance and fee handling of withdraw still apply
+/// - When recipients are gated, the recipient's AllowedRecipient PDA must be passed
+/// - Add the amount to the vault authority's DepositLedger
+/// - Emit a withdraw to event after successful transfer
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use crate::state::{AllowedRecipient, DepositLedger, Vault};
+use crate::errors::VaultError;
+use crate::events::WithdrawToEvent;
+
+#[derive(Accounts)]
+pub struct WithdrawTo<'info> {
+    #[account(mut)]
+    pub vault_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault", vault_authority.key().as_ref(), &vault.vault_id.to_le_bytes()[..vault.id_seed_len()]],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub recipient: SystemAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = vault_authority,
+        space = 8 + DepositLedger::INIT_SPACE,
+        seeds = [b"deposit_ledger", vault.key().as_ref(), vault_authority.key().as_ref()],
+        bump
+    )]
+    pub deposit_ledger: Account<'info, DepositLedger>,
+    // only needed while the vault is recipients_gated
+    pub allowed_recipient: Option<Account<'info, AllowedRecipient>>,
+    // only needed while the vault charges a fee
+    #[account(mut, address = vault.treasury)]
+    pub treasury: Option<SystemAccount<'info>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn _withdraw_to(ctx: Context<WithdrawTo>, amount: u64) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let vault_info = vault.to_account_info();
+    let recipient_info = ctx.accounts.recipient.to_account_info();
+    let treasury_info = ctx.accounts.treasury.as_ref().map(|treasury| treasury.to_account_info());
+
+    if vault.recipients_gated {
+        let allowed = ctx.accounts.allowed_recipient.as_ref().is_some_and(|entry| {
+            entry.vault == vault.key() && entry.recipient == recipient_info.key()
+        });
+        require!(allowed, VaultError::RecipientNotAllowed);
+    }
+
+    let fee = vault.pay_out(&vault_info, &recipient_info, treasury_info.as_ref(), amount)?;
+
+    let ledger = &mut ctx.accounts.deposit_ledger;
+    ledger.vault = vault.key();
+    ledger.user = ctx.accounts.vault_authority.key();
+    ledger.withdrawn = ledger
+    .withdrawn
+    .checked_add(amount)
+    .ok_or(VaultError::Overflow)?;
+
+    emit!(WithdrawToEvent {
+        amount,
+        recipient: recipient_info.key(),
+        vault_authority: ctx.accounts.vault_authority.key(),
+        vault: vault.key(),
+        fee,
+        treasury: vault.treasury,
+    });
+
+    Ok(())
+}
@@ -37,12 +37,12 @@ declare_id!("ARmiAGe6oAEq5BKguHydD3zt2n5PkV2Q5PLA1McuMkJT");
 pub mod on_chain_vault {
     use super::*;
 
-    pub fn init_vault(ctx: Context<InitializeVault>, locked: bool) -> Result<()> {
-      _init_vault(ctx, locked)
+    pub fn initialize_vault(ctx: Context<InitializeVault>, vault_id: u64, locked: bool, max_balance: u64) -> Result<()> {
+      _initialize_vault(ctx, vault_id, locked, max_balance)
     }
 
-    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
-      _deposit(ctx, amount)
+    pub fn deposit(ctx: Context<Deposit>, amount: u64, memo_commitment: Option<[u8; 32]>) -> Result<()> {
+      _deposit(ctx, amount, memo_commitment)
     }
 
     pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
@@ -52,4 +52,100 @@ pub mod on_chain_vault {
     pub fn toggle_lock(ctx: Context<ToggleLock>) -> Result<()> {
       _toggle_lock(ctx)
     }
+
+    pub fn lock_vault(ctx: Context<LockVault>) -> Result<()> {
+      _lock_vault(ctx)
+    }
+
+    pub fn unlock_vault(ctx: Context<UnlockVault>) -> Result<()> {
+      _unlock_vault(ctx)
+    }
+
+    pub fn request_withdrawal(ctx: Context<RequestWithdrawal>, amount: u64) -> Result<()> {
+      _request_withdrawal(ctx, amount)
+    }
+
+    pub fn execute_pending(ctx: Context<ExecutePending>) -> Result<()> {
+      _execute_pending(ctx)
+    }
+
+    pub fn cancel_pending(ctx: Context<CancelPending>) -> Result<()> {
+      _cancel_pending(ctx)
+    }
+
+    pub fn set_delegate(ctx: Context<SetDelegate>, max_amount: u64, recipient: Pubkey) -> Result<()> {
+      _set_delegate(ctx, max_amount, recipient)
+    }
+
+    pub fn delegated_withdraw(ctx: Context<DelegatedWithdraw>, amount: u64) -> Result<()> {
+      _delegated_withdraw(ctx, amount)
+    }
+
+    pub fn batch_balances<'info>(ctx: Context<'_, '_, 'info, 'info, QueryBalances>) -> Result<()> {
+      _batch_balances(ctx)
+    }
+
+    pub fn reveal_memo(ctx: Context<RevealMemo>, memo: String) -> Result<()> {
+      _reveal_memo(ctx, memo)
+    }
+
+    pub fn refund(ctx: Context<Refund>, amount: u64) -> Result<()> {
+      _refund(ctx, amount)
+    }
+
+    pub fn reconcile(ctx: Context<Reconcile>) -> Result<()> {
+      _reconcile(ctx)
+    }
+
+    pub fn vault_balance(ctx: Context<QueryBalance>) -> Result<()> {
+      _vault_balance(ctx)
+    }
+
+    pub fn set_max_balance(ctx: Context<SetMaxBalance>, max_balance: u64) -> Result<()> {
+      _set_max_balance(ctx, max_balance)
+    }
+
+    pub fn set_unlock_ts(ctx: Context<SetUnlockTs>, unlock_ts: i64) -> Result<()> {
+      _set_unlock_ts(ctx, unlock_ts)
+    }
+
+    pub fn set_daily_limit(ctx: Context<SetDailyLimit>, daily_limit: u64) -> Result<()> {
+      _set_daily_limit(ctx, daily_limit)
+    }
+
+    pub fn set_fee(ctx: Context<SetFee>, fee_bps: u16, treasury: Pubkey) -> Result<()> {
+      _set_fee(ctx, fee_bps, treasury)
+    }
+
+    pub fn set_recipients_gated(ctx: Context<SetRecipientsGated>, recipients_gated: bool) -> Result<()> {
+      _set_recipients_gated(ctx, recipients_gated)
+    }
+
+    pub fn allow_recipient(ctx: Context<AllowRecipient>) -> Result<()> {
+      _allow_recipient(ctx)
+    }
+
+    pub fn disallow_recipient(ctx: Context<DisallowRecipient>) -> Result<()> {
+      _disallow_recipient(ctx)
+    }
+
+    pub fn withdraw_to(ctx: Context<WithdrawTo>, amount: u64) -> Result<()> {
+      _withdraw_to(ctx, amount)
+    }
+
+    pub fn migrate_vault(ctx: Context<MigrateVault>) -> Result<()> {
+      _migrate_vault(ctx)
+    }
+
+    pub fn deposit_spl(ctx: Context<DepositSpl>, amount: u64) -> Result<()> {
+      _deposit_spl(ctx, amount)
+    }
+
+    pub fn withdraw_spl(ctx: Context<WithdrawSpl>, amount: u64) -> Result<()> {
+      _withdraw_spl(ctx, amount)
+    }
+
+    pub fn close_vault<'info>(ctx: Context<'_, '_, 'info, 'info, CloseVault<'info>>) -> Result<()> {
+      _close_vault(ctx)
+    }
 }
@@ -1,8 +1,182 @@
 use anchor_lang::prelude::*;
+use crate::errors::VaultError;
 
 #[account]
 #[derive(InitSpace)]
 pub struct Vault {
     pub vault_authority: Pubkey,
     pub locked: bool,
+    /// lamports put in through deposit
+    pub total_deposited: u64,
+    /// lamports taken out by withdrawals, refunds only show on the DepositLedger
+    pub total_withdrawn: u64,
+    /// cap on the vault's lamport balance, u64::MAX means uncapped
+    pub max_balance: u64,
+    /// when set, withdraw_to only pays out to allowed recipients
+    pub recipients_gated: bool,
+    /// layout version, see VAULT_VERSION and migrate_vault
+    pub version: u8,
+    /// lets one authority hold several vaults, `["vault", vault_authority, vault_id]`
+    pub vault_id: u64,
+    /// bump of the vault PDA, stored at initialization
+    pub bump: u8,
+    /// withdrawals are rejected before this unix timestamp, 0 means no time lock
+    pub unlock_ts: i64,
+    /// cap on lamports withdrawn per WITHDRAWAL_WINDOW, 0 means unlimited
+    pub daily_limit: u64,
+    pub window_start_ts: i64,
+    pub withdrawn_in_window: u64,
+    /// share of each withdraw sent to the treasury, in basis points
+    pub fee_bps: u16,
+    pub treasury: Pubkey,
+    /// vault created before vault ids, its PDA is `["vault", vault_authority]`
+    pub legacy_seeds: bool,
+    /// mints deposited through deposit_spl, close_vault needs the vault's
+    /// token account of each one
+    #[max_len(MAX_VAULT_MINTS)]
+    pub mints: Vec<Pubkey>,
+}
+
+impl Vault {
+    /// Length of the vault_id seed, a legacy vault has none.
+    pub fn id_seed_len(&self) -> usize {
+        if self.legacy_seeds {
+            0
+        } else {
+            8
+        }
+    }
+
+    /// Counts a withdrawal against the daily limit, starting a new window
+    /// once a full WITHDRAWAL_WINDOW has passed since the current one began.
+    pub fn track_withdrawal(&mut self, amount: u64, now: i64) -> Result<()> {
+        if now.saturating_sub(self.window_start_ts) >= WITHDRAWAL_WINDOW {
+            self.window_start_ts = now;
+            self.withdrawn_in_window = 0;
+        }
+        let withdrawn = self
+            .withdrawn_in_window
+            .checked_add(amount)
+            .ok_or(VaultError::Overflow)?;
+        require!(
+            self.daily_limit == 0 || withdrawn <= self.daily_limit,
+            VaultError::DailyLimitExceeded
+        );
+        self.withdrawn_in_window = withdrawn;
+        Ok(())
+    }
+
+    /// Pays `amount` out of the vault behind `vault_info`, shared by every
+    /// lamport withdrawal path. Applies the lock, time lock, rent and daily
+    /// limit checks, sends the fee to the treasury and the rest to `recipient`.
+    /// Returns the fee.
+    pub fn pay_out<'info>(
+        &mut self,
+        vault_info: &AccountInfo<'info>,
+        recipient: &AccountInfo<'info>,
+        treasury: Option<&AccountInfo<'info>>,
+        amount: u64,
+    ) -> Result<u64> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(!self.locked, VaultError::VaultLocked);
+        require!(now >= self.unlock_ts, VaultError::TimeLocked);
+        let remaining = vault_info
+            .lamports()
+            .checked_sub(amount)
+            .ok_or(VaultError::InsufficientBalance)?;
+        require!(
+            remaining >= Rent::get()?.minimum_balance(vault_info.data_len()),
+            VaultError::InsufficientBalance
+        );
+        self.track_withdrawal(amount, now)?;
+
+        let fee: u64 = (amount as u128)
+            .checked_mul(self.fee_bps as u128)
+            .map(|fee| fee / MAX_FEE_BPS as u128)
+            .and_then(|fee| fee.try_into().ok())
+            .ok_or(VaultError::Overflow)?;
+        let payout = amount.checked_sub(fee).ok_or(VaultError::Overflow)?;
+
+        **vault_info.try_borrow_mut_lamports()? = remaining;
+        **recipient.try_borrow_mut_lamports()? = recipient
+            .lamports()
+            .checked_add(payout)
+            .ok_or(VaultError::Overflow)?;
+        if fee > 0 {
+            let treasury = treasury.ok_or(VaultError::TreasuryRequired)?;
+            **treasury.try_borrow_mut_lamports()? = treasury
+                .lamports()
+                .checked_add(fee)
+                .ok_or(VaultError::Overflow)?;
+        }
+        self.total_withdrawn = self
+            .total_withdrawn
+            .checked_add(amount)
+            .ok_or(VaultError::Overflow)?;
+        Ok(fee)
+    }
+}
+
+/// Current `Vault` layout. Accounts created before versioning read as 0.
+pub const VAULT_VERSION: u8 = 9;
+
+/// Minimum time (in seconds) between requesting a withdrawal and executing it.
+pub const WITHDRAWAL_DELAY: i64 = 5;
+
+/// Length (in seconds) of the window the daily withdrawal limit applies to.
+pub const WITHDRAWAL_WINDOW: i64 = 24 * 60 * 60;
+
+/// Upper bound of `fee_bps`, a fee of 100%.
+pub const MAX_FEE_BPS: u16 = 10_000;
+
+/// Maximum number of vaults reported by a single `batch_balances` call.
+pub const MAX_BATCH_VAULTS: usize = 16;
+
+/// Maximum number of distinct mints a vault can hold through `deposit_spl`.
+pub const MAX_VAULT_MINTS: usize = 4;
+
+#[account]
+#[derive(InitSpace)]
+pub struct PendingWithdrawal {
+    pub vault: Pubkey,
+    pub amount: u64,
+    pub ready_ts: i64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct WithdrawDelegate {
+    pub vault: Pubkey,
+    pub delegate: Pubkey,
+    pub recipient: Pubkey,
+    pub remaining: u64,
+}
+
+/// Recipient approved for `withdraw_to`, `["allowed_recipient", vault, recipient]`.
+#[account]
+#[derive(InitSpace)]
+pub struct AllowedRecipient {
+    pub vault: Pubkey,
+    pub recipient: Pubkey,
+}
+
+/// Lamports a user moved through a vault, `["deposit_ledger", vault, user]`.
+/// Deposits are recorded for the depositor, withdrawals for the vault authority.
+#[account]
+#[derive(InitSpace)]
+pub struct DepositLedger {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub deposited: u64,
+    pub withdrawn: u64,
+}
+
+/// Commitment to a memo for a single deposit, revealed later via `reveal_memo`.
+#[account]
+#[derive(InitSpace)]
+pub struct DepositNote {
+    pub vault: Pubkey,
+    pub depositor: Pubkey,
+    pub amount: u64,
+    pub memo_commitment: [u8; 32],
 }
@@ -1,7 +1,10 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
+import { createMint, getAccount, getAssociatedTokenAddressSync, getOrCreateAssociatedTokenAccount, mintTo, TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID } from "@solana/spl-token";
 import { OnChainVault } from "../target/types/on_chain_vault";
 import { assert } from "chai";
+import crypto from "crypto";
+import fs from "fs";
 
 describe("on-chain-vault", async () => {
   const provider = anchor.AnchorProvider.local();
@@ -9,14 +12,19 @@ describe("on-chain-vault", async () => {
 
   const program = anchor.workspace.OnChainVault as Program<OnChainVault>;
 
+  // u64::MAX disables the vault's max balance
+  const UNCAPPED = new anchor.BN("18446744073709551615");
+
   const alice = anchor.web3.Keypair.generate();
   const bob = anchor.web3.Keypair.generate();
   const anatoly = anchor.web3.Keypair.generate();
 
-  // Vault PDAs will be derived from the vault authority
-  const getVaultPDA = (vaultAuthority: anchor.web3.PublicKey) => {
+  const DEFAULT_VAULT_ID = new anchor.BN(0);
+
+  // Vault PDAs will be derived from the vault authority and vault id
+  const getVaultPDA = (vaultAuthority: anchor.web3.PublicKey, vaultId = DEFAULT_VAULT_ID) => {
     return anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("vault"), vaultAuthority.toBuffer()],
+      [Buffer.from("vault"), vaultAuthority.toBuffer(), vaultId.toArrayLike(Buffer, "le", 8)],
       program.programId
     );
   };
@@ -30,7 +38,7 @@ describe("on-chain-vault", async () => {
 
     const locked = false;
 
-    let txSig = await program.methods.initVault(locked).accounts({
+    let txSig = await program.methods.initializeVault(DEFAULT_VAULT_ID, locked, UNCAPPED).accounts({
       vaultAuthority: alice.publicKey,
       vault: vaultAlicePDA,
       systemProgram: anchor.web3.SystemProgram.programId,
@@ -51,6 +59,7 @@ describe("on-chain-vault", async () => {
         logsEmitted = true;
         assert.strictEqual(event.data.vault.toString(), vaultAlicePDA.toString(), "Event vault should match Alice's vault PDA");
         assert.strictEqual(event.data.vaultAuthority.toString(), alice.publicKey.toString(), "Event vault authority should be Alice");
+        assert.strictEqual(event.data.vaultId.toString(), "0", "Event vault id should be the default id");
         assert.strictEqual(event.data.locked, false, "Event locked status should be false");
       }
     }
@@ -62,7 +71,7 @@ describe("on-chain-vault", async () => {
 
     const locked = true;
 
-    let txSig = await program.methods.initVault(locked).accounts({
+    let txSig = await program.methods.initializeVault(DEFAULT_VAULT_ID, locked, UNCAPPED).accounts({
       vaultAuthority: bob.publicKey,
       vault: vaultBobPDA,
       systemProgram: anchor.web3.SystemProgram.programId,
@@ -94,7 +103,7 @@ describe("on-chain-vault", async () => {
 
     const locked = false;
 
-    let txSig = await program.methods.initVault(locked).accounts({
+    let txSig = await program.methods.initializeVault(DEFAULT_VAULT_ID, locked, UNCAPPED).accounts({
       vaultAuthority: anatoly.publicKey,
       vault: vaultAnatolyPDA,
       systemProgram: anchor.web3.SystemProgram.programId,
@@ -126,7 +135,7 @@ describe("on-chain-vault", async () => {
 
     let flag = "This should fail";
     try {
-      await program.methods.initVault(locked).accounts({
+      await program.methods.initializeVault(DEFAULT_VAULT_ID, locked, UNCAPPED).accounts({
         vaultAuthority: alice.publicKey,
         vault: vaultAlicePDA,
         systemProgram: anchor.web3.SystemProgram.programId,
@@ -148,7 +157,7 @@ describe("on-chain-vault", async () => {
     let flag = "This should fail";
     try {
       // Alice trying to initialize a vault for Bob (but Alice signs)
-      await program.methods.initVault(locked).accounts({
+      await program.methods.initializeVault(DEFAULT_VAULT_ID, locked, UNCAPPED).accounts({
         vaultAuthority: bob.publicKey,
         vault: vaultBobPDA,
         systemProgram: anchor.web3.SystemProgram.programId,
@@ -166,7 +175,7 @@ describe("on-chain-vault", async () => {
     const vaultBalanceBefore = await provider.connection.getBalance(vaultAlicePDA);
     const userBalanceBefore = await provider.connection.getBalance(alice.publicKey);
 
-    let txSig = await program.methods.deposit(new anchor.BN(depositAmount)).accounts({
+    let txSig = await program.methods.deposit(new anchor.BN(depositAmount), null).accounts({
       user: alice.publicKey,
       vault: vaultAlicePDA,
     }).signers([alice]).rpc({ commitment: "confirmed" });
@@ -200,7 +209,7 @@ describe("on-chain-vault", async () => {
 
     let flag = "This should fail";
     try {
-      await program.methods.deposit(new anchor.BN(depositAmount)).accounts({
+      await program.methods.deposit(new anchor.BN(depositAmount), null).accounts({
         user: bob.publicKey,
         vault: vaultBobPDA,
       }).signers([bob]).rpc({ commitment: "confirmed" });
@@ -221,7 +230,7 @@ describe("on-chain-vault", async () => {
 
     let flag = "This should fail";
     try {
-      await program.methods.deposit(new anchor.BN(depositAmount)).accounts({
+      await program.methods.deposit(new anchor.BN(depositAmount), null).accounts({
         user: charlie.publicKey,
         vault: charlieVaultPDA,
       }).signers([charlie]).rpc({ commitment: "confirmed" });
@@ -233,12 +242,48 @@ describe("on-chain-vault", async () => {
     assert.strictEqual(flag, "Failed", "Depositing to non-existent vault should fail");
   });
 
+  it("Deposit into a vault fails until it is initialized", async () => {
+    const dave = anchor.web3.Keypair.generate();
+    await airdrop(provider.connection, dave.publicKey);
+    const [daveVaultPDA, daveVaultBump] = getVaultPDA(dave.publicKey);
+
+    const deposit = () => program.methods.deposit(new anchor.BN(1000000), null).accounts({
+      user: dave.publicKey,
+      vault: daveVaultPDA,
+    }).signers([dave]).rpc({ commitment: "confirmed" });
+
+    let flag = "This should fail";
+    try {
+      await deposit();
+    } catch (error) {
+      flag = "Failed";
+      const err = anchor.AnchorError.parse(error.logs);
+      assert.strictEqual(err.error.errorCode.code, "AccountNotInitialized", "Should fail with AccountNotInitialized error");
+    }
+    assert.strictEqual(flag, "Failed", "Depositing to an uninitialized vault should fail");
+
+    await program.methods.initializeVault(DEFAULT_VAULT_ID, false, UNCAPPED).accounts({
+      vaultAuthority: dave.publicKey,
+      vault: daveVaultPDA,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).signers([dave]).rpc({ commitment: "confirmed" });
+
+    const vaultData = await program.account.vault.fetch(daveVaultPDA);
+    assert.strictEqual(vaultData.locked, false, "Vault should be unlocked");
+    assert.strictEqual(vaultData.bump, daveVaultBump, "Vault should store its PDA bump");
+
+    const vaultBalanceBefore = await provider.connection.getBalance(daveVaultPDA);
+    await deposit();
+    const vaultBalanceAfter = await provider.connection.getBalance(daveVaultPDA);
+    assert.strictEqual(vaultBalanceAfter, vaultBalanceBefore + 1000000, "Deposit should succeed after initialization");
+  });
+
   it("Cannot deposit more than user balance", async () => {
     const depositAmount = new anchor.BN("999999999999999999"); // Huge amount
     
     let flag = "This should fail";
     try {
-      await program.methods.deposit(depositAmount).accounts({
+      await program.methods.deposit(depositAmount, null).accounts({
         user: alice.publicKey,
         vault: vaultAlicePDA,
       }).signers([alice]).rpc({ commitment: "confirmed" });
@@ -280,7 +325,7 @@ describe("on-chain-vault", async () => {
     const depositAmount = 500000;
     const vaultBalanceBefore = await provider.connection.getBalance(vaultBobPDA);
 
-    await program.methods.deposit(new anchor.BN(depositAmount)).accounts({
+    await program.methods.deposit(new anchor.BN(depositAmount), null).accounts({
       user: bob.publicKey,
       vault: vaultBobPDA,
     }).signers([bob]).rpc({ commitment: "confirmed" });
@@ -320,7 +365,7 @@ describe("on-chain-vault", async () => {
 
     let flag = "This should fail";
     try {
-      await program.methods.deposit(new anchor.BN(depositAmount)).accounts({
+      await program.methods.deposit(new anchor.BN(depositAmount), null).accounts({
         user: alice.publicKey,
         vault: vaultAlicePDA,
       }).signers([alice]).rpc({ commitment: "confirmed" });
@@ -417,6 +462,43 @@ describe("on-chain-vault", async () => {
     assert.strictEqual(flag, "Failed", "Withdrawing more than vault balance should fail");
   });
 
+  it("Withdrawals keep the vault rent exempt", async () => {
+    const erin = anchor.web3.Keypair.generate();
+    await airdrop(provider.connection, erin.publicKey);
+    const [erinVaultPDA] = getVaultPDA(erin.publicKey);
+
+    await program.methods.initializeVault(DEFAULT_VAULT_ID, false, UNCAPPED).accounts({
+      vaultAuthority: erin.publicKey,
+      vault: erinVaultPDA,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).signers([erin]).rpc({ commitment: "confirmed" });
+    await program.methods.deposit(new anchor.BN(1000000), null).accounts({
+      user: erin.publicKey,
+      vault: erinVaultPDA,
+    }).signers([erin]).rpc({ commitment: "confirmed" });
+
+    const withdraw = (amount: number) => program.methods.withdraw(new anchor.BN(amount)).accounts({
+      vaultAuthority: erin.publicKey,
+      vault: erinVaultPDA,
+    }).signers([erin]).rpc({ commitment: "confirmed" });
+
+    const info = await provider.connection.getAccountInfo(erinVaultPDA);
+    const rent = await provider.connection.getMinimumBalanceForRentExemption(info.data.length);
+
+    let flag = "This should fail";
+    try {
+      await withdraw(info.lamports);
+    } catch (error) {
+      flag = "Failed";
+      const err = anchor.AnchorError.parse(error.logs);
+      assert.strictEqual(err.error.errorCode.code, "InsufficientBalance", "Should fail with InsufficientBalance error");
+    }
+    assert.strictEqual(flag, "Failed", "Withdrawing the full balance should fail");
+
+    await withdraw(info.lamports - rent);
+    assert.strictEqual(await provider.connection.getBalance(erinVaultPDA), rent, "Vault should be left at the rent floor");
+  });
+
   it("Cannot toggle lock without authority", async () => {
     let flag = "This should fail";
     try {
@@ -478,7 +560,7 @@ describe("on-chain-vault", async () => {
     const depositAmount = 2000000;
     const vaultBalanceBefore = await provider.connection.getBalance(vaultAnatolyPDA);
 
-    await program.methods.deposit(new anchor.BN(depositAmount)).accounts({
+    await program.methods.deposit(new anchor.BN(depositAmount), null).accounts({
       user: anatoly.publicKey,
       vault: vaultAnatolyPDA,
     }).signers([anatoly]).rpc({ commitment: "confirmed" });
@@ -489,12 +571,12 @@ describe("on-chain-vault", async () => {
 
   it("Multiple deposits and withdrawals work correctly", async () => {
     // Multiple deposits
-    await program.methods.deposit(new anchor.BN(100000)).accounts({
+    await program.methods.deposit(new anchor.BN(100000), null).accounts({
       user: anatoly.publicKey,
       vault: vaultAnatolyPDA,
     }).signers([anatoly]).rpc({ commitment: "confirmed" });
 
-    await program.methods.deposit(new anchor.BN(200000)).accounts({
+    await program.methods.deposit(new anchor.BN(200000), null).accounts({
       user: anatoly.publicKey,
       vault: vaultAnatolyPDA,
     }).signers([anatoly]).rpc({ commitment: "confirmed" });
@@ -516,7 +598,7 @@ describe("on-chain-vault", async () => {
     const vaultBalanceBefore = await provider.connection.getBalance(vaultBobPDA);
     const aliceBalanceBefore = await provider.connection.getBalance(alice.publicKey);
 
-    let txSig = await program.methods.deposit(new anchor.BN(depositAmount)).accounts({
+    let txSig = await program.methods.deposit(new anchor.BN(depositAmount), null).accounts({
       user: alice.publicKey,
       vault: vaultBobPDA,
     }).signers([alice]).rpc({ commitment: "confirmed" });
@@ -550,7 +632,7 @@ describe("on-chain-vault", async () => {
     const vaultBalanceBefore = await provider.connection.getBalance(vaultAnatolyPDA);
     const bobBalanceBefore = await provider.connection.getBalance(bob.publicKey);
 
-    let txSig = await program.methods.deposit(new anchor.BN(depositAmount)).accounts({
+    let txSig = await program.methods.deposit(new anchor.BN(depositAmount), null).accounts({
       user: bob.publicKey,
       vault: vaultAnatolyPDA,
     }).signers([bob]).rpc({ commitment: "confirmed" });
@@ -584,7 +666,7 @@ describe("on-chain-vault", async () => {
     const vaultBalanceBefore = await provider.connection.getBalance(vaultAlicePDA);
     const anatolyBalanceBefore = await provider.connection.getBalance(anatoly.publicKey);
 
-    let txSig = await program.methods.deposit(new anchor.BN(depositAmount)).accounts({
+    let txSig = await program.methods.deposit(new anchor.BN(depositAmount), null).accounts({
       user: anatoly.publicKey,
       vault: vaultAlicePDA,
     }).signers([anatoly]).rpc({ commitment: "confirmed" });
@@ -624,7 +706,7 @@ describe("on-chain-vault", async () => {
 
     let flag = "This should fail";
     try {
-      await program.methods.deposit(new anchor.BN(depositAmount)).accounts({
+      await program.methods.deposit(new anchor.BN(depositAmount), null).accounts({
         user: bob.publicKey, // Bob trying to deposit
         vault: vaultAlicePDA, // Into Alice's locked vault
       }).signers([bob]).rpc({ commitment: "confirmed" });
@@ -644,7 +726,7 @@ describe("on-chain-vault", async () => {
     }).signers([alice]).rpc({ commitment: "confirmed" });
 
     // Bob deposits into Alice's vault
-    await program.methods.deposit(new anchor.BN(200000)).accounts({
+    await program.methods.deposit(new anchor.BN(200000), null).accounts({
       user: bob.publicKey,
       vault: vaultAlicePDA,
     }).signers([bob]).rpc({ commitment: "confirmed" });
@@ -670,8 +752,1583 @@ describe("on-chain-vault", async () => {
       vault: vaultAlicePDA,
     }).signers([alice]).rpc({ commitment: "confirmed" });
   });
+  describe("Withdrawal queue", () => {
+    const dave = anchor.web3.Keypair.generate();
+    const [vaultDavePDA] = getVaultPDA(dave.publicKey);
+    const getPendingPDA = (vault: anchor.web3.PublicKey) => {
+      return anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("pending"), vault.toBuffer()],
+        program.programId
+      );
+    };
+    const [pendingDavePDA] = getPendingPDA(vaultDavePDA);
+
+    before(async () => {
+      await airdrop(provider.connection, dave.publicKey);
+      await program.methods.initializeVault(DEFAULT_VAULT_ID, false, UNCAPPED).accounts({
+        vaultAuthority: dave.publicKey,
+        vault: vaultDavePDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).signers([dave]).rpc({ commitment: "confirmed" });
+      await program.methods.deposit(new anchor.BN(1000000), null).accounts({
+        user: dave.publicKey,
+        vault: vaultDavePDA,
+      }).signers([dave]).rpc({ commitment: "confirmed" });
+    });
+
+    it("Cannot execute a pending withdrawal before the delay", async () => {
+      await program.methods.requestWithdrawal(new anchor.BN(100000)).accounts({
+        vaultAuthority: dave.publicKey,
+        vault: vaultDavePDA,
+        pendingWithdrawal: pendingDavePDA,
+      }).signers([dave]).rpc({ commitment: "confirmed" });
+
+      const pending = await program.account.pendingWithdrawal.fetch(pendingDavePDA);
+      assert.strictEqual(pending.amount.toString(), "100000", "Pending amount should match the request");
+      assert.strictEqual(pending.vault.toString(), vaultDavePDA.toString(), "Pending withdrawal should reference Dave's vault");
+
+      let flag = "This should fail";
+      try {
+        await program.methods.executePending().accounts({
+          vaultAuthority: dave.publicKey,
+          vault: vaultDavePDA,
+          pendingWithdrawal: pendingDavePDA,
+        }).signers([dave]).rpc({ commitment: "confirmed" });
+      } catch (error) {
+        flag = "Failed";
+        assert.isTrue(error.toString().includes("WithdrawalNotReady"), "Should fail with WithdrawalNotReady error");
+      }
+      assert.strictEqual(flag, "Failed", "Executing before ready_ts should fail");
+    });
+
+    it("Executes a pending withdrawal after the delay", async () => {
+      await sleep(7000);
+      const vaultBalanceBefore = await provider.connection.getBalance(vaultDavePDA);
+
+      await program.methods.executePending().accounts({
+        vaultAuthority: dave.publicKey,
+        vault: vaultDavePDA,
+        pendingWithdrawal: pendingDavePDA,
+      }).signers([dave]).rpc({ commitment: "confirmed" });
+
+      const vaultBalanceAfter = await provider.connection.getBalance(vaultDavePDA);
+      assert.strictEqual(vaultBalanceBefore - vaultBalanceAfter, 100000, "Vault balance should decrease by the pending amount");
+      assert.isNull(await provider.connection.getAccountInfo(pendingDavePDA), "Pending withdrawal should be closed");
+    });
+
+    it("Cancels a pending withdrawal", async () => {
+      await program.methods.requestWithdrawal(new anchor.BN(200000)).accounts({
+        vaultAuthority: dave.publicKey,
+        vault: vaultDavePDA,
+        pendingWithdrawal: pendingDavePDA,
+      }).signers([dave]).rpc({ commitment: "confirmed" });
+      const vaultBalanceBefore = await provider.connection.getBalance(vaultDavePDA);
+
+      await program.methods.cancelPending().accounts({
+        vaultAuthority: dave.publicKey,
+        vault: vaultDavePDA,
+        pendingWithdrawal: pendingDavePDA,
+      }).signers([dave]).rpc({ commitment: "confirmed" });
+
+      const vaultBalanceAfter = await provider.connection.getBalance(vaultDavePDA);
+      assert.strictEqual(vaultBalanceAfter, vaultBalanceBefore, "Cancelling should not move funds out of the vault");
+      assert.isNull(await provider.connection.getAccountInfo(pendingDavePDA), "Pending withdrawal should be closed");
+    });
+  });
+
+
+  describe("Withdraw delegates", () => {
+    const erin = anchor.web3.Keypair.generate();
+    const delegate = anchor.web3.Keypair.generate();
+    const recipient = anchor.web3.Keypair.generate();
+    const [vaultErinPDA] = getVaultPDA(erin.publicKey);
+    const [delegatePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("wd_delegate"), vaultErinPDA.toBuffer(), delegate.publicKey.toBuffer()],
+      program.programId
+    );
+
+    before(async () => {
+      await airdrop(provider.connection, erin.publicKey);
+      await airdrop(provider.connection, delegate.publicKey);
+      await airdrop(provider.connection, recipient.publicKey);
+      await program.methods.initializeVault(DEFAULT_VAULT_ID, false, UNCAPPED).accounts({
+        vaultAuthority: erin.publicKey,
+        vault: vaultErinPDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).signers([erin]).rpc({ commitment: "confirmed" });
+      await program.methods.deposit(new anchor.BN(1000000), null).accounts({
+        user: erin.publicKey,
+        vault: vaultErinPDA,
+      }).signers([erin]).rpc({ commitment: "confirmed" });
+      await program.methods.setDelegate(new anchor.BN(300000), recipient.publicKey).accounts({
+        vaultAuthority: erin.publicKey,
+        vault: vaultErinPDA,
+        delegate: delegate.publicKey,
+        withdrawDelegate: delegatePDA,
+      }).signers([erin]).rpc({ commitment: "confirmed" });
+    });
+
+    it("Delegate withdrawals decrement the allowance", async () => {
+      const recipientBefore = await provider.connection.getBalance(recipient.publicKey);
+
+      await program.methods.delegatedWithdraw(new anchor.BN(200000)).accounts({
+        delegate: delegate.publicKey,
+        vault: vaultErinPDA,
+        withdrawDelegate: delegatePDA,
+        recipient: recipient.publicKey,
+      }).signers([delegate]).rpc({ commitment: "confirmed" });
+
+      const recipientAfter = await provider.connection.getBalance(recipient.publicKey);
+      assert.strictEqual(recipientAfter - recipientBefore, 200000, "Recipient should receive the delegated amount");
+      const delegateData = await program.account.withdrawDelegate.fetch(delegatePDA);
+      assert.strictEqual(delegateData.remaining.toString(), "100000", "Allowance should be decremented");
+    });
+
+    it("Cannot withdraw more than the remaining allowance", async () => {
+      let flag = "This should fail";
+      try {
+        await program.methods.delegatedWithdraw(new anchor.BN(100001)).accounts({
+          delegate: delegate.publicKey,
+          vault: vaultErinPDA,
+          withdrawDelegate: delegatePDA,
+          recipient: recipient.publicKey,
+        }).signers([delegate]).rpc({ commitment: "confirmed" });
+      } catch (error) {
+        flag = "Failed";
+        assert.isTrue(error.toString().includes("DelegateAllowanceExceeded"), "Should fail with DelegateAllowanceExceeded error");
+      }
+      assert.strictEqual(flag, "Failed", "Withdrawing over the allowance should fail");
+    });
+
+    it("Allowance can be used up exactly, then nothing more", async () => {
+      await program.methods.delegatedWithdraw(new anchor.BN(100000)).accounts({
+        delegate: delegate.publicKey,
+        vault: vaultErinPDA,
+        withdrawDelegate: delegatePDA,
+        recipient: recipient.publicKey,
+      }).signers([delegate]).rpc({ commitment: "confirmed" });
+
+      const delegateData = await program.account.withdrawDelegate.fetch(delegatePDA);
+      assert.strictEqual(delegateData.remaining.toString(), "0", "Allowance should be exhausted");
+
+      let flag = "This should fail";
+      try {
+        await program.methods.delegatedWithdraw(new anchor.BN(1)).accounts({
+          delegate: delegate.publicKey,
+          vault: vaultErinPDA,
+          withdrawDelegate: delegatePDA,
+          recipient: recipient.publicKey,
+        }).signers([delegate]).rpc({ commitment: "confirmed" });
+      } catch (error) {
+        flag = "Failed";
+        assert.isTrue(error.toString().includes("DelegateAllowanceExceeded"), "Should fail with DelegateAllowanceExceeded error");
+      }
+      assert.strictEqual(flag, "Failed", "Exhausted allowance should reject further withdrawals");
+    });
+
+    it("Unknown delegate cannot withdraw", async () => {
+      let flag = "This should fail";
+      try {
+        await program.methods.delegatedWithdraw(new anchor.BN(1)).accounts({
+          delegate: recipient.publicKey,
+          vault: vaultErinPDA,
+          withdrawDelegate: delegatePDA,
+          recipient: recipient.publicKey,
+        }).signers([recipient]).rpc({ commitment: "confirmed" });
+      } catch (error) {
+        flag = "Failed";
+        assert.isTrue(error.toString().includes("NotADelegate"), "Should fail with NotADelegate error");
+      }
+      assert.strictEqual(flag, "Failed", "Only the recorded delegate can withdraw");
+    });
+  });
+
+
+  describe("Batch balances", () => {
+    const vaults = () => [vaultAlicePDA, vaultBobPDA, vaultAnatolyPDA];
+
+    it("Reports every vault's lamports and withdrawable amount", async () => {
+      const txSig = await program.methods.batchBalances().remainingAccounts(
+        vaults().map((pubkey) => ({ pubkey, isSigner: false, isWritable: false }))
+      ).rpc({ commitment: "confirmed" });
+
+      const tx = await provider.connection.getParsedTransaction(txSig, "confirmed");
+      const eventParser = new anchor.EventParser(program.programId, new anchor.BorshCoder(program.idl));
+      const events = [...eventParser.parseLogs(tx.meta.logMessages)].filter((e) => e.name === "batchBalances");
+      assert.strictEqual(events.length, 1, "BatchBalances should have been emitted once");
+
+      const entries = events[0].data.entries;
+      assert.strictEqual(entries.length, 3, "One entry per vault");
+      const rent = await provider.connection.getMinimumBalanceForRentExemption(program.account.vault.size);
+      for (let i = 0; i < entries.length; i++) {
+        const pda = vaults()[i];
+        const balance = await provider.connection.getBalance(pda);
+        const vaultData = await program.account.vault.fetch(pda);
+        assert.strictEqual(entries[i].vault.toString(), pda.toString(), "Entries should keep input order");
+        assert.strictEqual(entries[i].lamports.toNumber(), balance, "Lamports should match the vault balance");
+        const expected = vaultData.locked ? 0 : balance - rent;
+        assert.strictEqual(entries[i].withdrawable.toNumber(), expected, "Withdrawable should exclude rent and locked vaults");
+      }
+    });
+
+    it("Rejects accounts that are not vaults", async () => {
+      let flag = "This should fail";
+      try {
+        await program.methods.batchBalances().remainingAccounts([
+          { pubkey: vaultAlicePDA, isSigner: false, isWritable: false },
+          { pubkey: alice.publicKey, isSigner: false, isWritable: false },
+        ]).rpc({ commitment: "confirmed" });
+      } catch (error) {
+        flag = "Failed";
+        assert.isTrue(error.toString().includes("AccountOwnedByWrongProgram"), "Should fail with AccountOwnedByWrongProgram error");
+      }
+      assert.strictEqual(flag, "Failed", "Non-vault accounts should be rejected");
+    });
+
+    it("Rejects batches over the size cap", async () => {
+      let flag = "This should fail";
+      try {
+        const keys = Array.from({ length: 17 }, () => anchor.web3.Keypair.generate().publicKey);
+        await program.methods.batchBalances().remainingAccounts(
+          keys.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false }))
+        ).rpc({ commitment: "confirmed" });
+      } catch (error) {
+        flag = "Failed";
+        assert.isTrue(error.toString().includes("BatchTooLarge"), "Should fail with BatchTooLarge error");
+      }
+      assert.strictEqual(flag, "Failed", "Oversized batches should be rejected");
+    });
+  });
+
+
+  describe("Deposit memo commitments", () => {
+    const frank = anchor.web3.Keypair.generate();
+    const [vaultFrankPDA] = getVaultPDA(frank.publicKey);
+    const memo = "invoice #42, paid in full";
+    const commitment = crypto.createHash("sha256").update(memo, "utf-8").digest();
+    const [notePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("deposit_note"), vaultFrankPDA.toBuffer(), commitment],
+      program.programId
+    );
+
+    before(async () => {
+      await airdrop(provider.connection, frank.publicKey);
+      await program.methods.initializeVault(DEFAULT_VAULT_ID, false, UNCAPPED).accounts({
+        vaultAuthority: frank.publicKey,
+        vault: vaultFrankPDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).signers([frank]).rpc({ commitment: "confirmed" });
+    });
+
+    it("Deposit stores the memo commitment on a note", async () => {
+      await program.methods.deposit(new anchor.BN(250000), Array.from(commitment)).accounts({
+        user: frank.publicKey,
+        vault: vaultFrankPDA,
+        depositNote: notePDA,
+      }).signers([frank]).rpc({ commitment: "confirmed" });
+
+      const noteData = await program.account.depositNote.fetch(notePDA);
+      assert.strictEqual(noteData.vault.toString(), vaultFrankPDA.toString(), "Note should reference the vault");
+      assert.strictEqual(noteData.depositor.toString(), frank.publicKey.toString(), "Note should record the depositor");
+      assert.strictEqual(noteData.amount.toString(), "250000", "Note should record the deposit amount");
+      assert.isTrue(Buffer.from(noteData.memoCommitment).equals(commitment), "Note should store the commitment");
+    });
+
+    it("Reveals a memo matching the commitment", async () => {
+      let txSig = await program.methods.revealMemo(memo).accounts({
+        depositor: frank.publicKey,
+        depositNote: notePDA,
+      }).signers([frank]).rpc({ commitment: "confirmed" });
+
+      const tx = await provider.connection.getParsedTransaction(txSig, "confirmed");
+      const eventParser = new anchor.EventParser(program.programId, new anchor.BorshCoder(program.idl));
+      const events = eventParser.parseLogs(tx.meta.logMessages);
+
+      let logsEmitted = false;
+      for (let event of events) {
+        if (event.name === "memoRevealedEvent") {
+          logsEmitted = true;
+          assert.strictEqual(event.data.memo, memo, "Event should carry the revealed memo");
+          assert.strictEqual(event.data.amount.toString(), "250000", "Event should carry the deposit amount");
+        }
+      }
+      assert.isTrue(logsEmitted, "MemoRevealedEvent should have been emitted");
+    });
+
+    it("Cannot reveal a memo that does not match the commitment", async () => {
+      let flag = "This should fail";
+      try {
+        await program.methods.revealMemo("invoice #43, paid in full").accounts({
+          depositor: frank.publicKey,
+          depositNote: notePDA,
+        }).signers([frank]).rpc({ commitment: "confirmed" });
+      } catch (error) {
+        flag = "Failed";
+        assert.isTrue(error.toString().includes("MemoMismatch"), "Should fail with MemoMismatch error");
+      }
+      assert.strictEqual(flag, "Failed", "A wrong preimage should be rejected");
+    });
+  });
+
+
+  describe("Refunds", () => {
+    const grace = anchor.web3.Keypair.generate();
+    const treasury = anchor.web3.Keypair.generate();
+    const recipient = anchor.web3.Keypair.generate();
+    const [vaultGracePDA] = getVaultPDA(grace.publicKey);
+    const [ledgerGracePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("deposit_ledger"), vaultGracePDA.toBuffer(), grace.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const refund = (amount: number, withTreasury: boolean) => {
+      const signers = withTreasury ? [grace, treasury] : [grace];
+      return program.methods.refund(new anchor.BN(amount)).accounts({
+        vaultAuthority: grace.publicKey,
+        vault: vaultGracePDA,
+        recipient: recipient.publicKey,
+        treasury: withTreasury ? treasury.publicKey : null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).signers(signers).rpc({ commitment: "confirmed" });
+    };
+
+    before(async () => {
+      await airdrop(provider.connection, grace.publicKey);
+      await airdrop(provider.connection, treasury.publicKey);
+      await airdrop(provider.connection, recipient.publicKey);
+      await program.methods.initializeVault(DEFAULT_VAULT_ID, false, UNCAPPED).accounts({
+        vaultAuthority: grace.publicKey,
+        vault: vaultGracePDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).signers([grace]).rpc({ commitment: "confirmed" });
+      await program.methods.deposit(new anchor.BN(1000000), null).accounts({
+        user: grace.publicKey,
+        vault: vaultGracePDA,
+      }).signers([grace]).rpc({ commitment: "confirmed" });
+      await program.methods.withdraw(new anchor.BN(400000)).accounts({
+        vaultAuthority: grace.publicKey,
+        vault: vaultGracePDA,
+      }).signers([grace]).rpc({ commitment: "confirmed" });
+    });
+
+    it("Withdrawals are recorded in total_withdrawn", async () => {
+      const vaultData = await program.account.vault.fetch(vaultGracePDA);
+      assert.strictEqual(vaultData.totalWithdrawn.toString(), "400000", "total_withdrawn should match the withdrawal");
+    });
+
+    it("Refund pays the recipient and lowers the net withdrawals", async () => {
+      const vaultBefore = await provider.connection.getBalance(vaultGracePDA);
+      const recipientBefore = await provider.connection.getBalance(recipient.publicKey);
+
+      await refund(150000, false);
+
+      assert.strictEqual(await provider.connection.getBalance(recipient.publicKey) - recipientBefore, 150000, "Recipient should receive the refund");
+      assert.strictEqual(await provider.connection.getBalance(vaultGracePDA), vaultBefore, "Vault balance should not change");
+      const vaultData = await program.account.vault.fetch(vaultGracePDA);
+      assert.strictEqual(vaultData.totalWithdrawn.toString(), "400000", "Vault totals should not change");
+      const ledger = await program.account.depositLedger.fetch(ledgerGracePDA);
+      assert.strictEqual(ledger.withdrawn.toString(), "250000", "Net withdrawals should be reduced by the refund");
+    });
+
+    it("Refund credits the fee back from the treasury", async () => {
+      await program.methods.setFee(250, treasury.publicKey).accounts({
+        vaultAuthority: grace.publicKey,
+        vault: vaultGracePDA,
+      }).signers([grace]).rpc({ commitment: "confirmed" });
+      const recipientBefore = await provider.connection.getBalance(recipient.publicKey);
+      const treasuryBefore = await provider.connection.getBalance(treasury.publicKey);
+
+      const txSig = await refund(100000, true);
+
+      assert.strictEqual(await provider.connection.getBalance(recipient.publicKey) - recipientBefore, 100000, "Recipient should receive the full amount");
+      assert.strictEqual(treasuryBefore - await provider.connection.getBalance(treasury.publicKey), 2500, "Treasury should credit back its fee");
+      const ledger = await program.account.depositLedger.fetch(ledgerGracePDA);
+      assert.strictEqual(ledger.withdrawn.toString(), "150000");
+
+      const tx = await provider.connection.getParsedTransaction(txSig, "confirmed");
+      const eventParser = new anchor.EventParser(program.programId, new anchor.BorshCoder(program.idl));
+      const event = [...eventParser.parseLogs(tx.meta.logMessages)].find((e) => e.name === "refundEvent");
+      assert.strictEqual(event.data.amount.toNumber(), 100000);
+      assert.strictEqual(event.data.fee.toNumber(), 2500);
+      assert.strictEqual(event.data.withdrawn.toNumber(), 150000);
+      assert.strictEqual(event.data.recipient.toString(), recipient.publicKey.toString());
+    });
+
+    it("Rejects a fee-charging refund without the treasury", async () => {
+      let flag = "This should fail";
+      try {
+        await refund(100000, false);
+      } catch (error) {
+        flag = "Failed";
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "TreasuryRequired", "Should fail with TreasuryRequired error");
+      }
+      assert.strictEqual(flag, "Failed", "Refunding without the treasury should fail");
+    });
+
+    it("Cannot refund more than the recorded withdrawals", async () => {
+      let flag = "This should fail";
+      try {
+        await refund(150001, true);
+      } catch (error) {
+        flag = "Failed";
+        assert.isTrue(error.toString().includes("RefundExceedsWithdrawn"), "Should fail with RefundExceedsWithdrawn error");
+      }
+      assert.strictEqual(flag, "Failed", "Refunding over the net withdrawals should fail");
+    });
+  });
+
+
+  describe("Reconciliation", () => {
+    const henry = anchor.web3.Keypair.generate();
+    const [vaultHenryPDA] = getVaultPDA(henry.publicKey);
+
+    const reconcile = async () => {
+      let txSig = await program.methods.reconcile().accounts({
+        vault: vaultHenryPDA,
+      }).rpc({ commitment: "confirmed" });
+
+      const tx = await provider.connection.getParsedTransaction(txSig, "confirmed");
+      const eventParser = new anchor.EventParser(program.programId, new anchor.BorshCoder(program.idl));
+      const events = [...eventParser.parseLogs(tx.meta.logMessages)].filter((e) => e.name === "reconciliation");
+      assert.strictEqual(events.length, 1, "Reconciliation should have been emitted once");
+      return events[0].data;
+    };
+
+    before(async () => {
+      await airdrop(provider.connection, henry.publicKey);
+      await program.methods.initializeVault(DEFAULT_VAULT_ID, false, UNCAPPED).accounts({
+        vaultAuthority: henry.publicKey,
+        vault: vaultHenryPDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).signers([henry]).rpc({ commitment: "confirmed" });
+      await program.methods.deposit(new anchor.BN(700000), null).accounts({
+        user: henry.publicKey,
+        vault: vaultHenryPDA,
+      }).signers([henry]).rpc({ commitment: "confirmed" });
+      await program.methods.withdraw(new anchor.BN(200000)).accounts({
+        vaultAuthority: henry.publicKey,
+        vault: vaultHenryPDA,
+      }).signers([henry]).rpc({ commitment: "confirmed" });
+    });
+
+    it("Reports no drift when all lamports moved through the program", async () => {
+      const data = await reconcile();
+      const balance = await provider.connection.getBalance(vaultHenryPDA);
+      assert.strictEqual(data.actual.toNumber(), balance, "Actual should be the vault balance");
+      assert.strictEqual(data.expected.toNumber(), balance, "Expected should match the balance");
+      assert.strictEqual(data.drift.toNumber(), 0, "Drift should be zero");
+    });
+
+    it("Reports drift after a direct transfer to the vault", async () => {
+      const tx = new anchor.web3.Transaction().add(anchor.web3.SystemProgram.transfer({
+        fromPubkey: henry.publicKey,
+        toPubkey: vaultHenryPDA,
+        lamports: 12345,
+      }));
+      await anchor.web3.sendAndConfirmTransaction(provider.connection, tx, [henry], { commitment: "confirmed" });
+
+      const data = await reconcile();
+      assert.strictEqual(data.drift.toNumber(), 12345, "Drift should equal the out-of-band transfer");
+      assert.strictEqual(data.actual.toNumber() - data.expected.toNumber(), 12345, "Actual should exceed expected by the transfer");
+    });
+
+    it("Reports drift after withdrawing out-of-band funding", async () => {
+      const tx = new anchor.web3.Transaction().add(anchor.web3.SystemProgram.transfer({
+        fromPubkey: henry.publicKey,
+        toPubkey: vaultHenryPDA,
+        lamports: 5 * anchor.web3.LAMPORTS_PER_SOL,
+      }));
+      await anchor.web3.sendAndConfirmTransaction(provider.connection, tx, [henry], { commitment: "confirmed" });
+      await program.methods.withdraw(new anchor.BN(4 * anchor.web3.LAMPORTS_PER_SOL)).accounts({
+        vaultAuthority: henry.publicKey,
+        vault: vaultHenryPDA,
+      }).signers([henry]).rpc({ commitment: "confirmed" });
+
+      const data = await reconcile();
+      assert.isTrue(data.expected.isNeg(), "Expected should go below zero");
+      assert.strictEqual(data.actual.toNumber(), await provider.connection.getBalance(vaultHenryPDA));
+      assert.strictEqual(data.drift.toNumber(), 5 * anchor.web3.LAMPORTS_PER_SOL + 12345, "Drift should equal all out-of-band transfers");
+    });
+  });
+
+  describe("Balance report", () => {
+    const ivan = anchor.web3.Keypair.generate();
+    const [vaultIvanPDA] = getVaultPDA(ivan.publicKey);
+
+    before(async () => {
+      await airdrop(provider.connection, ivan.publicKey);
+      await program.methods.initializeVault(DEFAULT_VAULT_ID, false, UNCAPPED).accounts({
+        vaultAuthority: ivan.publicKey,
+        vault: vaultIvanPDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).signers([ivan]).rpc({ commitment: "confirmed" });
+    });
+
+    it("Reports the balance after a deposit and a partial withdrawal", async () => {
+      await program.methods.deposit(new anchor.BN(900000), null).accounts({
+        user: ivan.publicKey,
+        vault: vaultIvanPDA,
+      }).signers([ivan]).rpc({ commitment: "confirmed" });
+      await program.methods.withdraw(new anchor.BN(350000)).accounts({
+        vaultAuthority: ivan.publicKey,
+        vault: vaultIvanPDA,
+      }).signers([ivan]).rpc({ commitment: "confirmed" });
+
+      const txSig = await program.methods.vaultBalance().accounts({
+        vault: vaultIvanPDA,
+      }).rpc({ commitment: "confirmed" });
+
+      const tx = await provider.connection.getParsedTransaction(txSig, "confirmed");
+      const eventParser = new anchor.EventParser(program.programId, new anchor.BorshCoder(program.idl));
+      const events = [...eventParser.parseLogs(tx.meta.logMessages)].filter((e) => e.name === "balanceReported");
+      assert.strictEqual(events.length, 1, "BalanceReported should have been emitted once");
+
+      const info = await provider.connection.getAccountInfo(vaultIvanPDA);
+      const rent = await provider.connection.getMinimumBalanceForRentExemption(info.data.length);
+      assert.strictEqual(events[0].data.vault.toString(), vaultIvanPDA.toString());
+      assert.strictEqual(events[0].data.lamports.toNumber(), rent + 900000 - 350000, "Balance should be rent plus the net deposit");
+      assert.strictEqual(events[0].data.lamports.toNumber(), info.lamports);
+    });
+  });
+
+
+  describe("Max balance", () => {
+    const ivan = anchor.web3.Keypair.generate();
+    const [vaultIvanPDA] = getVaultPDA(ivan.publicKey);
+
+    const deposit = (amount: number) =>
+      program.methods.deposit(new anchor.BN(amount), null).accounts({
+        user: ivan.publicKey,
+        vault: vaultIvanPDA,
+      }).signers([ivan]).rpc({ commitment: "confirmed" });
+
+    before(async () => {
+      await airdrop(provider.connection, ivan.publicKey);
+      await program.methods.initializeVault(DEFAULT_VAULT_ID, false, UNCAPPED).accounts({
+        vaultAuthority: ivan.publicKey,
+        vault: vaultIvanPDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).signers([ivan]).rpc({ commitment: "confirmed" });
+    });
+
+    it("A new vault stores the max balance given at init", async () => {
+      const vaultData = await program.account.vault.fetch(vaultIvanPDA);
+      assert.strictEqual(vaultData.maxBalance.toString(), UNCAPPED.toString(), "Vault should start uncapped");
+    });
+
+    it("Deposits up to exactly the max balance succeed", async () => {
+      const balance = await provider.connection.getBalance(vaultIvanPDA);
+      const cap = balance + 500000;
+      await program.methods.setMaxBalance(new anchor.BN(cap)).accounts({
+        vaultAuthority: ivan.publicKey,
+        vault: vaultIvanPDA,
+      }).signers([ivan]).rpc({ commitment: "confirmed" });
+
+      await deposit(500000);
+      assert.strictEqual(await provider.connection.getBalance(vaultIvanPDA), cap, "Vault should sit exactly at the cap");
+    });
+
+    it("Depositing one lamport over the max balance fails", async () => {
+      let flag = "This should fail";
+      try {
+        await deposit(1);
+      } catch (error) {
+        flag = "Failed";
+        assert.isTrue(error.toString().includes("MaxBalanceExceeded"), "Should fail with MaxBalanceExceeded error");
+      }
+      assert.strictEqual(flag, "Failed", "Depositing past the cap should fail");
+    });
+
+    it("Setting the max balance back to u64::MAX uncaps the vault", async () => {
+      await program.methods.setMaxBalance(UNCAPPED).accounts({
+        vaultAuthority: ivan.publicKey,
+        vault: vaultIvanPDA,
+      }).signers([ivan]).rpc({ commitment: "confirmed" });
+
+      await deposit(1);
+      const vaultData = await program.account.vault.fetch(vaultIvanPDA);
+      assert.strictEqual(vaultData.totalDeposited.toString(), "500001", "Both deposits should be recorded");
+    });
+  });
+
+
+  describe("Recipient allowlist", () => {
+    const judy = anchor.web3.Keypair.generate();
+    const kate = anchor.web3.Keypair.generate();
+    const leo = anchor.web3.Keypair.generate();
+    const [vaultJudyPDA] = getVaultPDA(judy.publicKey);
+
+    const getAllowedRecipientPDA = (recipient: anchor.web3.PublicKey) => {
+      return anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("allowed_recipient"), vaultJudyPDA.toBuffer(), recipient.toBuffer()],
+        program.programId
+      );
+    };
+    const [allowedKatePDA] = getAllowedRecipientPDA(kate.publicKey);
+
+    const withdrawTo = (recipient: anchor.web3.PublicKey, allowedRecipient: anchor.web3.PublicKey | null, amount = 100000) =>
+      program.methods.withdrawTo(new anchor.BN(amount)).accounts({
+        vaultAuthority: judy.publicKey,
+        vault: vaultJudyPDA,
+        recipient,
+        allowedRecipient,
+      }).signers([judy]).rpc({ commitment: "confirmed" });
+
+    const expectNotAllowed = async (recipient: anchor.web3.PublicKey, allowedRecipient: anchor.web3.PublicKey | null) => {
+      let flag = "This should fail";
+      try {
+        await withdrawTo(recipient, allowedRecipient);
+      } catch (error) {
+        flag = "Failed";
+        assert.isTrue(error.toString().includes("RecipientNotAllowed"), "Should fail with RecipientNotAllowed error");
+      }
+      assert.strictEqual(flag, "Failed", "Withdrawing to a recipient off the allowlist should fail");
+    };
+
+    before(async () => {
+      for (const k of [judy, kate, leo]) await airdrop(provider.connection, k.publicKey);
+      await program.methods.initializeVault(DEFAULT_VAULT_ID, false, UNCAPPED).accounts({
+        vaultAuthority: judy.publicKey,
+        vault: vaultJudyPDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).signers([judy]).rpc({ commitment: "confirmed" });
+      await program.methods.deposit(new anchor.BN(2000000), null).accounts({
+        user: judy.publicKey,
+        vault: vaultJudyPDA,
+      }).signers([judy]).rpc({ commitment: "confirmed" });
+    });
+
+    it("Any recipient can be paid while the vault is not gated", async () => {
+      const before = await provider.connection.getBalance(leo.publicKey);
+      await withdrawTo(leo.publicKey, null);
+      const after = await provider.connection.getBalance(leo.publicKey);
+      assert.strictEqual(after - before, 100000, "Leo should receive the withdrawal");
+    });
+
+    it("Only the vault authority can withdraw to a recipient", async () => {
+      const before = await provider.connection.getBalance(vaultJudyPDA);
+      let flag = "This should fail";
+      try {
+        await program.methods.withdrawTo(new anchor.BN(100000)).accounts({
+          vaultAuthority: leo.publicKey,
+          vault: vaultJudyPDA,
+          recipient: leo.publicKey,
+          allowedRecipient: null,
+        }).signers([leo]).rpc({ commitment: "confirmed" });
+      } catch (error) {
+        flag = "Failed";
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "ConstraintSeeds", "Should fail the vault seeds check");
+      }
+      assert.strictEqual(flag, "Failed", "A non-authority signer should not withdraw");
+      assert.strictEqual(await provider.connection.getBalance(vaultJudyPDA), before, "Vault balance should not change");
+    });
+
+    it("A gated vault pays out to an allowed recipient", async () => {
+      await program.methods.setRecipientsGated(true).accounts({
+        vaultAuthority: judy.publicKey,
+        vault: vaultJudyPDA,
+      }).signers([judy]).rpc({ commitment: "confirmed" });
+      await program.methods.allowRecipient().accounts({
+        vaultAuthority: judy.publicKey,
+        vault: vaultJudyPDA,
+        recipient: kate.publicKey,
+        allowedRecipient: allowedKatePDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).signers([judy]).rpc({ commitment: "confirmed" });
+
+      const before = await provider.connection.getBalance(kate.publicKey);
+      await withdrawTo(kate.publicKey, allowedKatePDA);
+      const after = await provider.connection.getBalance(kate.publicKey);
+      assert.strictEqual(after - before, 100000, "Kate should receive the withdrawal");
+    });
+
+    it("A gated vault rejects recipients off the allowlist", async () => {
+      await expectNotAllowed(leo.publicKey, null);
+      await expectNotAllowed(leo.publicKey, allowedKatePDA);
+    });
+
+    it("A disallowed recipient can no longer be paid", async () => {
+      await program.methods.disallowRecipient().accounts({
+        vaultAuthority: judy.publicKey,
+        vault: vaultJudyPDA,
+        allowedRecipient: allowedKatePDA,
+      }).signers([judy]).rpc({ commitment: "confirmed" });
+
+      assert.isNull(await provider.connection.getAccountInfo(allowedKatePDA), "Allowlist entry should be closed");
+      await expectNotAllowed(kate.publicKey, null);
+    });
+  });
+
+
+  describe("Lock and unlock", () => {
+    const owner = anchor.web3.Keypair.generate();
+    const [ownerVaultPDA] = getVaultPDA(owner.publicKey);
+
+    const deposit = () => program.methods.deposit(new anchor.BN(100000), null).accounts({
+      user: owner.publicKey,
+      vault: ownerVaultPDA,
+    }).signers([owner]).rpc({ commitment: "confirmed" });
+
+    const withdraw = () => program.methods.withdraw(new anchor.BN(50000)).accounts({
+      vaultAuthority: owner.publicKey,
+      vault: ownerVaultPDA,
+    }).signers([owner]).rpc({ commitment: "confirmed" });
+
+    const expectLocked = async (call: () => Promise<string>) => {
+      let flag = "This should fail";
+      try {
+        await call();
+      } catch (error) {
+        flag = "Failed";
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "VaultLocked", "Should fail with VaultLocked error");
+      }
+      assert.strictEqual(flag, "Failed", "Call on a locked vault should fail");
+    };
+
+    const eventNames = async (txSig: string) => {
+      const tx = await provider.connection.getParsedTransaction(txSig, "confirmed");
+      const eventParser = new anchor.EventParser(program.programId, new anchor.BorshCoder(program.idl));
+      return Array.from(eventParser.parseLogs(tx.meta.logMessages)).map((event) => event.name);
+    };
+
+    before(async () => {
+      await airdrop(provider.connection, owner.publicKey);
+      await program.methods.initializeVault(DEFAULT_VAULT_ID, false, UNCAPPED).accounts({
+        vaultAuthority: owner.publicKey,
+        vault: ownerVaultPDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).signers([owner]).rpc({ commitment: "confirmed" });
+      await deposit();
+    });
+
+    it("Locking rejects deposits and withdrawals", async () => {
+      const txSig = await program.methods.lockVault().accounts({
+        vaultAuthority: owner.publicKey,
+        vault: ownerVaultPDA,
+      }).signers([owner]).rpc({ commitment: "confirmed" });
+
+      assert.include(await eventNames(txSig), "lockEvent", "LockEvent should have been emitted");
+      assert.strictEqual((await program.account.vault.fetch(ownerVaultPDA)).locked, true);
+      await expectLocked(deposit);
+      await expectLocked(withdraw);
+    });
+
+    it("Only the vault authority can unlock", async () => {
+      let flag = "This should fail";
+      try {
+        await program.methods.unlockVault().accounts({
+          vaultAuthority: alice.publicKey,
+          vault: ownerVaultPDA,
+        }).signers([alice]).rpc({ commitment: "confirmed" });
+      } catch (error) {
+        flag = "Failed";
+      }
+      assert.strictEqual(flag, "Failed", "Unlocking someone else's vault should fail");
+      assert.strictEqual((await program.account.vault.fetch(ownerVaultPDA)).locked, true);
+    });
+
+    it("Unlocking accepts deposits and withdrawals again", async () => {
+      const txSig = await program.methods.unlockVault().accounts({
+        vaultAuthority: owner.publicKey,
+        vault: ownerVaultPDA,
+      }).signers([owner]).rpc({ commitment: "confirmed" });
+
+      assert.include(await eventNames(txSig), "unlockEvent", "UnlockEvent should have been emitted");
+      assert.strictEqual((await program.account.vault.fetch(ownerVaultPDA)).locked, false);
+
+      const balanceBefore = await provider.connection.getBalance(ownerVaultPDA);
+      await deposit();
+      await withdraw();
+      assert.strictEqual(await provider.connection.getBalance(ownerVaultPDA), balanceBefore + 50000);
+    });
+  });
+
+  describe("Time lock", () => {
+    const owner = anchor.web3.Keypair.generate();
+    const [ownerVaultPDA] = getVaultPDA(owner.publicKey);
+    let unlockTs: number;
+
+    const setUnlockTs = (ts: number) => program.methods.setUnlockTs(new anchor.BN(ts)).accounts({
+      vaultAuthority: owner.publicKey,
+      vault: ownerVaultPDA,
+    }).signers([owner]).rpc({ commitment: "confirmed" });
+
+    const deposit = () => program.methods.deposit(new anchor.BN(100000), null).accounts({
+      user: owner.publicKey,
+      vault: ownerVaultPDA,
+    }).signers([owner]).rpc({ commitment: "confirmed" });
+
+    const withdraw = () => program.methods.withdraw(new anchor.BN(100000)).accounts({
+      vaultAuthority: owner.publicKey,
+      vault: ownerVaultPDA,
+    }).signers([owner]).rpc({ commitment: "confirmed" });
+
+    const expectTimeLocked = async (call: () => Promise<string>) => {
+      let flag = "This should fail";
+      try {
+        await call();
+      } catch (error) {
+        flag = "Failed";
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "TimeLocked", "Should fail with TimeLocked error");
+      }
+      assert.strictEqual(flag, "Failed", "Call should fail while the vault is time locked");
+    };
+
+    before(async () => {
+      await airdrop(provider.connection, owner.publicKey);
+      await program.methods.initializeVault(DEFAULT_VAULT_ID, false, UNCAPPED).accounts({
+        vaultAuthority: owner.publicKey,
+        vault: ownerVaultPDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).signers([owner]).rpc({ commitment: "confirmed" });
+      await deposit();
+
+      const now = await provider.connection.getBlockTime(await provider.connection.getSlot("confirmed"));
+      unlockTs = now + 5;
+      await setUnlockTs(unlockTs);
+    });
+
+    it("Withdrawing before the unlock time fails", async () => {
+      const vaultData = await program.account.vault.fetch(ownerVaultPDA);
+      assert.strictEqual(vaultData.unlockTs.toNumber(), unlockTs);
+      await expectTimeLocked(withdraw);
+    });
+
+    it("Deposits are still accepted while time locked", async () => {
+      const before = await provider.connection.getBalance(ownerVaultPDA);
+      await deposit();
+      assert.strictEqual(await provider.connection.getBalance(ownerVaultPDA), before + 100000);
+    });
+
+    it("The unlock time cannot be brought forward while time locked", async () => {
+      await expectTimeLocked(() => setUnlockTs(0));
+    });
+
+    it("Withdrawing after the unlock time succeeds", async () => {
+      await sleep(7000);
+      const before = await provider.connection.getBalance(ownerVaultPDA);
+      await withdraw();
+      assert.strictEqual(await provider.connection.getBalance(ownerVaultPDA), before - 100000);
+    });
+  });
+
+  describe("Deposit ledger", () => {
+    const owner = anchor.web3.Keypair.generate();
+    const depositor = anchor.web3.Keypair.generate();
+    const [ownerVaultPDA] = getVaultPDA(owner.publicKey);
+
+    const getLedgerPDA = (vault: anchor.web3.PublicKey, user: anchor.web3.PublicKey) => {
+      return anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("deposit_ledger"), vault.toBuffer(), user.toBuffer()],
+        program.programId
+      );
+    };
+
+    const deposit = (user: anchor.web3.Keypair, vault: anchor.web3.PublicKey, amount: number) =>
+      program.methods.deposit(new anchor.BN(amount), null).accounts({
+        user: user.publicKey,
+        vault,
+      }).signers([user]).rpc({ commitment: "confirmed" });
+
+    const withdraw = (amount: number) => program.methods.withdraw(new anchor.BN(amount)).accounts({
+      vaultAuthority: owner.publicKey,
+      vault: ownerVaultPDA,
+    }).signers([owner]).rpc({ commitment: "confirmed" });
+
+    before(async () => {
+      await airdrop(provider.connection, owner.publicKey);
+      await airdrop(provider.connection, depositor.publicKey);
+      await program.methods.initializeVault(DEFAULT_VAULT_ID, false, UNCAPPED).accounts({
+        vaultAuthority: owner.publicKey,
+        vault: ownerVaultPDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).signers([owner]).rpc({ commitment: "confirmed" });
+    });
+
+    it("Sums a user's deposits across several calls", async () => {
+      for (const amount of [100000, 250000, 650000]) await deposit(depositor, ownerVaultPDA, amount);
+
+      const [ledgerPDA] = getLedgerPDA(ownerVaultPDA, depositor.publicKey);
+      const ledger = await program.account.depositLedger.fetch(ledgerPDA);
+      assert.strictEqual(ledger.vault.toString(), ownerVaultPDA.toString());
+      assert.strictEqual(ledger.user.toString(), depositor.publicKey.toString());
+      assert.strictEqual(ledger.deposited.toString(), "1000000");
+      assert.strictEqual(ledger.withdrawn.toString(), "0");
+    });
+
+    it("Sums the authority's withdrawals", async () => {
+      await withdraw(300000);
+      await withdraw(200000);
+
+      const [ledgerPDA] = getLedgerPDA(ownerVaultPDA, owner.publicKey);
+      const ledger = await program.account.depositLedger.fetch(ledgerPDA);
+      assert.strictEqual(ledger.deposited.toString(), "0");
+      assert.strictEqual(ledger.withdrawn.toString(), "500000");
+    });
+
+    it("Records withdraw_to, executed and delegated withdrawals for the authority", async () => {
+      const recipient = anchor.web3.Keypair.generate();
+      const delegate = anchor.web3.Keypair.generate();
+      const [delegatePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("wd_delegate"), ownerVaultPDA.toBuffer(), delegate.publicKey.toBuffer()],
+        program.programId
+      );
+      const [pendingPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("pending"), ownerVaultPDA.toBuffer()],
+        program.programId
+      );
+      await airdrop(provider.connection, recipient.publicKey);
+      await airdrop(provider.connection, delegate.publicKey);
+
+      await program.methods.withdrawTo(new anchor.BN(10000)).accounts({
+        vaultAuthority: owner.publicKey,
+        vault: ownerVaultPDA,
+        recipient: recipient.publicKey,
+        allowedRecipient: null,
+      }).signers([owner]).rpc({ commitment: "confirmed" });
+      await program.methods.requestWithdrawal(new anchor.BN(20000)).accounts({
+        vaultAuthority: owner.publicKey,
+        vault: ownerVaultPDA,
+        pendingWithdrawal: pendingPDA,
+      }).signers([owner]).rpc({ commitment: "confirmed" });
+      await sleep(7000);
+      await program.methods.executePending().accounts({
+        vaultAuthority: owner.publicKey,
+        vault: ownerVaultPDA,
+        pendingWithdrawal: pendingPDA,
+      }).signers([owner]).rpc({ commitment: "confirmed" });
+      await program.methods.setDelegate(new anchor.BN(30000), recipient.publicKey).accounts({
+        vaultAuthority: owner.publicKey,
+        vault: ownerVaultPDA,
+        delegate: delegate.publicKey,
+        withdrawDelegate: delegatePDA,
+      }).signers([owner]).rpc({ commitment: "confirmed" });
+      await program.methods.delegatedWithdraw(new anchor.BN(30000)).accounts({
+        delegate: delegate.publicKey,
+        vault: ownerVaultPDA,
+        withdrawDelegate: delegatePDA,
+        recipient: recipient.publicKey,
+      }).signers([delegate]).rpc({ commitment: "confirmed" });
+
+      const [ledgerPDA] = getLedgerPDA(ownerVaultPDA, owner.publicKey);
+      const ledger = await program.account.depositLedger.fetch(ledgerPDA);
+      assert.strictEqual(ledger.withdrawn.toString(), "560000", "Every outflow should be recorded");
+      assert.isNull(await provider.connection.getAccountInfo(getLedgerPDA(ownerVaultPDA, delegate.publicKey)[0]), "The delegate should get no ledger");
+    });
+
+    it("Rejects a deposit that would overflow the ledger", async () => {
+      // its ledger is loaded by the test validator from tests/fixtures/full_deposit_ledger.json
+      const secretKey = JSON.parse(fs.readFileSync("tests/fixtures/ledger_authority.json", "utf8"));
+      const ledgerAuthority = anchor.web3.Keypair.fromSecretKey(Uint8Array.from(secretKey));
+      const [ledgerVaultPDA] = getVaultPDA(ledgerAuthority.publicKey);
+      await airdrop(provider.connection, ledgerAuthority.publicKey);
+      await program.methods.initializeVault(DEFAULT_VAULT_ID, false, UNCAPPED).accounts({
+        vaultAuthority: ledgerAuthority.publicKey,
+        vault: ledgerVaultPDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).signers([ledgerAuthority]).rpc({ commitment: "confirmed" });
+
+      let flag = "This should fail";
+      try {
+        await deposit(ledgerAuthority, ledgerVaultPDA, 1001);
+      } catch (error) {
+        flag = "Failed";
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "Overflow", "Should fail with Overflow error");
+      }
+      assert.strictEqual(flag, "Failed", "Overflowing the ledger should fail");
+
+      await deposit(ledgerAuthority, ledgerVaultPDA, 1000);
+      const [ledgerPDA] = getLedgerPDA(ledgerVaultPDA, ledgerAuthority.publicKey);
+      const ledger = await program.account.depositLedger.fetch(ledgerPDA);
+      assert.strictEqual(ledger.deposited.toString(), "18446744073709551615");
+    });
+  });
+
+  describe("Daily limit", () => {
+    const owner = anchor.web3.Keypair.generate();
+    const [ownerVaultPDA] = getVaultPDA(owner.publicKey);
+
+    const withdraw = (authority: anchor.web3.Keypair, vault: anchor.web3.PublicKey, amount: number) =>
+      program.methods.withdraw(new anchor.BN(amount)).accounts({
+        vaultAuthority: authority.publicKey,
+        vault,
+      }).signers([authority]).rpc({ commitment: "confirmed" });
+
+    before(async () => {
+      await airdrop(provider.connection, owner.publicKey);
+      await program.methods.initializeVault(DEFAULT_VAULT_ID, false, UNCAPPED).accounts({
+        vaultAuthority: owner.publicKey,
+        vault: ownerVaultPDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).signers([owner]).rpc({ commitment: "confirmed" });
+      await program.methods.deposit(new anchor.BN(2000000), null).accounts({
+        user: owner.publicKey,
+        vault: ownerVaultPDA,
+      }).signers([owner]).rpc({ commitment: "confirmed" });
+      await program.methods.setDailyLimit(new anchor.BN(500000)).accounts({
+        vaultAuthority: owner.publicKey,
+        vault: ownerVaultPDA,
+      }).signers([owner]).rpc({ commitment: "confirmed" });
+    });
+
+    it("Blocks withdrawals over the limit within the window", async () => {
+      await withdraw(owner, ownerVaultPDA, 300000);
+
+      let flag = "This should fail";
+      try {
+        await withdraw(owner, ownerVaultPDA, 200001);
+      } catch (error) {
+        flag = "Failed";
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "DailyLimitExceeded", "Should fail with DailyLimitExceeded error");
+      }
+      assert.strictEqual(flag, "Failed", "Withdrawing over the daily limit should fail");
+
+      await withdraw(owner, ownerVaultPDA, 200000);
+      const vaultData = await program.account.vault.fetch(ownerVaultPDA);
+      assert.strictEqual(vaultData.withdrawnInWindow.toString(), "500000", "The limit can be used up exactly");
+    });
+
+    it("Starts a new window once a day has passed", async () => {
+      // loaded by the test validator from tests/fixtures/expired_window_vault.json
+      const secretKey = JSON.parse(fs.readFileSync("tests/fixtures/window_authority.json", "utf8"));
+      const windowAuthority = anchor.web3.Keypair.fromSecretKey(Uint8Array.from(secretKey));
+      const [windowVaultPDA] = getVaultPDA(windowAuthority.publicKey);
+      await airdrop(provider.connection, windowAuthority.publicKey);
+
+      const before = await program.account.vault.fetch(windowVaultPDA);
+      assert.strictEqual(before.withdrawnInWindow.toString(), before.dailyLimit.toString(), "Fixture should have used up its limit");
+
+      await withdraw(windowAuthority, windowVaultPDA, 300000);
+
+      const after = await program.account.vault.fetch(windowVaultPDA);
+      assert.strictEqual(after.withdrawnInWindow.toString(), "300000", "The window should have been reset");
+      assert.isAbove(after.windowStartTs.toNumber(), before.windowStartTs.toNumber(), "The window should start now");
+    });
+  });
+
+  describe("Withdrawal fee", () => {
+    const owner = anchor.web3.Keypair.generate();
+    const treasury = anchor.web3.Keypair.generate();
+    const [ownerVaultPDA] = getVaultPDA(owner.publicKey);
+
+    const setFee = (feeBps: number) => program.methods.setFee(feeBps, treasury.publicKey).accounts({
+      vaultAuthority: owner.publicKey,
+      vault: ownerVaultPDA,
+    }).signers([owner]).rpc({ commitment: "confirmed" });
+
+    before(async () => {
+      await airdrop(provider.connection, owner.publicKey);
+      await airdrop(provider.connection, treasury.publicKey);
+      await program.methods.initializeVault(DEFAULT_VAULT_ID, false, UNCAPPED).accounts({
+        vaultAuthority: owner.publicKey,
+        vault: ownerVaultPDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).signers([owner]).rpc({ commitment: "confirmed" });
+      await program.methods.deposit(new anchor.BN(5000000), null).accounts({
+        user: owner.publicKey,
+        vault: ownerVaultPDA,
+      }).signers([owner]).rpc({ commitment: "confirmed" });
+    });
+
+    for (const [feeBps, expectedFee] of [[0, 0], [250, 25000], [10000, 1000000]]) {
+      it(`Splits a withdrawal at ${feeBps} bps`, async () => {
+        const amount = 1000000;
+        await setFee(feeBps);
+
+        const vaultBefore = await provider.connection.getBalance(ownerVaultPDA);
+        const treasuryBefore = await provider.connection.getBalance(treasury.publicKey);
+        const txSig = await program.methods.withdraw(new anchor.BN(amount)).accounts({
+          vaultAuthority: owner.publicKey,
+          vault: ownerVaultPDA,
+          treasury: treasury.publicKey,
+        }).signers([owner]).rpc({ commitment: "confirmed" });
+
+        const tx = await provider.connection.getParsedTransaction(txSig, "confirmed");
+        const ownerIndex = tx.transaction.message.accountKeys.findIndex((key) => key.pubkey.equals(owner.publicKey));
+        const ownerReceived = tx.meta.postBalances[ownerIndex] - tx.meta.preBalances[ownerIndex] + tx.meta.fee;
+
+        assert.strictEqual(await provider.connection.getBalance(ownerVaultPDA), vaultBefore - amount, "Vault should pay out the full amount");
+        assert.strictEqual(await provider.connection.getBalance(treasury.publicKey) - treasuryBefore, expectedFee, "Treasury should get the fee");
+        assert.strictEqual(ownerReceived, amount - expectedFee, "Authority should get the rest");
+
+        const eventParser = new anchor.EventParser(program.programId, new anchor.BorshCoder(program.idl));
+        const event = [...eventParser.parseLogs(tx.meta.logMessages)].find((e) => e.name === "withdrawEvent");
+        assert.strictEqual(event.data.amount.toNumber(), amount);
+        assert.strictEqual(event.data.fee.toNumber(), expectedFee);
+        assert.strictEqual(event.data.treasury.toString(), treasury.publicKey.toString());
+      });
+    }
+
+    it("Rejects a fee above 10000 bps", async () => {
+      let flag = "This should fail";
+      try {
+        await setFee(10001);
+      } catch (error) {
+        flag = "Failed";
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "FeeTooHigh", "Should fail with FeeTooHigh error");
+      }
+      assert.strictEqual(flag, "Failed", "Setting a fee over 100% should fail");
+    });
+
+    it("Rejects a fee-charging withdrawal without the treasury", async () => {
+      await setFee(100);
+      let flag = "This should fail";
+      try {
+        await program.methods.withdraw(new anchor.BN(100000)).accounts({
+          vaultAuthority: owner.publicKey,
+          vault: ownerVaultPDA,
+          treasury: null,
+        }).signers([owner]).rpc({ commitment: "confirmed" });
+      } catch (error) {
+        flag = "Failed";
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "TreasuryRequired", "Should fail with TreasuryRequired error");
+      }
+      assert.strictEqual(flag, "Failed", "Withdrawing without the treasury should fail");
+    });
+
+    it("Charges the fee on every payout path", async () => {
+      const recipient = anchor.web3.Keypair.generate();
+      const delegate = anchor.web3.Keypair.generate();
+      const [delegatePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("wd_delegate"), ownerVaultPDA.toBuffer(), delegate.publicKey.toBuffer()],
+        program.programId
+      );
+      const [pendingPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("pending"), ownerVaultPDA.toBuffer()],
+        program.programId
+      );
+      await airdrop(provider.connection, recipient.publicKey);
+      await airdrop(provider.connection, delegate.publicKey);
+      await setFee(250);
+      await program.methods.setDelegate(new anchor.BN(100000), recipient.publicKey).accounts({
+        vaultAuthority: owner.publicKey,
+        vault: ownerVaultPDA,
+        delegate: delegate.publicKey,
+        withdrawDelegate: delegatePDA,
+      }).signers([owner]).rpc({ commitment: "confirmed" });
+      await program.methods.requestWithdrawal(new anchor.BN(100000)).accounts({
+        vaultAuthority: owner.publicKey,
+        vault: ownerVaultPDA,
+        pendingWithdrawal: pendingPDA,
+      }).signers([owner]).rpc({ commitment: "confirmed" });
+      await sleep(7000);
+
+      const payouts: [string, () => Promise<string>][] = [
+        ["withdrawToEvent", () => program.methods.withdrawTo(new anchor.BN(100000)).accounts({
+          vaultAuthority: owner.publicKey,
+          vault: ownerVaultPDA,
+          recipient: recipient.publicKey,
+          allowedRecipient: null,
+          treasury: treasury.publicKey,
+        }).signers([owner]).rpc({ commitment: "confirmed" })],
+        ["delegatedWithdrawEvent", () => program.methods.delegatedWithdraw(new anchor.BN(100000)).accounts({
+          delegate: delegate.publicKey,
+          vault: ownerVaultPDA,
+          withdrawDelegate: delegatePDA,
+          recipient: recipient.publicKey,
+          treasury: treasury.publicKey,
+        }).signers([delegate]).rpc({ commitment: "confirmed" })],
+        ["withdrawEvent", () => program.methods.executePending().accounts({
+          vaultAuthority: owner.publicKey,
+          vault: ownerVaultPDA,
+          pendingWithdrawal: pendingPDA,
+          treasury: treasury.publicKey,
+        }).signers([owner]).rpc({ commitment: "confirmed" })],
+      ];
+      const eventParser = new anchor.EventParser(program.programId, new anchor.BorshCoder(program.idl));
+      for (const [eventName, payout] of payouts) {
+        const vaultBefore = await provider.connection.getBalance(ownerVaultPDA);
+        const treasuryBefore = await provider.connection.getBalance(treasury.publicKey);
+        const txSig = await payout();
+
+        assert.strictEqual(await provider.connection.getBalance(ownerVaultPDA), vaultBefore - 100000, `${eventName}: vault should pay out the full amount`);
+        assert.strictEqual(await provider.connection.getBalance(treasury.publicKey) - treasuryBefore, 2500, `${eventName}: treasury should get the fee`);
+
+        const tx = await provider.connection.getParsedTransaction(txSig, "confirmed");
+        const event = [...eventParser.parseLogs(tx.meta.logMessages)].find((e) => e.name === eventName);
+        assert.strictEqual(event.data.fee.toNumber(), 2500);
+        assert.strictEqual(event.data.treasury.toString(), treasury.publicKey.toString());
+      }
+    });
+  });
+
+  describe("SPL tokens", () => {
+    const owner = anchor.web3.Keypair.generate();
+    const depositor = anchor.web3.Keypair.generate();
+    const [ownerVaultPDA] = getVaultPDA(owner.publicKey);
+    let mint: anchor.web3.PublicKey;
+    let depositorTokenAccount: anchor.web3.PublicKey;
+    let ownerTokenAccount: anchor.web3.PublicKey;
+    let vaultTokenAccount: anchor.web3.PublicKey;
+
+    const tokenBalance = async (account: anchor.web3.PublicKey) =>
+      Number((await getAccount(provider.connection, account, "confirmed")).amount);
+
+    const depositSpl = (amount: number) => program.methods.depositSpl(new anchor.BN(amount)).accounts({
+      user: depositor.publicKey,
+      vault: ownerVaultPDA,
+      mint,
+      userTokenAccount: depositorTokenAccount,
+      vaultTokenAccount,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).signers([depositor]).rpc({ commitment: "confirmed" });
+
+    before(async () => {
+      await airdrop(provider.connection, owner.publicKey);
+      await airdrop(provider.connection, depositor.publicKey);
+      await program.methods.initializeVault(DEFAULT_VAULT_ID, false, UNCAPPED).accounts({
+        vaultAuthority: owner.publicKey,
+        vault: ownerVaultPDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).signers([owner]).rpc({ commitment: "confirmed" });
+
+      mint = await createMint(provider.connection, owner, owner.publicKey, null, 6, undefined, { commitment: "confirmed" });
+      depositorTokenAccount = (await getOrCreateAssociatedTokenAccount(provider.connection, depositor, mint, depositor.publicKey, false, "confirmed")).address;
+      ownerTokenAccount = (await getOrCreateAssociatedTokenAccount(provider.connection, owner, mint, owner.publicKey, false, "confirmed")).address;
+      vaultTokenAccount = getAssociatedTokenAddressSync(mint, ownerVaultPDA, true);
+      await mintTo(provider.connection, owner, mint, depositorTokenAccount, owner, 1000000, [], { commitment: "confirmed" });
+    });
+
+    it("Deposit moves tokens into the vault's token account", async () => {
+      const txSig = await depositSpl(400000);
+
+      assert.strictEqual(await tokenBalance(depositorTokenAccount), 600000);
+      assert.strictEqual(await tokenBalance(vaultTokenAccount), 400000);
+      const tokenAccount = await getAccount(provider.connection, vaultTokenAccount, "confirmed");
+      assert.strictEqual(tokenAccount.owner.toString(), ownerVaultPDA.toString(), "Vault PDA should own its token account");
+
+      const tx = await provider.connection.getParsedTransaction(txSig, "confirmed");
+      const eventParser = new anchor.EventParser(program.programId, new anchor.BorshCoder(program.idl));
+      const events = Array.from(eventParser.parseLogs(tx.meta.logMessages));
+      const event = events.find((e) => e.name === "splDepositEvent");
+      assert.isDefined(event, "SplDepositEvent should have been emitted");
+      assert.strictEqual(event.data.amount.toString(), "400000");
+      assert.strictEqual(event.data.mint.toString(), mint.toString());
+    });
+
+    it("Withdraw moves tokens to the authority, signed by the vault", async () => {
+      await program.methods.withdrawSpl(new anchor.BN(150000)).accounts({
+        vaultAuthority: owner.publicKey,
+        vault: ownerVaultPDA,
+        mint,
+        vaultTokenAccount,
+        authorityTokenAccount: ownerTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      }).signers([owner]).rpc({ commitment: "confirmed" });
+
+      assert.strictEqual(await tokenBalance(vaultTokenAccount), 250000);
+      assert.strictEqual(await tokenBalance(ownerTokenAccount), 150000);
+    });
+
+    it("A locked vault rejects token deposits", async () => {
+      await program.methods.lockVault().accounts({
+        vaultAuthority: owner.publicKey,
+        vault: ownerVaultPDA,
+      }).signers([owner]).rpc({ commitment: "confirmed" });
+
+      let flag = "This should fail";
+      try {
+        await depositSpl(100000);
+      } catch (error) {
+        flag = "Failed";
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "VaultLocked", "Should fail with VaultLocked error");
+      }
+      assert.strictEqual(flag, "Failed", "Depositing tokens into a locked vault should fail");
+      assert.strictEqual(await tokenBalance(vaultTokenAccount), 250000);
+    });
+  });
+
+  describe("Closing a vault", () => {
+    const owner = anchor.web3.Keypair.generate();
+    const [ownerVaultPDA] = getVaultPDA(owner.publicKey);
+
+    const [pendingPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pending"), ownerVaultPDA.toBuffer()],
+      program.programId
+    );
+    let mint: anchor.web3.PublicKey;
+    let ownerTokenAccount: anchor.web3.PublicKey;
+    let vaultTokenAccount: anchor.web3.PublicKey;
+
+    const closeVault = (tokenAccounts: anchor.web3.PublicKey[] = []) => program.methods.closeVault().accounts({
+      vaultAuthority: owner.publicKey,
+      vault: ownerVaultPDA,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    }).remainingAccounts(
+      tokenAccounts.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true }))
+    ).signers([owner]).rpc({ commitment: "confirmed" });
+
+    const expectCloseError = async (code: string, tokenAccounts: anchor.web3.PublicKey[] = []) => {
+      let flag = "This should fail";
+      try {
+        await closeVault(tokenAccounts);
+      } catch (error) {
+        flag = "Failed";
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, code, `Should fail with ${code} error`);
+      }
+      assert.strictEqual(flag, "Failed", "Closing the vault should fail");
+      assert.isNotNull(await provider.connection.getAccountInfo(ownerVaultPDA), "Vault should still exist");
+    };
+
+    const splAccounts = () => ({
+      vault: ownerVaultPDA,
+      mint,
+      vaultTokenAccount,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+    });
+
+    before(async () => {
+      await airdrop(provider.connection, owner.publicKey);
+      await program.methods.initializeVault(DEFAULT_VAULT_ID, false, UNCAPPED).accounts({
+        vaultAuthority: owner.publicKey,
+        vault: ownerVaultPDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).signers([owner]).rpc({ commitment: "confirmed" });
+      await program.methods.deposit(new anchor.BN(800000), null).accounts({
+        user: owner.publicKey,
+        vault: ownerVaultPDA,
+      }).signers([owner]).rpc({ commitment: "confirmed" });
+    });
+
+    it("Cannot close a vault that still holds funds", async () => {
+      await expectCloseError("VaultNotEmpty");
+    });
+
+    it("Cannot close a vault with a pending withdrawal", async () => {
+      await program.methods.withdraw(new anchor.BN(800000)).accounts({
+        vaultAuthority: owner.publicKey,
+        vault: ownerVaultPDA,
+      }).signers([owner]).rpc({ commitment: "confirmed" });
+      await program.methods.requestWithdrawal(new anchor.BN(1)).accounts({
+        vaultAuthority: owner.publicKey,
+        vault: ownerVaultPDA,
+        pendingWithdrawal: pendingPDA,
+      }).signers([owner]).rpc({ commitment: "confirmed" });
+
+      await expectCloseError("PendingWithdrawalOpen");
+
+      await program.methods.cancelPending().accounts({
+        vaultAuthority: owner.publicKey,
+        vault: ownerVaultPDA,
+        pendingWithdrawal: pendingPDA,
+      }).signers([owner]).rpc({ commitment: "confirmed" });
+    });
+
+    it("Cannot close a vault whose token account still holds tokens", async () => {
+      mint = await createMint(provider.connection, owner, owner.publicKey, null, 6, undefined, { commitment: "confirmed" });
+      ownerTokenAccount = (await getOrCreateAssociatedTokenAccount(provider.connection, owner, mint, owner.publicKey, false, "confirmed")).address;
+      vaultTokenAccount = getAssociatedTokenAddressSync(mint, ownerVaultPDA, true);
+      await mintTo(provider.connection, owner, mint, ownerTokenAccount, owner, 1000, [], { commitment: "confirmed" });
+      await program.methods.depositSpl(new anchor.BN(1000)).accounts({
+        user: owner.publicKey,
+        userTokenAccount: ownerTokenAccount,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        ...splAccounts(),
+      }).signers([owner]).rpc({ commitment: "confirmed" });
+
+      const vaultData = await program.account.vault.fetch(ownerVaultPDA);
+      assert.deepEqual(vaultData.mints.map((m) => m.toString()), [mint.toString()], "The deposited mint should be recorded");
+      await expectCloseError("VaultNotEmpty", [vaultTokenAccount]);
+    });
+
+    it("Cannot close a vault without the token account of a deposited mint", async () => {
+      await expectCloseError("TokenAccountMismatch");
+      await expectCloseError("TokenAccountMismatch", [ownerTokenAccount]);
+    });
+
+    it("Closes an emptied vault and its token account and returns their rent", async () => {
+      await program.methods.withdrawSpl(new anchor.BN(1000)).accounts({
+        vaultAuthority: owner.publicKey,
+        authorityTokenAccount: ownerTokenAccount,
+        ...splAccounts(),
+      }).signers([owner]).rpc({ commitment: "confirmed" });
+      const rent = await provider.connection.getBalance(ownerVaultPDA);
+      const tokenRent = await provider.connection.getBalance(vaultTokenAccount);
+
+      const txSig = await closeVault([vaultTokenAccount]);
+
+      assert.isNull(await provider.connection.getAccountInfo(ownerVaultPDA), "Vault account should be closed");
+      assert.isNull(await provider.connection.getAccountInfo(vaultTokenAccount), "Vault token account should be closed");
+      const tx = await provider.connection.getParsedTransaction(txSig, "confirmed");
+      const eventParser = new anchor.EventParser(program.programId, new anchor.BorshCoder(program.idl));
+      const event = [...eventParser.parseLogs(tx.meta.logMessages)].find((e) => e.name === "vaultClosed");
+      assert.isDefined(event, "VaultClosed should have been emitted");
+      assert.strictEqual(event.data.lamports.toNumber(), rent, "Only the rent should be left to return");
+      const ownerIndex = tx.transaction.message.accountKeys.findIndex((key) => key.pubkey.equals(owner.publicKey));
+      assert.strictEqual(tx.meta.postBalances[ownerIndex] - tx.meta.preBalances[ownerIndex] + tx.meta.fee, rent + tokenRent, "Rent should go back to the authority");
+    });
+  });
+
+  describe("Multiple vaults per authority", () => {
+    const owner = anchor.web3.Keypair.generate();
+    const depositor = anchor.web3.Keypair.generate();
+    const [firstVaultPDA] = getVaultPDA(owner.publicKey, new anchor.BN(1));
+    const [secondVaultPDA] = getVaultPDA(owner.publicKey, new anchor.BN(2));
+
+    before(async () => {
+      await airdrop(provider.connection, owner.publicKey);
+      await airdrop(provider.connection, depositor.publicKey);
+      for (const [vaultId, vault] of [[new anchor.BN(1), firstVaultPDA], [new anchor.BN(2), secondVaultPDA]] as const) {
+        await program.methods.initializeVault(vaultId, false, UNCAPPED).accounts({
+          vaultAuthority: owner.publicKey,
+          vault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        }).signers([owner]).rpc({ commitment: "confirmed" });
+      }
+    });
+
+    it("Each vault id gets its own PDA and records its id", async () => {
+      assert.notStrictEqual(firstVaultPDA.toString(), secondVaultPDA.toString());
+      const first = await program.account.vault.fetch(firstVaultPDA);
+      const second = await program.account.vault.fetch(secondVaultPDA);
+      assert.strictEqual(first.vaultId.toString(), "1");
+      assert.strictEqual(second.vaultId.toString(), "2");
+      assert.strictEqual(first.vaultAuthority.toString(), owner.publicKey.toString());
+      assert.strictEqual(second.vaultAuthority.toString(), owner.publicKey.toString());
+    });
+
+    it("Two vault ids of the same authority hold independent balances", async () => {
+      const firstBefore = await provider.connection.getBalance(firstVaultPDA);
+      const secondBefore = await provider.connection.getBalance(secondVaultPDA);
+
+      await program.methods.deposit(new anchor.BN(600000), null).accounts({
+        user: depositor.publicKey,
+        vault: firstVaultPDA,
+      }).signers([depositor]).rpc({ commitment: "confirmed" });
+      await program.methods.deposit(new anchor.BN(300000), null).accounts({
+        user: depositor.publicKey,
+        vault: secondVaultPDA,
+      }).signers([depositor]).rpc({ commitment: "confirmed" });
+      await program.methods.withdraw(new anchor.BN(100000)).accounts({
+        vaultAuthority: owner.publicKey,
+        vault: firstVaultPDA,
+      }).signers([owner]).rpc({ commitment: "confirmed" });
+
+      assert.strictEqual(await provider.connection.getBalance(firstVaultPDA), firstBefore + 500000);
+      assert.strictEqual(await provider.connection.getBalance(secondVaultPDA), secondBefore + 300000);
+      const first = await program.account.vault.fetch(firstVaultPDA);
+      const second = await program.account.vault.fetch(secondVaultPDA);
+      assert.strictEqual(first.totalDeposited.toString(), "600000");
+      assert.strictEqual(first.totalWithdrawn.toString(), "100000");
+      assert.strictEqual(second.totalDeposited.toString(), "300000");
+      assert.strictEqual(second.totalWithdrawn.toString(), "0");
+    });
+  });
+
+  describe("Vault migration", () => {
+    // loaded by the test validator from tests/fixtures/legacy_vault.json, at the
+    // address vaults had before vault ids
+    const legacySigner = anchor.web3.Keypair.fromSecretKey(
+      Uint8Array.from(JSON.parse(fs.readFileSync("tests/fixtures/legacy_authority.json", "utf8")))
+    );
+    const legacyAuthority = legacySigner.publicKey;
+    const [legacyVault, legacyBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), legacyAuthority.toBuffer()],
+      program.programId
+    );
+    const mallory = anchor.web3.Keypair.generate();
+    const [vaultMalloryPDA] = getVaultPDA(mallory.publicKey);
+
+    const migrate = (vault: anchor.web3.PublicKey) =>
+      program.methods.migrateVault().accounts({
+        payer: mallory.publicKey,
+        vault,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).signers([mallory]).rpc({ commitment: "confirmed" });
+
+    before(async () => {
+      await airdrop(provider.connection, mallory.publicKey);
+      await airdrop(provider.connection, legacyAuthority);
+    });
+
+    it("Migrates a v1 vault to the current layout with defaults", async () => {
+      const before = await provider.connection.getAccountInfo(legacyVault);
+      assert.strictEqual(before.data.length, 41, "Fixture should have the v1 layout");
+      const oldRent = await provider.connection.getMinimumBalanceForRentExemption(41);
+
+      await migrate(legacyVault);
+
+      const after = await provider.connection.getAccountInfo(legacyVault);
+      assert.strictEqual(after.data.length, 8 + 32 + 1 + 8 + 8 + 8 + 1 + 1 + 8 + 1 + 8 + 8 + 8 + 8 + 2 + 32 + 1 + 4 + 4 * 32, "Account should grow to the current layout");
+      const rent = await provider.connection.getMinimumBalanceForRentExemption(after.data.length);
+      assert.strictEqual(after.lamports - rent, before.lamports - oldRent, "Payer should top up the rent, the vault's funds stay withdrawable");
+
+      const vaultData = await program.account.vault.fetch(legacyVault);
+      assert.strictEqual(vaultData.vaultAuthority.toString(), legacyAuthority.toString(), "Existing fields should be kept");
+      assert.strictEqual(vaultData.locked, false);
+      assert.strictEqual(vaultData.totalDeposited.toString(), "0");
+      assert.strictEqual(vaultData.totalWithdrawn.toString(), "0");
+      assert.strictEqual(vaultData.maxBalance.toString(), UNCAPPED.toString(), "Migrated vaults should start uncapped");
+      assert.strictEqual(vaultData.recipientsGated, false);
+      assert.strictEqual(vaultData.vaultId.toString(), "0");
+      assert.strictEqual(vaultData.legacySeeds, true, "Vaults from before vault ids keep their address");
+      assert.strictEqual(vaultData.bump, legacyBump, "Bump should be derived from the legacy seeds");
+      assert.strictEqual(vaultData.unlockTs.toString(), "0", "Migrated vaults should not be time locked");
+      assert.strictEqual(vaultData.dailyLimit.toString(), "0", "Migrated vaults should have no daily limit");
+      assert.strictEqual(vaultData.feeBps, 0, "Migrated vaults should charge no fee");
+      assert.deepEqual(vaultData.mints, [], "Migrated vaults should start with no recorded mints");
+      assert.strictEqual(vaultData.version, 9);
+    });
+
+    it("Re-running the migration leaves the vault unchanged", async () => {
+      const before = await provider.connection.getAccountInfo(legacyVault);
+      await migrate(legacyVault);
+      const after = await provider.connection.getAccountInfo(legacyVault);
+      assert.isTrue(after.data.equals(before.data), "Data should not change");
+      assert.strictEqual(after.lamports, before.lamports, "Lamports should not change");
+    });
+
+    it("A migrated legacy vault still accepts deposits and withdrawals", async () => {
+      const before = await provider.connection.getBalance(legacyVault);
+
+      await program.methods.deposit(new anchor.BN(300000), null).accounts({
+        user: mallory.publicKey,
+        vault: legacyVault,
+      }).signers([mallory]).rpc({ commitment: "confirmed" });
+      await program.methods.withdraw(new anchor.BN(500000)).accounts({
+        vaultAuthority: legacyAuthority,
+        vault: legacyVault,
+      }).signers([legacySigner]).rpc({ commitment: "confirmed" });
+
+      assert.strictEqual(await provider.connection.getBalance(legacyVault), before + 300000 - 500000);
+      const vaultData = await program.account.vault.fetch(legacyVault);
+      assert.strictEqual(vaultData.totalDeposited.toString(), "300000");
+      assert.strictEqual(vaultData.totalWithdrawn.toString(), "500000");
+    });
+
+    it("New vaults start on the current version", async () => {
+      await program.methods.initializeVault(DEFAULT_VAULT_ID, false, UNCAPPED).accounts({
+        vaultAuthority: mallory.publicKey,
+        vault: vaultMalloryPDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).signers([mallory]).rpc({ commitment: "confirmed" });
+
+      const vaultData = await program.account.vault.fetch(vaultMalloryPDA);
+      assert.strictEqual(vaultData.version, 9);
+      await migrate(vaultMalloryPDA);
+    });
+  });
+
 });
 
 async function airdrop(connection: any, address: any, amount = 100 * anchor.web3.LAMPORTS_PER_SOL) {
   await connection.confirmTransaction(await connection.requestAirdrop(address, amount), "confirmed");
 }
+
+function sleep(ms: number) {
+  return new Promise((resolve) => setTimeout(resolve, ms));
+}
@@ -16,4 +16,26 @@ pub enum TwitterError {
     MinDislikesReached,
     #[msg("Comment too Long")]
     CommentTooLong,
+    #[msg("Poll must have between 2 and 4 options")]
+    InvalidPollOptionCount,
+    #[msg("Poll option too long")]
+    PollOptionTooLong,
+    #[msg("Poll option does not exist")]
+    InvalidPollOption,
+    #[msg("Only the tweet author can do this")]
+    NotTweetAuthor,
+    #[msg("Content must be ASCII only")]
+    NonAsciiContent,
+    #[msg("Only the config admin can do this")]
+    NotConfigAdmin,
+    #[msg("Reactions on this tweet are frozen")]
+    ReactionsFrozen,
+    #[msg("Wait for the reaction cooldown before reacting again")]
+    ReactionCooldownActive,
+    #[msg("Max content length cannot exceed the tweet account size")]
+    InvalidMaxContentLength,
+    #[msg("Only followers of the author can react to this tweet")]
+    NotAFollower,
+    #[msg("The same tweet was passed more than once")]
+    DuplicateTweetAccount,
 }
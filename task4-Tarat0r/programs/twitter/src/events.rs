@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct TweetQuoted {
+    pub quote: Pubkey,
+    pub quote_author: Pubkey,
+    pub original_tweet: Pubkey,
+    pub original_content_hash: [u8; 32],
+}
+
+#[event]
+pub struct TweetOwnershipTransferred {
+    pub tweet: Pubkey,
+    pub previous_author: Pubkey,
+    pub new_author: Pubkey,
+}
+
+#[event]
+pub struct EngagementScore {
+    pub user: Pubkey,
+    pub score: i64,
+}
@@ -0,0 +1,41 @@
+//-------------------------------------------------------------------------------
+///
+/// Follow another author
+/// 
+/// - One Follow account per follower and followee
+/// - The followee does not need to sign
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::states::*;
+
+pub fn add_follow(ctx: Context<AddFollowContext>) -> Result<()> {
+    let follow = &mut ctx.accounts.follow;
+    follow.follower = ctx.accounts.follower.key();
+    follow.followee = ctx.accounts.followee.key();
+    follow.bump = ctx.bumps.follow;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AddFollowContext<'info> {
+    #[account(mut)]
+    pub follower: Signer<'info>,
+    /// CHECK: only used as a seed and stored as the followee key
+    pub followee: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = follower,
+        space = 8 + Follow::INIT_SPACE,
+        seeds = [
+            FOLLOW_SEED.as_bytes(),
+            follower.key().as_ref(),
+            followee.key().as_ref(),
+        ],
+        bump
+    )]
+    pub follow: Account<'info, Follow>,
+    pub system_program: Program<'info, System>,
+}
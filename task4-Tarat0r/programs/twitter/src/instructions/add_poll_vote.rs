@@ -0,0 +1,56 @@
+//-------------------------------------------------------------------------------
+///
+/// Vote on a poll attached to a tweet
+/// 
+/// - The chosen option index must exist on the poll
+/// - Creates a TweetPollVote PDA per voter and poll, so a second vote fails
+///   because the account is already in use
+/// - Increments the chosen option's vote count
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::states::*;
+
+pub fn add_poll_vote(ctx: Context<AddPollVoteContext>, option: u8) -> Result<()> {
+    let tweet_poll = &mut ctx.accounts.tweet_poll;
+    let count = tweet_poll
+        .votes
+        .get_mut(option as usize)
+        .ok_or(TwitterError::InvalidPollOption)?;
+    *count += 1;
+
+    let vote = &mut ctx.accounts.tweet_poll_vote;
+    vote.voter = ctx.accounts.voter.key();
+    vote.tweet_poll = tweet_poll.key();
+    vote.option = option;
+    vote.bump = ctx.bumps.tweet_poll_vote;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AddPollVoteContext<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + TweetPollVote::INIT_SPACE,
+        seeds = [
+            TWEET_POLL_VOTE_SEED.as_bytes(),
+            voter.key().as_ref(),
+            tweet_poll.key().as_ref(),
+        ],
+        bump
+    )]
+    pub tweet_poll_vote: Account<'info, TweetPollVote>,
+    #[account(
+        mut,
+        seeds = [TWEET_POLL_SEED.as_bytes(), tweet_poll.parent_tweet.as_ref()],
+        bump = tweet_poll.bump
+    )]
+    pub tweet_poll: Account<'info, TweetPoll>,
+    pub system_program: Program<'info, System>,
+}
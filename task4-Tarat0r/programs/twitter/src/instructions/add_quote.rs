@@ -0,0 +1,67 @@
+//-------------------------------------------------------------------------------
+///
+/// Quote a tweet with new commentary
+/// 
+/// - Validate that the commentary doesn't exceed COMMENT_LENGTH
+/// - Store the commentary, the original tweet and a hash of its content
+///   so the quote stays tamper-evident if the original ever changes
+/// - Increment quote_count on the original tweet
+/// - Emit a TweetQuoted event
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+use crate::errors::TwitterError;
+use crate::events::TweetQuoted;
+use crate::states::*;
+
+pub fn add_quote(ctx: Context<AddQuoteContext>, comment: String) -> Result<()> {
+    require!(
+        comment.len() <= COMMENT_LENGTH,
+        TwitterError::CommentTooLong
+    );
+
+    let original = &mut ctx.accounts.original_tweet;
+    original.quote_count += 1;
+
+    let quote = &mut ctx.accounts.quote;
+    quote.quote_author = ctx.accounts.quote_author.key();
+    quote.original_tweet = original.key();
+    quote.original_content_hash = hash(original.content.as_bytes()).to_bytes();
+    quote.content = comment;
+    quote.bump = ctx.bumps.quote;
+
+    emit!(TweetQuoted {
+        quote: quote.key(),
+        quote_author: quote.quote_author,
+        original_tweet: quote.original_tweet,
+        original_content_hash: quote.original_content_hash,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(comment: String)]
+pub struct AddQuoteContext<'info> {
+    #[account(
+        init,
+        payer = quote_author,
+        space = 8 + Quote::INIT_SPACE,
+        seeds = [
+            QUOTE_SEED.as_bytes(),
+            quote_author.key().as_ref(),
+            {&hash(comment.as_bytes()).to_bytes()},
+            original_tweet.key().as_ref(),
+        ],
+        bump
+    )]
+    pub quote: Account<'info, Quote>,
+
+    #[account(mut)]
+    pub quote_author: Signer<'info>,
+    #[account(mut)]
+    pub original_tweet: Account<'info, Tweet>,
+    pub system_program: Program<'info, System>,
+}
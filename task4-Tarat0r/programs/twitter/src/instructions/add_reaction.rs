@@ -7,12 +7,38 @@
 /// - Increment the appropriate counter (likes or dislikes) on the tweet
 /// - Set reaction fields: type, author, parent tweet, and bump
 /// - Handle both Like and Dislike reaction types
+/// - Reject new reactions while the tweet's reactions are frozen
+/// - Reject re-reacting within REACTION_COOLDOWN seconds of a removal
+/// - FollowersOnly tweets need the reactor's Follow account of the author,
+///   except for the author reacting to their own tweet
 /// 
 ///-------------------------------------------------------------------------------
 use anchor_lang::prelude::*;
+use crate::errors::TwitterError;
 use crate::states::*;
 
 pub fn add_reaction(ctx: Context<AddReactionContext>, reaction: ReactionType) -> Result<()> {
+    require!(!ctx.accounts.tweet.reactions_frozen, TwitterError::ReactionsFrozen);
+    let is_author = ctx.accounts.reaction_author.key() == ctx.accounts.tweet.tweet_author;
+    if ctx.accounts.tweet.visibility == Visibility::FollowersOnly && !is_author {
+        let follows = ctx.accounts.follow.as_ref().is_some_and(|follow| {
+            follow.follower == ctx.accounts.reaction_author.key()
+                && follow.followee == ctx.accounts.tweet.tweet_author
+        });
+        require!(follows, TwitterError::NotAFollower);
+    }
+
+    let cooldown = &mut ctx.accounts.reaction_cooldown;
+    if cooldown.last_removed_ts != 0 {
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now >= cooldown.last_removed_ts.saturating_add(REACTION_COOLDOWN),
+            TwitterError::ReactionCooldownActive
+        );
+    }
+    cooldown.reaction_author = ctx.accounts.reaction_author.key();
+    cooldown.parent_tweet = ctx.accounts.tweet.key();
+    cooldown.bump = ctx.bumps.reaction_cooldown;
 
     match reaction {
         ReactionType::Like => {
@@ -49,7 +75,21 @@ pub struct AddReactionContext<'info> {
         bump
     )]
     pub tweet_reaction: Account<'info, Reaction>,
+    #[account(
+        init_if_needed,
+        payer = reaction_author,
+        space = 8 + ReactionCooldown::INIT_SPACE,
+        seeds = [
+            REACTION_COOLDOWN_SEED.as_bytes(),
+            reaction_author.key().as_ref(),
+            tweet.key().as_ref(),
+        ],
+        bump
+    )]
+    pub reaction_cooldown: Account<'info, ReactionCooldown>,
     #[account(mut)]
     pub tweet: Account<'info, Tweet>,
+    // only needed for FollowersOnly tweets
+    pub follow: Option<Account<'info, Follow>>,
     pub system_program: Program<'info, System>,
 }
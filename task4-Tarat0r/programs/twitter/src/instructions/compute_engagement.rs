@@ -0,0 +1,44 @@
+//-------------------------------------------------------------------------------
+///
+/// Score a user's engagement across their tweets
+/// 
+/// - Tweets are passed in remaining_accounts and must all belong to the user
+/// - Each tweet may be passed only once
+/// - Each tweet adds likes, dislikes and quotes times their weights from states.rs
+/// - Read only, emit an EngagementScore event
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::events::EngagementScore;
+use crate::states::*;
+
+pub fn engagement_score<'info>(ctx: Context<'_, '_, 'info, 'info, ComputeEngagementContext<'info>>) -> Result<()> {
+    let user = ctx.accounts.user.key();
+    let mut score: i64 = 0;
+    let mut seen: Vec<Pubkey> = Vec::with_capacity(ctx.remaining_accounts.len());
+
+    for info in ctx.remaining_accounts.iter() {
+        require!(!seen.contains(info.key), TwitterError::DuplicateTweetAccount);
+        seen.push(info.key());
+
+        let tweet = Account::<Tweet>::try_from(info)?;
+        require_keys_eq!(tweet.tweet_author, user, TwitterError::NotTweetAuthor);
+
+        score = score
+            .saturating_add((tweet.likes as i64).saturating_mul(LIKE_WEIGHT))
+            .saturating_add((tweet.dislikes as i64).saturating_mul(DISLIKE_WEIGHT))
+            .saturating_add((tweet.quote_count as i64).saturating_mul(QUOTE_WEIGHT));
+    }
+
+    emit!(EngagementScore { user, score });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ComputeEngagementContext<'info> {
+    /// CHECK: only compared against each tweet's author
+    pub user: UncheckedAccount<'info>,
+}
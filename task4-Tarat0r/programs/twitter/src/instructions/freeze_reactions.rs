@@ -0,0 +1,28 @@
+//-------------------------------------------------------------------------------
+///
+/// Freeze or unfreeze new reactions on a tweet
+/// 
+/// - Only the tweet author can change it
+/// - While frozen, add_reaction is rejected but remove_reaction still works
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::states::*;
+
+pub fn set_reactions_frozen(ctx: Context<FreezeReactionsContext>, frozen: bool) -> Result<()> {
+    ctx.accounts.tweet.reactions_frozen = frozen;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FreezeReactionsContext<'info> {
+    pub tweet_author: Signer<'info>,
+    #[account(
+        mut,
+        constraint = tweet.tweet_author == tweet_author.key() @ TwitterError::NotTweetAuthor
+    )]
+    pub tweet: Account<'info, Tweet>,
+}
@@ -0,0 +1,47 @@
+//-------------------------------------------------------------------------------
+///
+/// Create the program-wide config account
+/// 
+/// - Can only be created once, the signer becomes the admin
+/// - Holds the content policy and max content length enforced on new tweets
+/// - max_content_len is bounded by CONTENT_LENGTH, the space of a Tweet
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::states::*;
+
+pub fn initialize_config(
+    ctx: Context<InitializeConfig>,
+    content_policy: ContentPolicy,
+    max_content_len: u16,
+) -> Result<()> {
+    require!(
+        max_content_len as usize <= CONTENT_LENGTH,
+        TwitterError::InvalidMaxContentLength
+    );
+
+    let config = &mut ctx.accounts.config;
+    config.admin = ctx.accounts.admin.key();
+    config.content_policy = content_policy;
+    config.max_content_len = max_content_len;
+    config.bump = ctx.bumps.config;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [CONFIG_SEED.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    pub system_program: Program<'info, System>,
+}
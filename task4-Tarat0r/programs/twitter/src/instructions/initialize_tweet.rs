@@ -8,6 +8,8 @@
 /// - Set tweet fields: topic, content, author, likes, dislikes, and bump
 /// - Initialize counters (likes and dislikes) to zero
 /// - Use topic in PDA seeds for tweet identification
+/// - Reject non-ASCII content when the config policy is AsciiOnly
+/// - Content may not exceed the configured max_content_len
 /// 
 ///-------------------------------------------------------------------------------
 use anchor_lang::prelude::*;
@@ -20,9 +22,16 @@ pub fn initialize_tweet(
     topic: String,
     content: String,
 ) -> Result<()> {
-    if content.len() > 500 {
+    let config = load_config(&ctx.accounts.config)?;
+    let max_content_len = config
+        .as_ref()
+        .map_or(CONTENT_LENGTH, |config| config.max_content_len as usize);
+    if content.len() > max_content_len {
         return Err(TwitterError::ContentTooLong.into());
     }
+    if config.map(|config| config.content_policy) == Some(ContentPolicy::AsciiOnly) {
+        require!(content.is_ascii(), TwitterError::NonAsciiContent);
+    }
 
     let tweet = &mut ctx.accounts.tweet;
     tweet.topic = topic;
@@ -30,6 +39,9 @@ pub fn initialize_tweet(
     tweet.content = content;
     tweet.likes = 0;
     tweet.dislikes = 0;
+    tweet.quote_count = 0;
+    tweet.reactions_frozen = false;
+    tweet.visibility = Visibility::Public;
     let bump = ctx.bumps.tweet;
     ctx.accounts.tweet.bump = bump;
 
@@ -37,6 +49,14 @@ pub fn initialize_tweet(
     Ok(())
 }
 
+/// The config PDA, `None` when no config has been created yet.
+fn load_config(config: &UncheckedAccount) -> Result<Option<Config>> {
+    if config.data_is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(Config::try_deserialize(&mut &config.try_borrow_data()?[..])?))
+}
+
 
 #[derive(Accounts)]
 #[instruction(topic: String)]
@@ -52,5 +72,8 @@ pub struct InitializeTweet<'info> {
         bump
     )]
     pub tweet: Account<'info, Tweet>,
+    /// CHECK: config PDA, read only if it has been initialized
+    #[account(seeds = [CONFIG_SEED.as_bytes()], bump)]
+    pub config: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
 }
@@ -0,0 +1,52 @@
+//-------------------------------------------------------------------------------
+///
+/// Attach a simple poll to an existing tweet
+/// 
+/// - Only the tweet author can create the poll
+/// - A poll has between 2 and MAX_POLL_OPTIONS options
+/// - Each option must fit into POLL_OPTION_LENGTH bytes
+/// - One poll per tweet, seeded by the tweet pubkey
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::states::*;
+
+pub fn initialize_tweet_poll(ctx: Context<InitializeTweetPoll>, options: Vec<String>) -> Result<()> {
+    require!(
+        (2..=MAX_POLL_OPTIONS).contains(&options.len()),
+        TwitterError::InvalidPollOptionCount
+    );
+    require!(
+        options.iter().all(|option| option.len() <= POLL_OPTION_LENGTH),
+        TwitterError::PollOptionTooLong
+    );
+
+    let tweet_poll = &mut ctx.accounts.tweet_poll;
+    tweet_poll.parent_tweet = ctx.accounts.tweet.key();
+    tweet_poll.votes = vec![0; options.len()];
+    tweet_poll.options = options;
+    tweet_poll.bump = ctx.bumps.tweet_poll;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeTweetPoll<'info> {
+    #[account(mut)]
+    pub tweet_author: Signer<'info>,
+    #[account(
+        init,
+        payer = tweet_author,
+        space = 8 + TweetPoll::INIT_SPACE,
+        seeds = [TWEET_POLL_SEED.as_bytes(), tweet.key().as_ref()],
+        bump
+    )]
+    pub tweet_poll: Account<'info, TweetPoll>,
+    #[account(
+        constraint = tweet.tweet_author == tweet_author.key() @ TwitterError::NotTweetAuthor
+    )]
+    pub tweet: Account<'info, Tweet>,
+    pub system_program: Program<'info, System>,
+}
@@ -12,3 +12,39 @@ pub mod add_comment;
 
 pub use remove_comment::*;
 pub mod remove_comment;
+
+pub use initialize_tweet_poll::*;
+pub mod initialize_tweet_poll;
+
+pub use add_poll_vote::*;
+pub mod add_poll_vote;
+
+pub use add_quote::*;
+pub mod add_quote;
+
+pub use initialize_config::*;
+pub mod initialize_config;
+
+pub use update_content_policy::*;
+pub mod update_content_policy;
+
+pub use update_max_content_len::*;
+pub mod update_max_content_len;
+
+pub use freeze_reactions::*;
+pub mod freeze_reactions;
+
+pub use transfer_tweet::*;
+pub mod transfer_tweet;
+
+pub use add_follow::*;
+pub mod add_follow;
+
+pub use remove_follow::*;
+pub mod remove_follow;
+
+pub use set_visibility::*;
+pub mod set_visibility;
+
+pub use compute_engagement::*;
+pub mod compute_engagement;
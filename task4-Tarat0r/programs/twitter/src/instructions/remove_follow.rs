@@ -0,0 +1,32 @@
+//-------------------------------------------------------------------------------
+///
+/// Stop following an author
+/// 
+/// - Closes the Follow account and returns its rent to the follower
+/// - Reactions made while following stay in place
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::states::*;
+
+pub fn remove_follow(_ctx: Context<RemoveFollowContext>) -> Result<()> {
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RemoveFollowContext<'info> {
+    #[account(mut)]
+    pub follower: Signer<'info>,
+    #[account(
+        mut,
+        close = follower,
+        seeds = [
+            FOLLOW_SEED.as_bytes(),
+            follower.key().as_ref(),
+            follow.followee.as_ref(),
+        ],
+        bump = follow.bump
+    )]
+    pub follow: Account<'info, Follow>,
+}
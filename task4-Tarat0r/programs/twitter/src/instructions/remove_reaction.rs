@@ -6,6 +6,7 @@
 /// - Verify that the tweet reaction exists and belongs to the reaction author
 /// - Decrement the appropriate counter (likes or dislikes) on the tweet
 /// - Close the tweet reaction account and return rent to reaction author
+/// - Start the reaction cooldown for this author and tweet
 /// 
 ///-------------------------------------------------------------------------------
 use anchor_lang::prelude::*;
@@ -25,6 +26,7 @@ pub fn remove_reaction(ctx: Context<RemoveReactionContext>) -> Result<()> {
         }
     }
 
+    ctx.accounts.reaction_cooldown.last_removed_ts = Clock::get()?.unix_timestamp;
 
     Ok(())
     }
@@ -46,6 +48,16 @@ pub struct RemoveReactionContext<'info> {
 
     )]
     pub tweet_reaction: Account<'info, Reaction>,
+    #[account(
+        mut,
+        seeds = [
+            REACTION_COOLDOWN_SEED.as_bytes(),
+            reaction_author.key().as_ref(),
+            tweet.key().as_ref(),
+        ],
+        bump = reaction_cooldown.bump
+    )]
+    pub reaction_cooldown: Account<'info, ReactionCooldown>,
     #[account(mut)]
     pub tweet: Account<'info, Tweet>,
 }
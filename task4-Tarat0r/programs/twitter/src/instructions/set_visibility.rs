@@ -0,0 +1,29 @@
+//-------------------------------------------------------------------------------
+///
+/// Change who can react to a tweet
+/// 
+/// - Only the tweet author can change it
+/// - FollowersOnly tweets require a Follow account of the author in add_reaction
+/// - Existing reactions are untouched
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::states::*;
+
+pub fn update_visibility(ctx: Context<SetVisibilityContext>, visibility: Visibility) -> Result<()> {
+    ctx.accounts.tweet.visibility = visibility;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetVisibilityContext<'info> {
+    pub tweet_author: Signer<'info>,
+    #[account(
+        mut,
+        constraint = tweet.tweet_author == tweet_author.key() @ TwitterError::NotTweetAuthor
+    )]
+    pub tweet: Account<'info, Tweet>,
+}
@@ -0,0 +1,39 @@
+//-------------------------------------------------------------------------------
+///
+/// Hand a tweet over to another author
+/// 
+/// - Only the current tweet author can transfer it
+/// - The tweet keeps its address, which stays derived from the original author
+/// - Reactions, comments and quotes key off the tweet pubkey and stay valid
+/// - Emit a TweetOwnershipTransferred event
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::events::TweetOwnershipTransferred;
+use crate::states::*;
+
+pub fn change_tweet_author(ctx: Context<TransferTweetContext>, new_author: Pubkey) -> Result<()> {
+    let tweet = &mut ctx.accounts.tweet;
+    let previous_author = tweet.tweet_author;
+    tweet.tweet_author = new_author;
+
+    emit!(TweetOwnershipTransferred {
+        tweet: tweet.key(),
+        previous_author,
+        new_author,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct TransferTweetContext<'info> {
+    pub tweet_author: Signer<'info>,
+    #[account(
+        mut,
+        constraint = tweet.tweet_author == tweet_author.key() @ TwitterError::NotTweetAuthor
+    )]
+    pub tweet: Account<'info, Tweet>,
+}
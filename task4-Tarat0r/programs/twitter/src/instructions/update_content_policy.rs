@@ -0,0 +1,33 @@
+//-------------------------------------------------------------------------------
+///
+/// Change the content policy stored in the config account
+/// 
+/// - Only the config admin can change it
+/// - Applies to tweets created afterwards, existing tweets are untouched
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::states::*;
+
+pub fn update_content_policy(
+    ctx: Context<UpdateContentPolicyContext>,
+    content_policy: ContentPolicy,
+) -> Result<()> {
+    ctx.accounts.config.content_policy = content_policy;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateContentPolicyContext<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED.as_bytes()],
+        bump = config.bump,
+        has_one = admin @ TwitterError::NotConfigAdmin
+    )]
+    pub config: Account<'info, Config>,
+}
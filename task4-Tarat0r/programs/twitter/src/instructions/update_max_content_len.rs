@@ -0,0 +1,39 @@
+//-------------------------------------------------------------------------------
+///
+/// Change the max tweet content length stored in the config account
+/// 
+/// - Only the config admin can change it
+/// - Cannot exceed CONTENT_LENGTH, the space reserved in a Tweet account
+/// - Applies to tweets created afterwards, existing tweets are untouched
+/// 
+///-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::errors::TwitterError;
+use crate::states::*;
+
+pub fn update_max_content_len(
+    ctx: Context<UpdateMaxContentLenContext>,
+    max_content_len: u16,
+) -> Result<()> {
+    require!(
+        max_content_len as usize <= CONTENT_LENGTH,
+        TwitterError::InvalidMaxContentLength
+    );
+
+    ctx.accounts.config.max_content_len = max_content_len;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateMaxContentLenContext<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED.as_bytes()],
+        bump = config.bump,
+        has_one = admin @ TwitterError::NotConfigAdmin
+    )]
+    pub config: Account<'info, Config>,
+}
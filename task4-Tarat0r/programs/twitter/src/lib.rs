@@ -9,6 +9,9 @@
 /// - Add reactions (likes/dislikes) to tweets
 /// - Comment on tweets
 /// - Remove their own reactions and comments
+/// - Transfer their tweets to another author
+/// - Follow other authors and limit reactions to their followers
+/// - Score a user's engagement across their tweets
 /// 
 /// INSTRUCTIONS:
 /// Complete the implementation of all instructions by filling in the TODOs.
@@ -25,7 +28,13 @@
 /// SEEDS:
 /// - Tweet: [topic.as_bytes(), TWEET_SEED.as_bytes(), tweet_authority.key().as_ref()]
 /// - TweetReaction: [TWEET_REACTION_SEED.as_bytes(), reaction_author.key().as_ref(), tweet.key().as_ref()]
+/// - ReactionCooldown: [REACTION_COOLDOWN_SEED.as_bytes(), reaction_author.key().as_ref(), tweet.key().as_ref()]
 /// - Comment: [COMMENT_SEED.as_bytes(), comment_author.key().as_ref(), {hash(comment.content.as_bytes()).to_bytes().as_ref()}, comment.parent_tweet.key().as_ref()]
+/// - Quote: [QUOTE_SEED.as_bytes(), quote_author.key().as_ref(), {hash(content.as_bytes()).to_bytes().as_ref()}, original_tweet.key().as_ref()]
+/// - TweetPoll: [TWEET_POLL_SEED.as_bytes(), tweet.key().as_ref()]
+/// - TweetPollVote: [TWEET_POLL_VOTE_SEED.as_bytes(), voter.key().as_ref(), tweet_poll.key().as_ref()]
+/// - Config: [CONFIG_SEED.as_bytes()]
+/// - Follow: [FOLLOW_SEED.as_bytes(), follower.key().as_ref(), followee.key().as_ref()]
 /// 
 /// GOOD LUCK!
 /// 
@@ -35,6 +44,7 @@ use crate::instructions::*;
 use anchor_lang::prelude::*;
 
 pub mod errors;
+pub mod events;
 pub mod instructions;
 pub mod states;
 
@@ -63,4 +73,43 @@ pub mod twitter {
     pub fn comment_remove(ctx: Context<RemoveCommentContext>) -> Result<()> {
         remove_comment(ctx)
     }
+    pub fn quote_tweet(ctx: Context<AddQuoteContext>, comment: String) -> Result<()> {
+        add_quote(ctx, comment)
+    }
+    pub fn create_tweet_poll(ctx: Context<InitializeTweetPoll>, options: Vec<String>) -> Result<()> {
+        initialize_tweet_poll(ctx, options)
+    }
+    pub fn vote_on_tweet_poll(ctx: Context<AddPollVoteContext>, option: u8) -> Result<()> {
+        add_poll_vote(ctx, option)
+    }
+    pub fn init_config(ctx: Context<InitializeConfig>, content_policy: states::ContentPolicy, max_content_len: u16) -> Result<()> {
+        initialize_config(ctx, content_policy, max_content_len)
+    }
+    pub fn set_content_policy(ctx: Context<UpdateContentPolicyContext>, content_policy: states::ContentPolicy) -> Result<()> {
+        update_content_policy(ctx, content_policy)
+    }
+    pub fn set_max_content_len(ctx: Context<UpdateMaxContentLenContext>, max_content_len: u16) -> Result<()> {
+        update_max_content_len(ctx, max_content_len)
+    }
+    pub fn freeze_reactions(ctx: Context<FreezeReactionsContext>) -> Result<()> {
+        set_reactions_frozen(ctx, true)
+    }
+    pub fn unfreeze_reactions(ctx: Context<FreezeReactionsContext>) -> Result<()> {
+        set_reactions_frozen(ctx, false)
+    }
+    pub fn transfer_tweet(ctx: Context<TransferTweetContext>, new_author: Pubkey) -> Result<()> {
+        change_tweet_author(ctx, new_author)
+    }
+    pub fn follow(ctx: Context<AddFollowContext>) -> Result<()> {
+        add_follow(ctx)
+    }
+    pub fn unfollow(ctx: Context<RemoveFollowContext>) -> Result<()> {
+        remove_follow(ctx)
+    }
+    pub fn set_tweet_visibility(ctx: Context<SetVisibilityContext>, visibility: states::Visibility) -> Result<()> {
+        update_visibility(ctx, visibility)
+    }
+    pub fn compute_engagement<'info>(ctx: Context<'_, '_, 'info, 'info, ComputeEngagementContext<'info>>) -> Result<()> {
+        engagement_score(ctx)
+    }
 }
@@ -3,10 +3,27 @@ use anchor_lang::prelude::*;
 pub const TOPIC_LENGTH: usize = 32;
 pub const CONTENT_LENGTH: usize = 500;
 pub const COMMENT_LENGTH: usize = 500;
+pub const MAX_POLL_OPTIONS: usize = 4;
+pub const POLL_OPTION_LENGTH: usize = 32;
 
 pub const TWEET_SEED: &str = "TWEET_SEED";
 pub const TWEET_REACTION_SEED: &str = "TWEET_REACTION_SEED";
 pub const COMMENT_SEED: &str = "COMMENT_SEED";
+pub const TWEET_POLL_SEED: &str = "TWEET_POLL_SEED";
+pub const TWEET_POLL_VOTE_SEED: &str = "TWEET_POLL_VOTE_SEED";
+pub const QUOTE_SEED: &str = "QUOTE_SEED";
+pub const CONFIG_SEED: &str = "CONFIG_SEED";
+pub const REACTION_COOLDOWN_SEED: &str = "REACTION_COOLDOWN_SEED";
+pub const FOLLOW_SEED: &str = "FOLLOW_SEED";
+
+/// Seconds an author has to wait after removing a reaction before reacting
+/// to the same tweet again.
+pub const REACTION_COOLDOWN: i64 = 3;
+
+/// Weights compute_engagement applies to a tweet's counters.
+pub const LIKE_WEIGHT: i64 = 2;
+pub const DISLIKE_WEIGHT: i64 = -1;
+pub const QUOTE_WEIGHT: i64 = 3;
 
 #[derive(AnchorDeserialize, AnchorSerialize, Clone, InitSpace)]
 pub enum ReactionType {
@@ -14,6 +31,28 @@ pub enum ReactionType {
     Dislike,
 }
 
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ContentPolicy {
+    Unicode,
+    AsciiOnly,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum Visibility {
+    Public,
+    FollowersOnly,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,
+    pub content_policy: ContentPolicy,
+    /// longest tweet content accepted, at most CONTENT_LENGTH
+    pub max_content_len: u16,
+    pub bump: u8,
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct Tweet {
@@ -24,6 +63,10 @@ pub struct Tweet {
     pub content: String,
     pub likes: u64,
     pub dislikes: u64,
+    pub quote_count: u64,
+    pub reactions_frozen: bool,
+    /// FollowersOnly tweets only accept reactions from followers of the author
+    pub visibility: Visibility,
     pub bump: u8,
 }
 
@@ -36,6 +79,24 @@ pub struct Reaction {
     pub bump: u8,
 }
 
+/// Outlives the Reaction account so remove_reaction can leave a timestamp behind.
+#[account]
+#[derive(InitSpace)]
+pub struct ReactionCooldown {
+    pub reaction_author: Pubkey,
+    pub parent_tweet: Pubkey,
+    pub last_removed_ts: i64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Follow {
+    pub follower: Pubkey,
+    pub followee: Pubkey,
+    pub bump: u8,
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct Comment {
@@ -45,3 +106,35 @@ pub struct Comment {
     pub content: String,
     pub bump: u8,
 }
+
+#[account]
+#[derive(InitSpace)]
+pub struct TweetPoll {
+    pub parent_tweet: Pubkey,
+    #[max_len(MAX_POLL_OPTIONS, POLL_OPTION_LENGTH)]
+    pub options: Vec<String>,
+    #[max_len(MAX_POLL_OPTIONS)]
+    pub votes: Vec<u64>,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct TweetPollVote {
+    pub voter: Pubkey,
+    pub tweet_poll: Pubkey,
+    pub option: u8,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Quote {
+    pub quote_author: Pubkey,
+    pub original_tweet: Pubkey,
+    /// hash of the original tweet content at the time it was quoted
+    pub original_content_hash: [u8; 32],
+    #[max_len(COMMENT_LENGTH)]
+    pub content: String,
+    pub bump: u8,
+}
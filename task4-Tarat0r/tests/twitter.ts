@@ -9,6 +9,13 @@ import crypto from "crypto";
 const TWEET_SEED = "TWEET_SEED";
 const TWEET_REACTION = "TWEET_REACTION_SEED";
 const COMMENT_SEED = "COMMENT_SEED";
+const TWEET_POLL_SEED = "TWEET_POLL_SEED";
+const TWEET_POLL_VOTE_SEED = "TWEET_POLL_VOTE_SEED";
+const QUOTE_SEED = "QUOTE_SEED";
+const CONFIG_SEED = "CONFIG_SEED";
+const REACTION_COOLDOWN_SEED = "REACTION_COOLDOWN_SEED";
+const REACTION_COOLDOWN = 3;
+const FOLLOW_SEED = "FOLLOW_SEED";
 
 describe("twitter", () => {
   const provider = anchor.AnchorProvider.env();
@@ -474,6 +481,9 @@ describe("twitter", () => {
       const [tweet_pkey, tweet_bump] = getTweetAddress(topic_bob4, bob.publicKey, program.programId);
       const [reaction_pkey, reaction_bump] = getReactionAddress(alice.publicKey, tweet_pkey, program.programId);
 
+      // re-reacting is only allowed once the reaction cooldown has passed
+      await sleep((REACTION_COOLDOWN + 2) * 1000);
+
       await program.methods.likeTweet().accounts(
         {
           reactionAuthor: alice.publicKey,
@@ -832,6 +842,790 @@ describe("twitter", () => {
     });
   });
 
+  describe("Tweet Poll", async () => {
+    const topic_poll = "Lunch poll";
+    const [poll_tweet_pkey] = getTweetAddress(topic_poll, bob.publicKey, program.programId);
+    const [tweet_poll_pkey] = getTweetPollAddress(poll_tweet_pkey, program.programId);
+
+    it("Should create a poll on a tweet with 3 options", async () => {
+      await program.methods.initialize(topic_poll, "What should we eat?").accounts(
+        {
+          tweetAuthority: bob.publicKey,
+          tweet: poll_tweet_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([bob]).rpc({ commitment: "confirmed" })
+
+      await program.methods.createTweetPoll(["Pizza", "Sushi", "Tacos"]).accounts(
+        {
+          tweetAuthor: bob.publicKey,
+          tweetPoll: tweet_poll_pkey,
+          tweet: poll_tweet_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([bob]).rpc({ commitment: "confirmed" })
+
+      const pollData = await program.account.tweetPoll.fetch(tweet_poll_pkey);
+      assert.deepEqual(pollData.options, ["Pizza", "Sushi", "Tacos"]);
+      assert.deepEqual(pollData.votes.map((v) => v.toNumber()), [0, 0, 0]);
+      assert.strictEqual(pollData.parentTweet.toString(), poll_tweet_pkey.toString());
+    });
+
+    it("Should fail to create a poll with too many options", async () => {
+      const topic = "Too many options";
+      const [tweet_pkey] = getTweetAddress(topic, bob.publicKey, program.programId);
+      const [poll_pkey] = getTweetPollAddress(tweet_pkey, program.programId);
+      await program.methods.initialize(topic, "Pick one").accounts(
+        {
+          tweetAuthority: bob.publicKey,
+          tweet: tweet_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([bob]).rpc({ commitment: "confirmed" })
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.createTweetPoll(["a", "b", "c", "d", "e"]).accounts(
+          {
+            tweetAuthor: bob.publicKey,
+            tweetPoll: poll_pkey,
+            tweet: tweet_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([bob]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "InvalidPollOptionCount", "Expected 'InvalidPollOptionCount' error for 5 options");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Poll creation should have failed with more than 4 options")
+    });
+
+    it("Should count a vote and reject voting twice", async () => {
+      const [vote_pkey] = getTweetPollVoteAddress(alice.publicKey, tweet_poll_pkey, program.programId);
+
+      await program.methods.voteOnTweetPoll(1).accounts(
+        {
+          voter: alice.publicKey,
+          tweetPollVote: vote_pkey,
+          tweetPoll: tweet_poll_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([alice]).rpc({ commitment: "confirmed" })
+
+      let pollData = await program.account.tweetPoll.fetch(tweet_poll_pkey);
+      assert.deepEqual(pollData.votes.map((v) => v.toNumber()), [0, 1, 0]);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.voteOnTweetPoll(2).accounts(
+          {
+            voter: alice.publicKey,
+            tweetPollVote: vote_pkey,
+            tweetPoll: tweet_poll_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([alice]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        should_fail = "Failed"
+        assert.isTrue(SolanaError.contains(error.logs, "already in use"), "Expected 'already in use' error when voting twice")
+      }
+      assert.strictEqual(should_fail, "Failed", "Should not be able to vote twice on the same poll");
+
+      pollData = await program.account.tweetPoll.fetch(tweet_poll_pkey);
+      assert.deepEqual(pollData.votes.map((v) => v.toNumber()), [0, 1, 0]);
+    });
+
+    it("Should fail to vote for an option that does not exist", async () => {
+      const [vote_pkey] = getTweetPollVoteAddress(charlie.publicKey, tweet_poll_pkey, program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.voteOnTweetPoll(3).accounts(
+          {
+            voter: charlie.publicKey,
+            tweetPollVote: vote_pkey,
+            tweetPoll: tweet_poll_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([charlie]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "InvalidPollOption", "Expected 'InvalidPollOption' error for out-of-range option");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Voting on a missing option should fail")
+    });
+  });
+
+  describe("Quote Tweet", async () => {
+    it("Should quote a tweet referencing the original and its content hash", async () => {
+      const [original_pkey] = getTweetAddress(topic_bob1, bob.publicKey, program.programId);
+      const commentary = "Bob is right about this one";
+      const [quote_pkey, quote_bump] = getQuoteAddress(commentary, alice.publicKey, original_pkey, program.programId);
+
+      await program.methods.quoteTweet(commentary).accounts(
+        {
+          quote: quote_pkey,
+          quoteAuthor: alice.publicKey,
+          originalTweet: original_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([alice]).rpc({ commitment: "confirmed" })
+
+      const quoteData = await program.account.quote.fetch(quote_pkey);
+      const expectedHash = crypto.createHash('sha256').update(content_bob1, 'utf-8').digest();
+      assert.strictEqual(quoteData.quoteAuthor.toString(), alice.publicKey.toString());
+      assert.strictEqual(quoteData.originalTweet.toString(), original_pkey.toString());
+      assert.isTrue(Buffer.from(quoteData.originalContentHash).equals(expectedHash), "Quote should store the hash of the original content");
+      assert.strictEqual(quoteData.content, commentary);
+      assert.strictEqual(quoteData.bump, quote_bump);
+
+      const tweetData = await program.account.tweet.fetch(original_pkey);
+      assert.strictEqual(tweetData.quoteCount.toString(), "1", "Original tweet quote count should be incremented");
+    });
+
+    it("Should fail to quote a tweet with commentary exceeding length limit", async () => {
+      const [original_pkey] = getTweetAddress(topic_bob1, bob.publicKey, program.programId);
+      const [quote_pkey] = getQuoteAddress(comment_alice1, alice.publicKey, original_pkey, program.programId);
+
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.quoteTweet(comment_alice1).accounts(
+          {
+            quote: quote_pkey,
+            quoteAuthor: alice.publicKey,
+            originalTweet: original_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([alice]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "CommentTooLong", "Expected 'CommentTooLong' error for commentary longer than 500 bytes");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Quoting with too long commentary should fail")
+    });
+  });
+
+
+  describe("Content Policy", async () => {
+    const [config_pkey, config_bump] = getConfigAddress(program.programId);
+
+    const tweetWith = async (topic: string, content: string) => {
+      const [tweet_pkey] = getTweetAddress(topic, charlie.publicKey, program.programId);
+      await program.methods.initialize(topic, content).accounts(
+        {
+          tweetAuthority: charlie.publicKey,
+          tweet: tweet_pkey,
+          config: config_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([charlie]).rpc({ commitment: "confirmed" })
+      return tweet_pkey;
+    };
+
+    const setPolicy = async (admin: anchor.web3.Keypair, policy: any) => {
+      await program.methods.setContentPolicy(policy).accounts(
+        {
+          admin: admin.publicKey,
+          config: config_pkey,
+        }
+      ).signers([admin]).rpc({ commitment: "confirmed" })
+    };
+
+    it("Should create the config with the Unicode policy and accept any content", async () => {
+      await program.methods.initConfig({ unicode: {} }, 500).accounts(
+        {
+          admin: bob.publicKey,
+          config: config_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([bob]).rpc({ commitment: "confirmed" })
+
+      const configData = await program.account.config.fetch(config_pkey);
+      assert.strictEqual(configData.admin.toString(), bob.publicKey.toString());
+      assert.deepEqual(configData.contentPolicy, { unicode: {} });
+      assert.strictEqual(configData.maxContentLen, 500);
+      assert.strictEqual(configData.bump, config_bump);
+
+      const tweet_pkey = await tweetWith("Policy unicode", unicode_content);
+      const tweetData = await program.account.tweet.fetch(tweet_pkey);
+      assert.strictEqual(tweetData.content, unicode_content);
+    });
+
+    it("Should accept ASCII content under the AsciiOnly policy", async () => {
+      await setPolicy(bob, { asciiOnly: {} });
+
+      const tweet_pkey = await tweetWith("Policy ascii", single_char_content);
+      const tweetData = await program.account.tweet.fetch(tweet_pkey);
+      assert.strictEqual(tweetData.content, single_char_content);
+    });
+
+    it("Should reject non-ASCII content under the AsciiOnly policy", async () => {
+      let should_fail = "This Should Fail"
+      try {
+        await tweetWith("Policy rejected", unicode_content);
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "NonAsciiContent", "Expected 'NonAsciiContent' error for non-ASCII content");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Non-ASCII content should be rejected")
+    });
+
+    it("Should fail to change the policy when not the config admin", async () => {
+      let should_fail = "This Should Fail"
+      try {
+        await setPolicy(alice, { unicode: {} });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "NotConfigAdmin", "Expected 'NotConfigAdmin' error for a non-admin signer");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Only the admin should change the policy")
+    });
+
+    after(async () => {
+      await setPolicy(bob, { unicode: {} });
+    });
+  });
+
+
+  describe("Content Length", async () => {
+    const [config_pkey, config_bump] = getConfigAddress(program.programId);
+    const max_len = 20;
+
+    const tweetWith = async (topic: string, content: string) => {
+      const [tweet_pkey] = getTweetAddress(topic, charlie.publicKey, program.programId);
+      await program.methods.initialize(topic, content).accounts(
+        {
+          tweetAuthority: charlie.publicKey,
+          tweet: tweet_pkey,
+          config: config_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([charlie]).rpc({ commitment: "confirmed" })
+      return tweet_pkey;
+    };
+
+    const setMaxLen = async (admin: anchor.web3.Keypair, len: number) => {
+      await program.methods.setMaxContentLen(len).accounts(
+        {
+          admin: admin.publicKey,
+          config: config_pkey,
+        }
+      ).signers([admin]).rpc({ commitment: "confirmed" })
+    };
+
+    it("Should accept content exactly at the configured length", async () => {
+      await setMaxLen(bob, max_len);
+      const configData = await program.account.config.fetch(config_pkey);
+      assert.strictEqual(configData.maxContentLen, max_len);
+
+      const content = "a".repeat(max_len);
+      const tweet_pkey = await tweetWith("Length at limit", content);
+      const tweetData = await program.account.tweet.fetch(tweet_pkey);
+      assert.strictEqual(tweetData.content, content);
+    });
+
+    it("Should reject content one byte over the configured length", async () => {
+      let should_fail = "This Should Fail"
+      try {
+        await tweetWith("Length over limit", "a".repeat(max_len + 1));
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "ContentTooLong", "Expected 'ContentTooLong' error for content over the configured length");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Content over the configured length should be rejected")
+    });
+
+    it("Should fail to set a length larger than the tweet account allows", async () => {
+      let should_fail = "This Should Fail"
+      try {
+        await setMaxLen(bob, 501);
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "InvalidMaxContentLength", "Expected 'InvalidMaxContentLength' error for a length over 500");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Max content length over 500 should be rejected")
+    });
+
+    it("Should fail to change the length when not the config admin", async () => {
+      let should_fail = "This Should Fail"
+      try {
+        await setMaxLen(alice, 100);
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "NotConfigAdmin", "Expected 'NotConfigAdmin' error for a non-admin signer");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Only the admin should change the max content length")
+    });
+
+    after(async () => {
+      await setMaxLen(bob, 500);
+    });
+  });
+
+
+  describe("Freeze Reactions", async () => {
+    const topic_frozen = "Frozen reactions";
+    const [tweet_pkey, tweet_bump] = getTweetAddress(topic_frozen, bob.publicKey, program.programId);
+
+    const react = async (author: anchor.web3.Keypair) => {
+      const [reaction_pkey] = getReactionAddress(author.publicKey, tweet_pkey, program.programId);
+      await program.methods.likeTweet().accounts(
+        {
+          reactionAuthor: author.publicKey,
+          tweetReaction: reaction_pkey,
+          tweet: tweet_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([author]).rpc({ commitment: "confirmed" })
+    };
+
+    const setFrozen = async (author: anchor.web3.Keypair, frozen: boolean) => {
+      const method = frozen ? program.methods.freezeReactions() : program.methods.unfreezeReactions();
+      await method.accounts(
+        {
+          tweetAuthor: author.publicKey,
+          tweet: tweet_pkey,
+        }
+      ).signers([author]).rpc({ commitment: "confirmed" })
+    };
+
+    before(async () => {
+      await program.methods.initialize(topic_frozen, content_bob1).accounts(
+        {
+          tweetAuthority: bob.publicKey,
+          tweet: tweet_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([bob]).rpc({ commitment: "confirmed" })
+      await react(charlie);
+    });
+
+    it("Should fail to freeze reactions when not the tweet author", async () => {
+      let should_fail = "This Should Fail"
+      try {
+        await setFrozen(alice, true);
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "NotTweetAuthor", "Expected 'NotTweetAuthor' error for a non-author signer");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Only the author should freeze reactions")
+    });
+
+    it("Should fail to react to a frozen tweet", async () => {
+      await setFrozen(bob, true);
+      const tweetData = await program.account.tweet.fetch(tweet_pkey);
+      assert.isTrue(tweetData.reactionsFrozen, "Tweet reactions should be frozen");
+
+      let should_fail = "This Should Fail"
+      try {
+        await react(alice);
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "ReactionsFrozen", "Expected 'ReactionsFrozen' error when reacting to a frozen tweet");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Reacting to a frozen tweet should fail")
+    });
+
+    it("Should still remove a reaction from a frozen tweet", async () => {
+      const [reaction_pkey] = getReactionAddress(charlie.publicKey, tweet_pkey, program.programId);
+      await program.methods.reactionRemove().accounts(
+        {
+          reactionAuthor: charlie.publicKey,
+          tweetReaction: reaction_pkey,
+          tweet: tweet_pkey,
+        }
+      ).signers([charlie]).rpc({ commitment: "confirmed" })
+
+      await checkTweet(
+        program, tweet_pkey, bob.publicKey, topic_frozen, content_bob1, 0, 0, tweet_bump
+      )
+    });
+
+    it("Should accept reactions again after unfreezing", async () => {
+      await setFrozen(bob, false);
+      await react(alice);
+
+      await checkTweet(
+        program, tweet_pkey, bob.publicKey, topic_frozen, content_bob1, 1, 0, tweet_bump
+      )
+    });
+  });
+
+
+  describe("Reaction Cooldown", async () => {
+    const topic_cooldown = "Cooldown topic";
+    const [tweet_pkey] = getTweetAddress(topic_cooldown, bob.publicKey, program.programId);
+    const [reaction_pkey] = getReactionAddress(charlie.publicKey, tweet_pkey, program.programId);
+    const [cooldown_pkey, cooldown_bump] = getReactionCooldownAddress(charlie.publicKey, tweet_pkey, program.programId);
+
+    const like = async () => {
+      await program.methods.likeTweet().accounts(
+        {
+          reactionAuthor: charlie.publicKey,
+          tweetReaction: reaction_pkey,
+          tweet: tweet_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([charlie]).rpc({ commitment: "confirmed" })
+    };
+
+    const unlike = async () => {
+      await program.methods.reactionRemove().accounts(
+        {
+          reactionAuthor: charlie.publicKey,
+          tweetReaction: reaction_pkey,
+          tweet: tweet_pkey,
+        }
+      ).signers([charlie]).rpc({ commitment: "confirmed" })
+    };
+
+    before(async () => {
+      await program.methods.initialize(topic_cooldown, content_bob1).accounts(
+        {
+          tweetAuthority: bob.publicKey,
+          tweet: tweet_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([bob]).rpc({ commitment: "confirmed" })
+    });
+
+    it("Should keep the cooldown account after the reaction is removed", async () => {
+      await like();
+      await unlike();
+
+      const cooldownData = await program.account.reactionCooldown.fetch(cooldown_pkey);
+      assert.strictEqual(cooldownData.reactionAuthor.toString(), charlie.publicKey.toString());
+      assert.strictEqual(cooldownData.parentTweet.toString(), tweet_pkey.toString());
+      assert.isTrue(cooldownData.lastRemovedTs.toNumber() > 0, "Removal time should be recorded");
+      assert.strictEqual(cooldownData.bump, cooldown_bump);
+    });
+
+    it("Should fail to react again right after removing a reaction", async () => {
+      let should_fail = "This Should Fail"
+      try {
+        await like();
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "ReactionCooldownActive", "Expected 'ReactionCooldownActive' error for a rapid re-reaction");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Rapid toggling should be blocked")
+    });
+
+    it("Should allow reacting again once the cooldown has passed", async () => {
+      await sleep((REACTION_COOLDOWN + 2) * 1000);
+      await like();
+
+      const tweetData = await program.account.tweet.fetch(tweet_pkey);
+      assert.strictEqual(tweetData.likes.toString(), "1", "The spaced re-reaction should count");
+    });
+  });
+
+
+  describe("Transfer Tweet", async () => {
+    const topic_transfer = "Transferred tweet";
+    const [tweet_pkey, tweet_bump] = getTweetAddress(topic_transfer, bob.publicKey, program.programId);
+    const [reaction_pkey] = getReactionAddress(charlie.publicKey, tweet_pkey, program.programId);
+
+    const transfer = async (author: anchor.web3.Keypair, new_author: PublicKey) => {
+      await program.methods.transferTweet(new_author).accounts(
+        {
+          tweetAuthor: author.publicKey,
+          tweet: tweet_pkey,
+        }
+      ).signers([author]).rpc({ commitment: "confirmed" })
+    };
+
+    const freeze = async (author: anchor.web3.Keypair) => {
+      await program.methods.freezeReactions().accounts(
+        {
+          tweetAuthor: author.publicKey,
+          tweet: tweet_pkey,
+        }
+      ).signers([author]).rpc({ commitment: "confirmed" })
+    };
+
+    before(async () => {
+      await program.methods.initialize(topic_transfer, content_bob1).accounts(
+        {
+          tweetAuthority: bob.publicKey,
+          tweet: tweet_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([bob]).rpc({ commitment: "confirmed" })
+      await program.methods.likeTweet().accounts(
+        {
+          reactionAuthor: charlie.publicKey,
+          tweetReaction: reaction_pkey,
+          tweet: tweet_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([charlie]).rpc({ commitment: "confirmed" })
+    });
+
+    it("Should fail to transfer a tweet when not the tweet author", async () => {
+      let should_fail = "This Should Fail"
+      try {
+        await transfer(alice, alice.publicKey);
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "NotTweetAuthor", "Expected 'NotTweetAuthor' error for a non-author signer");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Only the author should transfer the tweet")
+    });
+
+    it("Should transfer the tweet to a new author", async () => {
+      await transfer(bob, alice.publicKey);
+
+      const tweetData = await program.account.tweet.fetch(tweet_pkey);
+      assert.strictEqual(tweetData.tweetAuthor.toString(), alice.publicKey.toString());
+      assert.strictEqual(tweetData.topic, topic_transfer);
+      assert.strictEqual(tweetData.likes.toString(), "1");
+      assert.strictEqual(tweetData.bump, tweet_bump);
+    });
+
+    it("Should fail for the previous author to manage the tweet", async () => {
+      let should_fail = "This Should Fail"
+      try {
+        await freeze(bob);
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "NotTweetAuthor", "Expected 'NotTweetAuthor' error for the previous author");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "The previous author should no longer manage the tweet")
+    });
+
+    it("Should let the new author manage the tweet", async () => {
+      await freeze(alice);
+
+      const [tweet_poll_pkey] = getTweetPollAddress(tweet_pkey, program.programId);
+      await program.methods.createTweetPoll(["Yes", "No"]).accounts(
+        {
+          tweetAuthor: alice.publicKey,
+          tweetPoll: tweet_poll_pkey,
+          tweet: tweet_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([alice]).rpc({ commitment: "confirmed" })
+
+      const tweetData = await program.account.tweet.fetch(tweet_pkey);
+      assert.isTrue(tweetData.reactionsFrozen, "New author should be able to freeze reactions");
+      const pollData = await program.account.tweetPoll.fetch(tweet_poll_pkey);
+      assert.strictEqual(pollData.parentTweet.toString(), tweet_pkey.toString());
+    });
+
+    it("Should keep existing reactions valid after a transfer", async () => {
+      await program.methods.reactionRemove().accounts(
+        {
+          reactionAuthor: charlie.publicKey,
+          tweetReaction: reaction_pkey,
+          tweet: tweet_pkey,
+        }
+      ).signers([charlie]).rpc({ commitment: "confirmed" })
+
+      const tweetData = await program.account.tweet.fetch(tweet_pkey);
+      assert.strictEqual(tweetData.likes.toString(), "0");
+    });
+  });
+
+
+  describe("Followers Only", async () => {
+    const topic_followers = "Followers only";
+    const [tweet_pkey] = getTweetAddress(topic_followers, bob.publicKey, program.programId);
+    const [follow_charlie_pkey] = getFollowAddress(charlie.publicKey, bob.publicKey, program.programId);
+
+    const react = async (author: anchor.web3.Keypair, follow: PublicKey | null) => {
+      const [reaction_pkey] = getReactionAddress(author.publicKey, tweet_pkey, program.programId);
+      await program.methods.likeTweet().accounts(
+        {
+          reactionAuthor: author.publicKey,
+          tweetReaction: reaction_pkey,
+          tweet: tweet_pkey,
+          follow: follow,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([author]).rpc({ commitment: "confirmed" })
+    };
+
+    before(async () => {
+      await program.methods.initialize(topic_followers, content_bob1).accounts(
+        {
+          tweetAuthority: bob.publicKey,
+          tweet: tweet_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([bob]).rpc({ commitment: "confirmed" })
+      await program.methods.follow().accounts(
+        {
+          follower: charlie.publicKey,
+          followee: bob.publicKey,
+          follow: follow_charlie_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([charlie]).rpc({ commitment: "confirmed" })
+    });
+
+    it("Should create tweets as public and record follows", async () => {
+      const tweetData = await program.account.tweet.fetch(tweet_pkey);
+      assert.deepEqual(tweetData.visibility, { public: {} });
+
+      const followData = await program.account.follow.fetch(follow_charlie_pkey);
+      assert.strictEqual(followData.follower.toString(), charlie.publicKey.toString());
+      assert.strictEqual(followData.followee.toString(), bob.publicKey.toString());
+    });
+
+    it("Should fail to change visibility when not the tweet author", async () => {
+      let should_fail = "This Should Fail"
+      try {
+        await program.methods.setTweetVisibility({ followersOnly: {} }).accounts(
+          {
+            tweetAuthor: alice.publicKey,
+            tweet: tweet_pkey,
+          }
+        ).signers([alice]).rpc({ commitment: "confirmed" })
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "NotTweetAuthor", "Expected 'NotTweetAuthor' error for a non-author signer");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Only the author should change the visibility")
+    });
+
+    it("Should let a follower react to a followers-only tweet", async () => {
+      await program.methods.setTweetVisibility({ followersOnly: {} }).accounts(
+        {
+          tweetAuthor: bob.publicKey,
+          tweet: tweet_pkey,
+        }
+      ).signers([bob]).rpc({ commitment: "confirmed" })
+
+      await react(charlie, follow_charlie_pkey);
+      const tweetData = await program.account.tweet.fetch(tweet_pkey);
+      assert.deepEqual(tweetData.visibility, { followersOnly: {} });
+      assert.strictEqual(tweetData.likes.toString(), "1");
+    });
+
+    it("Should fail for a non-follower to react to a followers-only tweet", async () => {
+      for (const follow of [null, follow_charlie_pkey]) {
+        let should_fail = "This Should Fail"
+        try {
+          await react(alice, follow);
+        } catch (error) {
+          const err = anchor.AnchorError.parse(error.logs);
+          assert.strictEqual(err.error.errorCode.code, "NotAFollower", "Expected 'NotAFollower' error for a non-follower");
+          should_fail = "Failed"
+        }
+        assert.strictEqual(should_fail, "Failed", "A non-follower should not react to a followers-only tweet")
+      }
+    });
+
+    it("Should close the follow account on unfollow", async () => {
+      await program.methods.unfollow().accounts(
+        {
+          follower: charlie.publicKey,
+          follow: follow_charlie_pkey,
+        }
+      ).signers([charlie]).rpc({ commitment: "confirmed" })
+
+      const followInfo = await provider.connection.getAccountInfo(follow_charlie_pkey);
+      assert.isNull(followInfo, "Follow account should be closed");
+    });
+  });
+
+
+  describe("Engagement Score", async () => {
+    const dave = anchor.web3.Keypair.generate();
+    const topics = ["Engagement one", "Engagement two"];
+    const tweets = topics.map((topic) => getTweetAddress(topic, dave.publicKey, program.programId)[0]);
+
+    const react = async (author: anchor.web3.Keypair, tweet: PublicKey, like: boolean) => {
+      const [reaction_pkey] = getReactionAddress(author.publicKey, tweet, program.programId);
+      const method = like ? program.methods.likeTweet() : program.methods.dislikeTweet();
+      await method.accounts(
+        {
+          reactionAuthor: author.publicKey,
+          tweetReaction: reaction_pkey,
+          tweet: tweet,
+          systemProgram: anchor.web3.SystemProgram.programId
+        }
+      ).signers([author]).rpc({ commitment: "confirmed" })
+    };
+
+    const score = async (user: PublicKey, tweet_keys: PublicKey[]) => {
+      const sim = await program.methods.computeEngagement().accounts(
+        {
+          user: user,
+        }
+      ).remainingAccounts(
+        tweet_keys.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false }))
+      ).simulate();
+      const event = sim.events.find((e) => e.name === "engagementScore");
+      assert.isDefined(event, "Expected an EngagementScore event");
+      return event.data;
+    };
+
+    before(async () => {
+      await airdrop(provider.connection, dave.publicKey);
+      for (let i = 0; i < topics.length; i++) {
+        await program.methods.initialize(topics[i], content_bob1).accounts(
+          {
+            tweetAuthority: dave.publicKey,
+            tweet: tweets[i],
+            systemProgram: anchor.web3.SystemProgram.programId
+          }
+        ).signers([dave]).rpc({ commitment: "confirmed" })
+      }
+      await react(alice, tweets[0], true);
+      await react(charlie, tweets[0], true);
+      await react(alice, tweets[1], false);
+    });
+
+    it("Should weight likes and dislikes across the user's tweets", async () => {
+      const data = await score(dave.publicKey, tweets);
+      assert.strictEqual(data.user.toString(), dave.publicKey.toString());
+      assert.strictEqual(data.score.toString(), "3");
+    });
+
+    it("Should score a user with no tweets as zero", async () => {
+      const data = await score(dave.publicKey, []);
+      assert.strictEqual(data.score.toString(), "0");
+    });
+
+    it("Should fail when a tweet belongs to someone else", async () => {
+      let should_fail = "This Should Fail"
+      try {
+        await score(alice.publicKey, tweets);
+      } catch (error) {
+        assert.isTrue(SolanaError.contains(error.simulationResponse?.logs ?? error.logs, "NotTweetAuthor"), "Expected 'NotTweetAuthor' error for a foreign tweet");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "Only the user's own tweets should be scored")
+    });
+
+    it("Should fail when a tweet is passed twice", async () => {
+      let should_fail = "This Should Fail"
+      try {
+        await score(dave.publicKey, [tweets[0], tweets[0]]);
+      } catch (error) {
+        assert.isTrue(SolanaError.contains(error.simulationResponse?.logs ?? error.logs, "DuplicateTweetAccount"), "Expected 'DuplicateTweetAccount' error for a repeated tweet");
+        should_fail = "Failed"
+      }
+      assert.strictEqual(should_fail, "Failed", "A tweet should only be scored once")
+    });
+  });
+
 });
 
 
@@ -955,3 +1749,61 @@ async function checkComment(
     assert.strictEqual(commentnData.bump.toString(), bump.toString(), `Comment bump should be ${bump} but was ${commentnData.bump}`)
   }
 }
+
+function getTweetPollAddress(tweet: PublicKey, programID: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(TWEET_POLL_SEED),
+      tweet.toBuffer(),
+    ], programID);
+}
+
+function getTweetPollVoteAddress(voter: PublicKey, tweet_poll: PublicKey, programID: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(TWEET_POLL_VOTE_SEED),
+      voter.toBuffer(),
+      tweet_poll.toBuffer(),
+    ], programID);
+}
+
+function getQuoteAddress(comment: string, author: PublicKey, original_tweet: PublicKey, programID: PublicKey) {
+  const content_seed = crypto.createHash('sha256').update(comment, 'utf-8').digest();
+
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(QUOTE_SEED),
+      author.toBuffer(),
+      content_seed,
+      original_tweet.toBuffer(),
+    ], programID);
+}
+
+function getConfigAddress(programID: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(CONFIG_SEED),
+    ], programID);
+}
+
+function getReactionCooldownAddress(author: PublicKey, tweet: PublicKey, programID: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(REACTION_COOLDOWN_SEED),
+      author.toBuffer(),
+      tweet.toBuffer(),
+    ], programID);
+}
+
+function sleep(ms: number) {
+  return new Promise((resolve) => setTimeout(resolve, ms));
+}
+
+function getFollowAddress(follower: PublicKey, followee: PublicKey, programID: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(FOLLOW_SEED),
+      follower.toBuffer(),
+      followee.toBuffer(),
+    ], programID);
+}